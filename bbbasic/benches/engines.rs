@@ -0,0 +1,61 @@
+//! Runs a few programs on both engines and compares their timings. It only
+//! reports them, timings depend on the machine. Under `cargo test` each
+//! program runs once, checking that both engines print the same.
+
+use std::time::{Duration, Instant};
+use bbbasic::{Engine, Interpreter, MemoryHost};
+
+const MANDELBROT: &str = include_str!("../../bbcli/demo/mandelbrot.bbb");
+
+const INTEGERS: &str = "s% = 0
+FOR i% = 1 TO 200000
+    s% = s% + i% * 2 - 1
+NEXT i%
+PRINT s%
+";
+
+const PROCEDURES: &str = "total = 0
+FOR i% = 1 TO 20000
+    PROCadd(i% / 2)
+NEXT i%
+PRINT total
+END
+
+DEF PROCadd(n)
+total = total + n
+ENDPROC
+";
+
+const RUNS: u32 = 20;
+
+/// Fastest of `runs` runs, which is the least disturbed by other processes.
+fn time(code: &str, engine: Engine, runs: u32) -> (Duration, String) {
+    let mut fastest = Duration::MAX;
+    let mut output = String::new();
+
+    for _ in 0..runs {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+
+        let start = Instant::now();
+        interpreter.run(code).expect("Program failed");
+        fastest = fastest.min(start.elapsed());
+
+        output = interpreter.into_host().output;
+    }
+
+    (fastest, output)
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, `cargo test` doesn't.
+    let runs = if std::env::args().any(|a| a == "--bench") { RUNS } else { 1 };
+
+    for (name, code) in [("mandelbrot", MANDELBROT), ("integers", INTEGERS), ("procedures", PROCEDURES)] {
+        let (tree, tree_output) = time(code, Engine::TreeWalker, runs);
+        let (bytecode, bytecode_output) = time(code, Engine::Bytecode, runs);
+        assert_eq!(tree_output, bytecode_output, "{} prints something else on the VM", name);
+
+        let speedup = tree.as_secs_f64() / bytecode.as_secs_f64();
+        println!("{:<12} tree-walker {:>10.2?}  bytecode {:>10.2?}  {:.2}x", name, tree, bytecode, speedup);
+    }
+}
@@ -0,0 +1,579 @@
+//! Program tree the interpreter runs on. It is lowered from the parse tree
+//! once, before the program runs, and refers to variables by slot.
+
+use crate::error::BasicError;
+use crate::parser;
+use crate::parser::{BoolOperator, InputPromptSep, NumberLiteral_value, NumericVariable_name, NumericVariable_type_dem, OpeninFunction_name, OpenoutFunction_name, PrintListItem_value};
+use crate::scope::{DataType, Slot, Symbols};
+use crate::value::Value;
+use peginator::PegParser;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variable {
+    pub slot: Slot,
+    pub data_type: DataType,
+}
+
+/// A number literal as written in the source.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumberLiteral {
+    Float(String),
+    Integer(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expression {
+    Number(NumberLiteral),
+    /// A number known before the program runs: a parsed literal or a folded
+    /// sub-expression. Never a string or boolean.
+    Constant(Value),
+    Variable(Variable),
+    Add(Box<Expression>, Box<Expression>),
+    Sub(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+    Div(Box<Expression>, Box<Expression>),
+    Time,
+    Rnd(Option<Box<Expression>>),
+    Get,
+    Inkey(Box<Expression>),
+    Openin(StringValue),
+    Openout(StringValue),
+    Bget(Box<Expression>),
+    Eof(Box<Expression>),
+    /// `FNname(arguments)`, the name without `FN`. Only native functions
+    /// can be called, see [`Interpreter::register_fn`](crate::Interpreter::register_fn).
+    Function { name: String, arguments: Vec<Operand> },
+    /// The value in memory at the address, `a%?b` is `?(a% + b)`.
+    Indirect(Indirection, Box<Expression>),
+}
+
+/// What an indirection operator reads or writes, see
+/// [`Memory::peek`](crate::memory::Memory::peek).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Indirection {
+    /// `?`
+    Byte,
+    /// `!`
+    Word,
+    /// `|`
+    Float,
+    /// `$`
+    String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StringValue {
+    Literal(String),
+    Variable(Variable),
+}
+
+/// Operand of a comparison or item of a `PRINT` list.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operand {
+    Number(Expression),
+    String(StringValue),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Lower,
+    LowerEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Condition {
+    Or(Vec<Condition>),
+    And(Vec<Condition>),
+    Compare(Operand, Comparison, Operand),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrintItem {
+    pub value: Operand,
+    pub newline: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForStatement {
+    pub variable: Variable,
+    pub start: Expression,
+    pub target: Expression,
+    pub step: Option<Expression>,
+    pub body: Block,
+    pub next: Next,
+}
+
+/// `NEXT` at the end of a `FOR` loop.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Next {
+    /// The variable after `NEXT`, if any. It isn't checked when the program
+    /// runs, see [`check`](crate::checker::check).
+    pub variable: Option<Variable>,
+    pub position: Position,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Statement {
+    Assignment(Variable, Expression),
+    StringAssignment(Variable, String),
+    Print(Vec<PrintItem>),
+    Input { prompt: String, variable: Variable },
+    For(ForStatement),
+    ExitFor,
+    While { condition: Condition, body: Block },
+    ExitWhile,
+    If { condition: Condition, then_block: Block, else_block: Option<Block> },
+    End,
+    SetTime(Expression),
+    Cls,
+    Vdu(Vec<Expression>),
+    Plot { mode: Expression, x: Expression, y: Expression },
+    Move { x: Expression, y: Expression },
+    Draw { x: Expression, y: Expression },
+    Sound { channel: Expression, amplitude: Expression, pitch: Expression, duration: Expression },
+    Bput { channel: Expression, value: Expression },
+    Close(Expression),
+    /// `DIM variable size`, which reserves `size + 1` bytes of memory.
+    Dim { variable: Variable, size: Expression },
+    /// `?address = value` and the other indirection operators.
+    Poke { indirection: Indirection, address: Expression, value: Operand },
+    /// `PROCname(arguments)`, the name without `PROC`.
+    Call { name: String, arguments: Vec<Operand> },
+    /// `ENDPROC`
+    Return,
+    Trace(TraceMode),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TraceMode {
+    On,
+    Off,
+    Procedures,
+    /// `TRACE n`, lines below `n`.
+    Below(Expression),
+}
+
+/// Where a statement starts in the source, 1-based. Line 0 means unknown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Located {
+    pub position: Position,
+    pub statement: Statement,
+}
+
+pub type Block = Vec<Located>;
+
+/// `DEF PROCname(parameters)` and the statements up to the next `DEF`.
+/// Parameters are local to the procedure, they get back their old values
+/// once it returns.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Procedure {
+    pub name: String,
+    pub parameters: Vec<Variable>,
+    pub body: Block,
+    pub position: Position,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Program {
+    pub body: Block,
+    pub procedures: Vec<Procedure>,
+}
+
+impl Program {
+    /// Lowers a parsed program, resolving its variables against `symbols`.
+    /// The positions of the statements are unknown, see
+    /// [`Program::resolve_source`].
+    pub fn resolve(program: &parser::Program, symbols: &mut Symbols) -> Program {
+        lower(program, &Lines::default(), symbols)
+    }
+
+    /// Like [`Program::resolve`], `code` being the source `program` was
+    /// parsed from, which gives every statement its position.
+    pub fn resolve_source(program: &parser::Program, code: &str, symbols: &mut Symbols) -> Program {
+        lower(program, &Lines::new(code), symbols)
+    }
+
+    /// Like [`Program::resolve_source`], line `i` of `code` being numbered
+    /// `numbers[i - 1]`, like the lines of a [`Listing`](crate::Listing).
+    pub fn resolve_numbered(program: &parser::Program, code: &str, numbers: &[u32], symbols: &mut Symbols) -> Program {
+        let mut lines = Lines::new(code);
+        lines.numbers = numbers.iter().map(|n| *n as usize).collect();
+
+        lower(program, &lines, symbols)
+    }
+
+    /// Parses `code` and lowers it with positions, its variables resolved
+    /// against new symbols.
+    pub fn parse_source(code: &str) -> Result<(Program, Symbols), BasicError> {
+        let parsed = parser::Program::parse(code).map_err(|e| BasicError::from_parse_error(code, &e))?;
+
+        let mut symbols = Symbols::new();
+        let program = Program::resolve_source(&parsed, code, &mut symbols);
+
+        Ok((program, symbols))
+    }
+
+    /// The procedure called `name`, the first one if it is defined twice.
+    pub fn procedure(&self, name: &str) -> Option<&Procedure> {
+        self.procedures.iter().find(|p| p.name == name)
+    }
+}
+
+fn lower(program: &parser::Program, lines: &Lines, symbols: &mut Symbols) -> Program {
+    Program {
+        body: block(&program.body, lines, symbols),
+        procedures: program.procedures.iter().map(|p| Procedure {
+            name: p.name.to_string(),
+            parameters: p.parameters.iter().map(|v| match v {
+                parser::Parameter::NumericVariable(v) => numeric_variable(v, symbols),
+                parser::Parameter::StringVariable(v) => string_variable(v, symbols)
+            }).collect(),
+            body: block(&p.body, lines, symbols),
+            position: lines.position(p.position.start),
+        }).collect(),
+    }
+}
+
+/// Start of every line, to turn byte offsets into positions.
+#[derive(Default)]
+struct Lines {
+    starts: Vec<usize>,
+    /// Number of every line, counting from 1 if empty.
+    numbers: Vec<usize>,
+}
+
+impl Lines {
+    fn new(code: &str) -> Lines {
+        let starts = std::iter::once(0)
+            .chain(code.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Lines { starts, numbers: Vec::new() }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        if self.starts.is_empty() {
+            return Position::default();
+        }
+
+        let line = self.starts.partition_point(|&s| s <= offset);
+        let column = offset - self.starts[line - 1] + 1;
+
+        Position { line: self.numbers.get(line - 1).copied().unwrap_or(line), column }
+    }
+}
+
+impl Expression {
+    pub fn resolve(expression: &parser::Expression, symbols: &mut Symbols) -> Expression {
+        match expression {
+            parser::Expression::Add(a) => Expression::Add(Box::new(Expression::resolve(&a.left, symbols)), Box::new(term(&a.right, symbols))),
+            parser::Expression::Sub(s) => Expression::Sub(Box::new(Expression::resolve(&s.left, symbols)), Box::new(term(&s.right, symbols))),
+            parser::Expression::Term(t) => term(t, symbols)
+        }
+    }
+}
+
+impl Condition {
+    pub fn resolve(condition: &parser::BoolDisjunction, symbols: &mut Symbols) -> Condition {
+        let mut conjunctions: Vec<Condition> = condition.conjunction.iter()
+            .map(|c| conjunction(c, symbols))
+            .collect();
+
+        match conjunctions.len() {
+            1 => conjunctions.remove(0),
+            _ => Condition::Or(conjunctions)
+        }
+    }
+}
+
+fn conjunction(conjunction: &parser::BoolConjunction, symbols: &mut Symbols) -> Condition {
+    let mut terms: Vec<Condition> = conjunction.term.iter()
+        .map(|t| bool_term(t, symbols))
+        .collect();
+
+    match terms.len() {
+        1 => terms.remove(0),
+        _ => Condition::And(terms)
+    }
+}
+
+fn bool_term(term: &parser::BoolTerm, symbols: &mut Symbols) -> Condition {
+    if let Some(e) = &term.expression {
+        return Condition::resolve(&e.disjunction, symbols);
+    }
+
+    match &term.condition {
+        // The grammar always gives one of both.
+        None => Condition::Or(Vec::new()),
+        Some(c) => {
+            let comparison = match c.op {
+                BoolOperator::BoolOpEqual(_) => Comparison::Equal,
+                BoolOperator::BoolOpNotEqual(_) => Comparison::NotEqual,
+                BoolOperator::BoolOpGreater(_) => Comparison::Greater,
+                BoolOperator::BoolOpGreaterEqual(_) => Comparison::GreaterEqual,
+                BoolOperator::BoolOpLower(_) => Comparison::Lower,
+                BoolOperator::BoolOpLowerEqual(_) => Comparison::LowerEqual
+            };
+
+            Condition::Compare(bool_operand(&c.left, symbols), comparison, bool_operand(&c.right, symbols))
+        }
+    }
+}
+
+fn bool_operand(operand: &parser::BoolOperand, symbols: &mut Symbols) -> Operand {
+    match operand {
+        parser::BoolOperand::Expression(e) => Operand::Number(Expression::resolve(e, symbols)),
+        parser::BoolOperand::StringLiteral(s) => Operand::String(StringValue::Literal(s.body.to_string())),
+        parser::BoolOperand::StringVariable(v) => Operand::String(StringValue::Variable(string_variable(v, symbols)))
+    }
+}
+
+fn term(term: &parser::Term, symbols: &mut Symbols) -> Expression {
+    match term {
+        parser::Term::Mul(m) => Expression::Mul(Box::new(self::term(&m.left, symbols)), Box::new(factor(&m.right, symbols))),
+        parser::Term::Div(d) => Expression::Div(Box::new(self::term(&d.left, symbols)), Box::new(factor(&d.right, symbols))),
+        parser::Term::Factor(f) => factor(f, symbols)
+    }
+}
+
+fn factor(factor: &parser::Factor, symbols: &mut Symbols) -> Expression {
+    match factor {
+        parser::Factor::Group(g) => Expression::resolve(&g.body, symbols),
+        parser::Factor::NumberLiteral(n) => Expression::Number(match &n.value {
+            NumberLiteral_value::FloatLiteral(f) => NumberLiteral::Float(f.body.to_string()),
+            NumberLiteral_value::IntegerLiteral(i) => NumberLiteral::Integer(i.body.to_string())
+        }),
+        parser::Factor::NumericVariable(v) => Expression::Variable(numeric_variable(v, symbols)),
+        parser::Factor::TimeFunction(_) => Expression::Time,
+        parser::Factor::RndFunction(r) => Expression::Rnd(r.argument.as_ref().map(|a| Box::new(Expression::resolve(a, symbols)))),
+        parser::Factor::GetFunction(_) => Expression::Get,
+        parser::Factor::InkeyFunction(i) => Expression::Inkey(Box::new(Expression::resolve(&i.argument, symbols))),
+        parser::Factor::OpeninFunction(o) => Expression::Openin(match &o.name {
+            OpeninFunction_name::StringLiteral(s) => StringValue::Literal(s.body.to_string()),
+            OpeninFunction_name::StringVariable(v) => StringValue::Variable(string_variable(v, symbols))
+        }),
+        parser::Factor::OpenoutFunction(o) => Expression::Openout(match &o.name {
+            OpenoutFunction_name::StringLiteral(s) => StringValue::Literal(s.body.to_string()),
+            OpenoutFunction_name::StringVariable(v) => StringValue::Variable(string_variable(v, symbols))
+        }),
+        parser::Factor::BgetFunction(b) => Expression::Bget(Box::new(self::factor(&b.channel, symbols))),
+        parser::Factor::EofFunction(e) => Expression::Eof(Box::new(self::factor(&e.channel, symbols))),
+        parser::Factor::FnCall(c) => Expression::Function {
+            name: c.name.to_string(),
+            arguments: c.arguments.iter().map(|a| bool_operand(a, symbols)).collect(),
+        },
+        parser::Factor::Indirection(i) => {
+            let (indirection, address) = self::indirection(i, symbols);
+            Expression::Indirect(indirection, Box::new(address))
+        }
+    }
+}
+
+/// Operator and address of an indirection, with the base of `a%?b` added
+/// to the offset.
+fn indirection(indirection: &parser::Indirection, symbols: &mut Symbols) -> (Indirection, Expression) {
+    match indirection {
+        parser::Indirection::UnaryIndirection(u) => {
+            let kind = match u.op {
+                parser::IndirectionOperator::ByteIndirection(_) => Indirection::Byte,
+                parser::IndirectionOperator::WordIndirection(_) => Indirection::Word,
+                parser::IndirectionOperator::FloatIndirection(_) => Indirection::Float,
+                parser::IndirectionOperator::StringIndirection(_) => Indirection::String
+            };
+
+            (kind, factor(&u.address, symbols))
+        }
+
+        parser::Indirection::DyadicIndirection(d) => {
+            let kind = match d.op {
+                parser::DyadicOperator::ByteIndirection(_) => Indirection::Byte,
+                parser::DyadicOperator::WordIndirection(_) => Indirection::Word
+            };
+            let base = Expression::Variable(numeric_variable(&d.base, symbols));
+
+            (kind, Expression::Add(Box::new(base), Box::new(factor(&d.offset, symbols))))
+        }
+    }
+}
+
+fn numeric_variable(variable: &parser::NumericVariable, symbols: &mut Symbols) -> Variable {
+    let data_type = match &variable.type_dem {
+        None => DataType::Float,
+        Some(d) => match d {
+            NumericVariable_type_dem::ByteDenominator(_) => DataType::Byte,
+            NumericVariable_type_dem::FloatDenominator(_) => DataType::Float,
+            NumericVariable_type_dem::IntegerDenominator(_) => DataType::Integer
+        }
+    };
+
+    let name = match &variable.name {
+        NumericVariable_name::ResidentName(n) | NumericVariable_name::VariableName(n) => n
+    };
+
+    Variable { slot: symbols.resolve(name, data_type), data_type }
+}
+
+fn string_variable(variable: &parser::StringVariable, symbols: &mut Symbols) -> Variable {
+    Variable { slot: symbols.resolve(&variable.name, DataType::String), data_type: DataType::String }
+}
+
+fn block(block: &parser::Block, lines: &Lines, symbols: &mut Symbols) -> Block {
+    block.statements.iter().map(|s| Located {
+        position: lines.position(s.position.start),
+        statement: statement(&s.statement, lines, symbols),
+    }).collect()
+}
+
+fn statement(statement: &parser::Statement, lines: &Lines, symbols: &mut Symbols) -> Statement {
+    use parser::Statement as S;
+
+    match statement {
+        S::Assignment(a) => {
+            let variable = numeric_variable(&a.variable, symbols);
+            Statement::Assignment(variable, Expression::resolve(&a.value, symbols))
+        }
+
+        S::StringAssignment(a) => Statement::StringAssignment(string_variable(&a.variable, symbols), a.value.body.to_string()),
+
+        S::PrintStatement(p) => Statement::Print(p.list.iter().map(|item| PrintItem {
+            value: match &item.value {
+                PrintListItem_value::Expression(e) => Operand::Number(Expression::resolve(e, symbols)),
+                PrintListItem_value::StringLiteral(s) => Operand::String(StringValue::Literal(s.body.to_string())),
+                PrintListItem_value::StringVariable(v) => Operand::String(StringValue::Variable(string_variable(v, symbols)))
+            },
+            newline: item.sep.is_none(),
+        }).collect()),
+
+        S::InputStatement(i) => {
+            let prompt = match &i.prompt {
+                None => "?".to_string(),
+                Some(p) => match p.sep {
+                    Some(InputPromptSep::InputAskSep(_)) => format!("{}?", p.text.body),
+                    _ => p.text.body.to_string()
+                }
+            };
+
+            let variable = match &i.variable {
+                parser::InputStatement_variable::StringVariable(v) => string_variable(v, symbols),
+                parser::InputStatement_variable::NumericVariable(v) => numeric_variable(v, symbols)
+            };
+
+            Statement::Input { prompt, variable }
+        }
+
+        S::ForStatement(f) => {
+            let variable = numeric_variable(&f.assignment.variable, symbols);
+            let start = Expression::resolve(&f.assignment.value, symbols);
+            let target = Expression::resolve(&f.target, symbols);
+            let step = f.step.as_ref().map(|s| Expression::resolve(&s.value, symbols));
+            let body = self::block(&f.body, lines, symbols);
+            let next = Next {
+                variable: f.next.variable.as_ref().map(|v| numeric_variable(v, symbols)),
+                position: lines.position(f.next.position.start),
+            };
+
+            Statement::For(ForStatement { variable, start, target, step, body, next })
+        }
+
+        S::ExitForStatement(_) => Statement::ExitFor,
+
+        S::WhileStatement(w) => Statement::While {
+            condition: Condition::resolve(&w.condition, symbols),
+            body: self::block(&w.body, lines, symbols),
+        },
+
+        S::ExitWhileStatement(_) => Statement::ExitWhile,
+
+        S::IfStatement(i) => Statement::If {
+            condition: Condition::resolve(&i.condition, symbols),
+            then_block: self::block(&i.then_block, lines, symbols),
+            else_block: i.else_block.as_ref().map(|b| self::block(b, lines, symbols)),
+        },
+
+        S::EndStatement(_) => Statement::End,
+        S::TimeAssignment(t) => Statement::SetTime(Expression::resolve(&t.value, symbols)),
+        S::ClsStatement(_) => Statement::Cls,
+        S::VduStatement(v) => Statement::Vdu(v.values.iter().map(|e| Expression::resolve(e, symbols)).collect()),
+
+        S::PlotStatement(p) => Statement::Plot {
+            mode: Expression::resolve(&p.mode, symbols),
+            x: Expression::resolve(&p.x, symbols),
+            y: Expression::resolve(&p.y, symbols),
+        },
+
+        S::MoveStatement(m) => Statement::Move {
+            x: Expression::resolve(&m.x, symbols),
+            y: Expression::resolve(&m.y, symbols),
+        },
+
+        S::DrawStatement(d) => Statement::Draw {
+            x: Expression::resolve(&d.x, symbols),
+            y: Expression::resolve(&d.y, symbols),
+        },
+
+        S::SoundStatement(s) => Statement::Sound {
+            channel: Expression::resolve(&s.channel, symbols),
+            amplitude: Expression::resolve(&s.amplitude, symbols),
+            pitch: Expression::resolve(&s.pitch, symbols),
+            duration: Expression::resolve(&s.duration, symbols),
+        },
+
+        S::BputStatement(b) => Statement::Bput {
+            channel: factor(&b.channel, symbols),
+            value: Expression::resolve(&b.value, symbols),
+        },
+
+        S::CloseStatement(c) => Statement::Close(factor(&c.channel, symbols)),
+
+        S::DimStatement(d) => Statement::Dim {
+            variable: numeric_variable(&d.variable, symbols),
+            size: Expression::resolve(&d.size, symbols),
+        },
+
+        S::IndirectAssignment(a) => {
+            let (indirection, address) = self::indirection(&a.target, symbols);
+            Statement::Poke { indirection, address, value: bool_operand(&a.value, symbols) }
+        }
+
+        S::ProcCall(c) => Statement::Call {
+            name: c.name.to_string(),
+            arguments: c.arguments.iter().map(|a| bool_operand(a, symbols)).collect(),
+        },
+
+        S::EndprocStatement(_) => Statement::Return,
+
+        S::TraceStatement(t) => Statement::Trace(match &t.mode {
+            parser::TraceMode::TraceOn(_) => TraceMode::On,
+            parser::TraceMode::TraceOff(_) => TraceMode::Off,
+            parser::TraceMode::TraceProc(_) => TraceMode::Procedures,
+            parser::TraceMode::Expression(e) => TraceMode::Below(Expression::resolve(e, symbols))
+        }),
+    }
+}
@@ -1,93 +1,75 @@
+use crate::ast::{Comparison, Condition, Operand, StringValue};
 use crate::error::InterpreterError;
 use crate::expression::Compute;
+use crate::host::Host;
 use crate::scope::Scope;
 use crate::value::Value;
-use crate::parser::{BoolCondition, BoolConjunction, BoolDisjunction, BoolExpression, BoolOperand, BoolOperator, BoolTerm, Factor};
 
 pub trait ComputeBool {
     #[allow(unused_variables)]
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError>;
+    fn compute_bool(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError>;
 }
 
 
-impl ComputeBool for Factor {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        self.compute(scope)
-    }
-}
-
-impl ComputeBool for BoolOperand {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
+impl ComputeBool for StringValue {
+    fn compute_bool(&self, scope: &mut Scope, _: &mut dyn Host) -> Result<Value, InterpreterError> {
         match self {
-            BoolOperand::Expression(e) => e.compute(scope),
-            BoolOperand::StringLiteral(s) => Ok(Value::String(s.body.to_string())),
-
-            BoolOperand::StringVariable(s) => Ok(Value::String(scope.get_string(&s.name)?.clone()))
+            StringValue::Literal(s) => Ok(Value::String(s.to_string())),
+            StringValue::Variable(v) => Ok(Value::String(scope.string(v.slot)?.clone()))
         }
     }
 }
 
-impl ComputeBool for BoolCondition {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        match self.op {
-            BoolOperator::BoolOpEqual(_) => Ok(Value::Boolean(self.left.compute_bool(scope)?.eq(&self.right.compute_bool(scope)?)?)),
-            BoolOperator::BoolOpGreater(_) => Ok(Value::Boolean(self.left.compute_bool(scope)?.gt(&self.right.compute_bool(scope)?)?)),
-            BoolOperator::BoolOpGreaterEqual(_) => Ok(Value::Boolean(self.left.compute_bool(scope)?.ge(&self.right.compute_bool(scope)?)?)),
-            BoolOperator::BoolOpLower(_) => Ok(Value::Boolean(self.left.compute_bool(scope)?.lt(&self.right.compute_bool(scope)?)?)),
-            BoolOperator::BoolOpLowerEqual(_) => Ok(Value::Boolean(self.left.compute_bool(scope)?.le(&self.right.compute_bool(scope)?)?)),
-            BoolOperator::BoolOpNotEqual(_) => Err(InterpreterError::NotImplemented("Boolean op NE".to_string()))
+impl ComputeBool for Operand {
+    fn compute_bool(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError> {
+        match self {
+            Operand::Number(e) => e.compute(scope, host),
+            Operand::String(s) => s.compute_bool(scope, host)
         }
     }
 }
 
-impl ComputeBool for BoolTerm {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        match &self.expression {
-            None => {}
-            Some(e) => return e.compute_bool(scope)
-        };
-
-        match &self.condition {
-            None => {}
-            Some(c) => return c.compute_bool(scope)
-        };
+impl ComputeBool for Condition {
+    fn compute_bool(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError> {
+        match self {
+            Condition::Compare(left, op, right) => {
+                let l = left.compute_bool(scope, host)?;
+                let r = right.compute_bool(scope, host)?;
+
+                let result = match op {
+                    Comparison::Equal => l.eq(&r)?,
+                    Comparison::NotEqual => l.ne(&r)?,
+                    Comparison::Greater => l.gt(&r)?,
+                    Comparison::GreaterEqual => l.ge(&r)?,
+                    Comparison::Lower => l.lt(&r)?,
+                    Comparison::LowerEqual => l.le(&r)?
+                };
+
+                Ok(Value::Boolean(result))
+            }
 
-        Err(InterpreterError::Unreachable)
-    }
-}
+            Condition::And(terms) => {
+                for term in terms {
+                    match term.compute_bool(scope, host)? {
+                        Value::Boolean(b) => if !b { return Ok(Value::Boolean(false)); }
+                        _ => return Err(InterpreterError::TypeMismatch),
+                    }
+                }
 
-impl ComputeBool for BoolConjunction {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        for i in 0..self.term.len() {
-            match self.term.get(i).unwrap().compute_bool(scope)? {
-                Value::Boolean(b) => if b == false { return Ok(Value::Boolean(false)); }
-                _ => return Err(InterpreterError::TypeMismatch),
+                Ok(Value::Boolean(true))
             }
-        }
 
-        Ok(Value::Boolean(true))
-    }
-}
+            Condition::Or(conjunctions) => {
+                for conjunction in conjunctions {
+                    match conjunction.compute_bool(scope, host)? {
+                        Value::Boolean(b) => if b { return Ok(Value::Boolean(true)); },
+                        _ => return Err(InterpreterError::TypeMismatch),
+                    }
+                }
 
-impl ComputeBool for BoolDisjunction {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        for i in 0..self.conjunction.len() {
-            match self.conjunction.get(i).unwrap().compute_bool(scope)? {
-                Value::String(_) => return Err(InterpreterError::TypeMismatch),
-                Value::Integer(_) => return Err(InterpreterError::TypeMismatch),
-                Value::Float(_) => return Err(InterpreterError::TypeMismatch),
-                Value::Byte(_) => return Err(InterpreterError::TypeMismatch),
-                Value::Boolean(b) => if b == true { return Ok(Value::Boolean(true)); },
+                Ok(Value::Boolean(false))
             }
         }
-
-        Ok(Value::Boolean(false))
-    }
-}
-
-impl ComputeBool for BoolExpression {
-    fn compute_bool(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        self.disjunction.compute_bool(scope)
     }
 }
 
@@ -95,63 +77,76 @@ impl ComputeBool for BoolExpression {
 mod tests {
     use peginator::PegParser;
     use super::*;
+    use crate::host::MemoryHost;
+    use crate::parser::BoolExpression;
+
+    fn parse(code: &str, scope: &mut Scope) -> Condition {
+        let r = BoolExpression::parse(code).expect("Parse failed");
+        Condition::resolve(&r.disjunction, scope.symbols_mut())
+    }
 
     #[test]
     fn bool_expression_true() {
-        let r = BoolExpression::parse("12 < 23").expect("Parse failed");
         let mut s = Scope::new();
+        let r = parse("12 < 23", &mut s);
+        let mut h = MemoryHost::new();
 
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
 
         assert!(v.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn bool_expression_false() {
-        let r = BoolExpression::parse("12 > 23").expect("Parse failed");
         let mut s = Scope::new();
+        let r = parse("12 > 23", &mut s);
+        let mut h = MemoryHost::new();
 
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
 
         assert!(!v.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn bool_expression_conjunction() {
-        let r = BoolExpression::parse("12 < 23 AND 12 > 1").expect("Parse failed");
         let mut s = Scope::new();
+        let r = parse("12 < 23 AND 12 > 1", &mut s);
+        let mut h = MemoryHost::new();
 
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
 
         assert!(v.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn bool_expression_conjunction_false() {
-        let r = BoolExpression::parse("12 < 23 AND 12 < 1").expect("Parse failed");
         let mut s = Scope::new();
+        let r = parse("12 < 23 AND 12 < 1", &mut s);
+        let mut h = MemoryHost::new();
 
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
 
         assert!(!v.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn bool_expression_disjunction() {
-        let r = BoolExpression::parse("12 > 23 OR 12 > 1").expect("Parse failed");
         let mut s = Scope::new();
+        let r = parse("12 > 23 OR 12 > 1", &mut s);
+        let mut h = MemoryHost::new();
 
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
 
         assert!(v.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn bool_expression_disjunction_false() {
-        let r = BoolExpression::parse("12 > 23 OR 12 < 1").expect("Parse failed");
         let mut s = Scope::new();
+        let r = parse("12 > 23 OR 12 < 1", &mut s);
+        let mut h = MemoryHost::new();
 
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
 
         assert!(!v.as_bool().expect("Not a bool"));
     }
@@ -160,13 +155,14 @@ mod tests {
     #[test]
     fn bool_expression_lt() {
         let mut s = Scope::new();
+        let mut h = MemoryHost::new();
 
-        let r1 = BoolExpression::parse("12 < 13").expect("Parse failed");
-        let v1 = r1.compute_bool(&mut s).expect("Boolean computation failed");
+        let r1 = parse("12 < 13", &mut s);
+        let v1 = r1.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v1.as_bool().expect("Not a bool"));
 
-        let r2 = BoolExpression::parse("12 < 11").expect("Parse failed");
-        let v2 = r2.compute_bool(&mut s).expect("Boolean computation failed");
+        let r2 = parse("12 < 11", &mut s);
+        let v2 = r2.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(!v2.as_bool().expect("Not a bool"));
     }
 
@@ -174,78 +170,83 @@ mod tests {
     #[test]
     fn bool_expression_gt() {
         let mut s = Scope::new();
+        let mut h = MemoryHost::new();
 
-        let r1 = BoolExpression::parse("12 > 10 + 1").expect("Parse failed");
-        let v1 = r1.compute_bool(&mut s).expect("Boolean computation failed");
+        let r1 = parse("12 > 10 + 1", &mut s);
+        let v1 = r1.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v1.as_bool().expect("Not a bool"));
 
-        let r2 = BoolExpression::parse("12 > 10 + 4").expect("Parse failed");
-        let v2 = r2.compute_bool(&mut s).expect("Boolean computation failed");
+        let r2 = parse("12 > 10 + 4", &mut s);
+        let v2 = r2.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(!v2.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn bool_expression_eq() {
         let mut s = Scope::new();
+        let mut h = MemoryHost::new();
 
-        let r1 = BoolExpression::parse("12 = 12.0").expect("Parse failed");
-        let v1 = r1.compute_bool(&mut s).expect("Boolean computation failed");
+        let r1 = parse("12 = 12.0", &mut s);
+        let v1 = r1.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v1.as_bool().expect("Not a bool"));
 
-        let r2 = BoolExpression::parse("12 = 14").expect("Parse failed");
-        let v2 = r2.compute_bool(&mut s).expect("Boolean computation failed");
+        let r2 = parse("12 = 14", &mut s);
+        let v2 = r2.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(!v2.as_bool().expect("Not a bool"));
 
-        let r3 = BoolExpression::parse("\"ABC\" = \"ABC\"").expect("Parse failed");
-        let v3 = r3.compute_bool(&mut s).expect("Boolean computation failed");
+        let r3 = parse("\"ABC\" = \"ABC\"", &mut s);
+        let v3 = r3.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v3.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn comparing_variables() {
         let mut s = Scope::new();
+        let mut h = MemoryHost::new();
 
-        s.set_float(&"a".to_string(), 1.0);
-        s.set_float(&"b".to_string(), 1.0);
+        s.set_float("a", 1.0);
+        s.set_float("b", 1.0);
 
-        let r1 = BoolExpression::parse("a = b").expect("Parse failed");
-        let v1 = r1.compute_bool(&mut s).expect("Boolean computation failed");
+        let r1 = parse("a = b", &mut s);
+        let v1 = r1.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v1.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn comparing_expressions() {
         let mut s = Scope::new();
+        let mut h = MemoryHost::new();
 
-        s.set_float(&"a".to_string(), 1.0);
-        s.set_float(&"b".to_string(), 1.0);
+        s.set_float("a", 1.0);
+        s.set_float("b", 1.0);
 
-        let r = BoolExpression::parse("a * 2 = b * 2").expect("Parse failed");
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let r = parse("a * 2 = b * 2", &mut s);
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v.as_bool().expect("Not a bool"));
 
-        let r = BoolExpression::parse("3 > b * 2").expect("Parse failed");
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let r = parse("3 > b * 2", &mut s);
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v.as_bool().expect("Not a bool"));
     }
 
     #[test]
     fn comparing_strings() {
         let mut s = Scope::new();
+        let mut h = MemoryHost::new();
 
-        s.set_string(&"a".to_string(), "ABC".to_string());
+        s.set_string("a", "ABC".to_string());
 
 
-        let r = BoolExpression::parse("a$ = \"ABC\"").expect("Parse failed");
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let r = parse("a$ = \"ABC\"", &mut s);
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v.as_bool().expect("Not a bool"));
 
-/*        let r = BoolExpression::parse("\"ABC\" = a$").expect("Parse failed");
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+/*        let r = parse("\"ABC\" = a$", &mut s);
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v.as_bool().expect("Not a bool"));
 
-        let r = BoolExpression::parse("\"ABC\" = \"ABC\"").expect("Parse failed");
-        let v = r.compute_bool(&mut s).expect("Boolean computation failed");
+        let r = parse("\"ABC\" = \"ABC\"", &mut s);
+        let v = r.compute_bool(&mut s, &mut h).expect("Boolean computation failed");
         assert!(v.as_bool().expect("Not a bool"));*/
     }
 }
\ No newline at end of file
@@ -0,0 +1,396 @@
+//! Finds mistakes in a program before it runs, which would otherwise only
+//! show up once the line they are on is executed, if at all.
+//!
+//! Variables count as assigned once any statement before the one reading
+//! them assigns them, in either branch of an `IF`. Procedures are checked
+//! with the variables assigned where they are first called, those never
+//! called with every variable the program assigns.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use crate::ast::{Block, Condition, Expression, ForStatement, Operand, Position, Procedure, Program, Statement, StringValue, TraceMode, Variable};
+use crate::error::BasicError;
+use crate::parser;
+use crate::scope::{Slot, Symbols};
+use peginator::PegParser;
+
+/// A problem found by [`check`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Problem {
+    /// A variable is read before any statement assigns it.
+    Unassigned(String),
+    /// `NEXT` names another variable than its `FOR`.
+    NextMismatch { expected: String, found: String },
+    ExitForOutsideFor,
+    ExitWhileOutsideWhile,
+    /// A string is compared with a number.
+    Mixed,
+    /// Statements after `END` or `ENDPROC`.
+    Unreachable,
+    /// A `PROC` without a `DEF PROC` or an `FN`, neither registered by the
+    /// host.
+    NoSuchProcedure(String),
+}
+
+impl Problem {
+    /// Whether the program may still run fine. Calls of unknown procedures
+    /// and functions are, when the host running it registers them.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Problem::NoSuchProcedure(_))
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Unassigned(name) => write!(f, "{} is read before it is assigned", name),
+            Problem::NextMismatch { expected, found } => write!(f, "NEXT {} doesn't match FOR {}", found, expected),
+            Problem::ExitForOutsideFor => write!(f, "EXIT FOR outside of a FOR loop"),
+            Problem::ExitWhileOutsideWhile => write!(f, "EXIT WHILE outside of a WHILE loop"),
+            Problem::Mixed => write!(f, "Comparing a string with a number"),
+            Problem::Unreachable => write!(f, "Unreachable code"),
+            Problem::NoSuchProcedure(name) => write!(f, "No such FN/PROC: {}", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Diagnostic {
+    pub position: Position,
+    pub problem: Problem,
+}
+
+/// Shows the problem as `line:column: problem`.
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.position.line, self.position.column, self.problem)
+    }
+}
+
+/// Parses and checks `code`.
+///
+/// ```
+/// use bbbasic::checker::{check_source, Problem};
+///
+/// let diagnostics = check_source("iteration% = 1\nPRINT iteraton%\n").unwrap();
+///
+/// assert_eq!(diagnostics[0].position.line, 2);
+/// assert_eq!(diagnostics[0].problem, Problem::Unassigned("iteraton%".to_string()));
+/// ```
+pub fn check_source(code: &str) -> Result<Vec<Diagnostic>, BasicError> {
+    check_source_with(code, &NativeNames::default())
+}
+
+/// Like [`check_source`], for a program that can call the `natives`.
+pub fn check_source_with(code: &str, natives: &NativeNames) -> Result<Vec<Diagnostic>, BasicError> {
+    let parsed = parser::Program::parse(code).map_err(|e| BasicError::from_parse_error(code, &e))?;
+
+    let mut symbols = Symbols::new();
+    let program = Program::resolve_source(&parsed, code, &mut symbols);
+
+    Ok(check_with(&program, &symbols, natives))
+}
+
+/// Names of the natives a host registers, without `FN` or `PROC`, which
+/// programs call without defining them. See
+/// [`Interpreter::check`](crate::Interpreter::check).
+#[derive(Clone, Debug, Default)]
+pub struct NativeNames {
+    pub functions: HashSet<String>,
+    pub procedures: HashSet<String>,
+}
+
+/// Problems of `program`, its variables named after `symbols`, in the
+/// order of the source.
+pub fn check(program: &Program, symbols: &Symbols) -> Vec<Diagnostic> {
+    check_with(program, symbols, &NativeNames::default())
+}
+
+/// Like [`check`], for a program that can call the `natives`.
+pub fn check_with(program: &Program, symbols: &Symbols, natives: &NativeNames) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        program,
+        symbols,
+        natives,
+        assigned: HashSet::new(),
+        loops: Vec::new(),
+        calls: Vec::new(),
+        procedures: HashMap::new(),
+        diagnostics: Vec::new(),
+    };
+
+    checker.block(&program.body);
+
+    let mut everything = HashSet::new();
+    assignments(&program.body, &mut everything);
+    for procedure in &program.procedures {
+        assignments(&procedure.body, &mut everything);
+    }
+
+    for procedure in &program.procedures {
+        if !checker.procedures.contains_key(procedure.name.as_str()) {
+            checker.assigned = everything.clone();
+            checker.procedure(procedure);
+        }
+    }
+
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort();
+    diagnostics.dedup();
+
+    diagnostics
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Loop {
+    For,
+    While,
+}
+
+struct Checker<'a> {
+    program: &'a Program,
+    symbols: &'a Symbols,
+    natives: &'a NativeNames,
+    assigned: HashSet<Slot>,
+    /// Loops around the current statement, in the current procedure.
+    loops: Vec<Loop>,
+    /// Procedures being checked, recursive calls are skipped.
+    calls: Vec<&'a str>,
+    /// Procedures checked, with the variables they assign.
+    procedures: HashMap<&'a str, HashSet<Slot>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, position: Position, problem: Problem) {
+        self.diagnostics.push(Diagnostic { position, problem });
+    }
+
+    fn name(&self, variable: &Variable) -> String {
+        format!("{}{}", self.symbols.name(variable.slot), variable.data_type.suffix())
+    }
+
+    fn block(&mut self, block: &'a Block) {
+        for (i, located) in block.iter().enumerate() {
+            if i > 0 && matches!(block[i - 1].statement, Statement::End | Statement::Return) {
+                self.report(located.position, Problem::Unreachable);
+                return;
+            }
+
+            self.statement(&located.statement, located.position);
+        }
+    }
+
+    fn statement(&mut self, statement: &'a Statement, position: Position) {
+        match statement {
+            Statement::Assignment(v, e) => {
+                self.expression(e, position);
+                self.assigned.insert(v.slot);
+            }
+            Statement::StringAssignment(v, _) | Statement::Input { variable: v, .. } => {
+                self.assigned.insert(v.slot);
+            }
+            Statement::Dim { variable, size } => {
+                self.expression(size, position);
+                self.assigned.insert(variable.slot);
+            }
+            Statement::Poke { address, value, .. } => {
+                self.expression(address, position);
+                self.operand(value, position);
+            }
+            Statement::Print(items) => for item in items {
+                self.operand(&item.value, position);
+            }
+            Statement::For(f) => self.for_loop(f, position),
+            Statement::ExitFor => if !self.loops.contains(&Loop::For) {
+                self.report(position, Problem::ExitForOutsideFor);
+            }
+            Statement::While { condition, body } => {
+                self.condition(condition, position);
+                self.loops.push(Loop::While);
+                self.block(body);
+                self.loops.pop();
+            }
+            Statement::ExitWhile => if !self.loops.contains(&Loop::While) {
+                self.report(position, Problem::ExitWhileOutsideWhile);
+            }
+            Statement::If { condition, then_block, else_block } => {
+                self.condition(condition, position);
+
+                let before = self.assigned.clone();
+                self.block(then_block);
+                if let Some(else_block) = else_block {
+                    let then_assigned = std::mem::replace(&mut self.assigned, before);
+                    self.block(else_block);
+                    self.assigned.extend(then_assigned);
+                }
+            }
+            Statement::SetTime(e) | Statement::Close(e) | Statement::Trace(TraceMode::Below(e)) => self.expression(e, position),
+            Statement::Vdu(values) => for e in values {
+                self.expression(e, position);
+            }
+            Statement::Plot { mode, x, y } => self.expressions([mode, x, y], position),
+            Statement::Move { x, y } | Statement::Draw { x, y } => self.expressions([x, y], position),
+            Statement::Sound { channel, amplitude, pitch, duration } => self.expressions([channel, amplitude, pitch, duration], position),
+            Statement::Bput { channel, value } => self.expressions([channel, value], position),
+            Statement::Call { name, arguments } => {
+                for a in arguments {
+                    self.operand(a, position);
+                }
+                self.call(name, position);
+            }
+            Statement::End | Statement::Cls | Statement::Return | Statement::Trace(_) => {}
+        }
+    }
+
+    fn for_loop(&mut self, for_loop: &'a ForStatement, position: Position) {
+        self.expression(&for_loop.start, position);
+        self.expression(&for_loop.target, position);
+        if let Some(step) = &for_loop.step {
+            self.expression(step, position);
+        }
+        self.assigned.insert(for_loop.variable.slot);
+
+        self.loops.push(Loop::For);
+        self.block(&for_loop.body);
+        self.loops.pop();
+
+        if let Some(next) = &for_loop.next.variable {
+            if *next != for_loop.variable {
+                let problem = Problem::NextMismatch { expected: self.name(&for_loop.variable), found: self.name(next) };
+                self.report(for_loop.next.position, problem);
+            }
+        }
+    }
+
+    /// Checks the procedure on its first call, later calls only assign
+    /// what it assigned then.
+    fn call(&mut self, name: &'a str, position: Position) {
+        let Some(procedure) = self.program.procedure(name) else {
+            if !self.natives.procedures.contains(name) {
+                self.report(position, Problem::NoSuchProcedure(name.to_string()));
+            }
+            return;
+        };
+
+        if self.calls.contains(&name) {
+            return;
+        }
+
+        match self.procedures.get(name) {
+            Some(assigned) => self.assigned.extend(assigned.iter().copied()),
+            None => self.procedure(procedure)
+        }
+    }
+
+    fn procedure(&mut self, procedure: &'a Procedure) {
+        let before = self.assigned.clone();
+        let loops = std::mem::take(&mut self.loops);
+        self.calls.push(&procedure.name);
+
+        self.assigned.extend(procedure.parameters.iter().map(|p| p.slot));
+        self.block(&procedure.body);
+
+        // Parameters get back their old values.
+        for parameter in &procedure.parameters {
+            if !before.contains(&parameter.slot) {
+                self.assigned.remove(&parameter.slot);
+            }
+        }
+
+        self.calls.pop();
+        self.loops = loops;
+        let assigned = self.assigned.difference(&before).copied().collect();
+        self.procedures.insert(&procedure.name, assigned);
+    }
+
+    fn read(&mut self, variable: &Variable, position: Position) {
+        // Reported once, the first time. Resident variables always exist.
+        if self.assigned.insert(variable.slot) && self.symbols.resident(variable.slot).is_none() {
+            let name = self.name(variable);
+            self.report(position, Problem::Unassigned(name));
+        }
+    }
+
+    fn expressions<'e>(&mut self, expressions: impl IntoIterator<Item = &'e Expression>, position: Position) {
+        for e in expressions {
+            self.expression(e, position);
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression, position: Position) {
+        match expression {
+            Expression::Variable(v) => self.read(v, position),
+            Expression::Add(l, r) | Expression::Sub(l, r) | Expression::Mul(l, r) | Expression::Div(l, r) => {
+                self.expression(l, position);
+                self.expression(r, position);
+            }
+            Expression::Rnd(Some(e)) | Expression::Inkey(e) | Expression::Bget(e) | Expression::Eof(e) | Expression::Indirect(_, e) => self.expression(e, position),
+            Expression::Openin(s) | Expression::Openout(s) => self.string(s, position),
+            Expression::Function { name, arguments } => {
+                for a in arguments {
+                    self.operand(a, position);
+                }
+                if !self.natives.functions.contains(name) {
+                    self.report(position, Problem::NoSuchProcedure(name.to_string()));
+                }
+            }
+            Expression::Number(_) | Expression::Constant(_) | Expression::Time | Expression::Rnd(None) | Expression::Get => {}
+        }
+    }
+
+    fn string(&mut self, value: &StringValue, position: Position) {
+        if let StringValue::Variable(v) = value {
+            self.read(v, position);
+        }
+    }
+
+    fn operand(&mut self, operand: &Operand, position: Position) {
+        match operand {
+            Operand::Number(e) => self.expression(e, position),
+            Operand::String(s) => self.string(s, position)
+        }
+    }
+
+    fn condition(&mut self, condition: &Condition, position: Position) {
+        match condition {
+            Condition::Or(conditions) | Condition::And(conditions) => for c in conditions {
+                self.condition(c, position);
+            }
+            Condition::Compare(l, _, r) => {
+                self.operand(l, position);
+                self.operand(r, position);
+
+                if matches!((l, r), (Operand::Number(_), Operand::String(_)) | (Operand::String(_), Operand::Number(_))) {
+                    self.report(position, Problem::Mixed);
+                }
+            }
+        }
+    }
+}
+
+/// Adds the variables `block` assigns to `assigned`.
+fn assignments(block: &Block, assigned: &mut HashSet<Slot>) {
+    for located in block {
+        match &located.statement {
+            Statement::Assignment(v, _)
+            | Statement::StringAssignment(v, _)
+            | Statement::Input { variable: v, .. }
+            | Statement::Dim { variable: v, .. } => {
+                assigned.insert(v.slot);
+            }
+            Statement::For(f) => {
+                assigned.insert(f.variable.slot);
+                assignments(&f.body, assigned);
+            }
+            Statement::While { body, .. } => assignments(body, assigned),
+            Statement::If { then_block, else_block, .. } => {
+                assignments(then_block, assigned);
+                if let Some(else_block) = else_block {
+                    assignments(else_block, assigned);
+                }
+            }
+            _ => {}
+        }
+    }
+}
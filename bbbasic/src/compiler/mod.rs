@@ -0,0 +1,749 @@
+use std::collections::HashMap;
+use crate::ast::{Block, Comparison, Condition, Expression, ForStatement, Indirection, Located, Operand, PrintItem, Program, Statement, StringValue, TraceMode, Variable};
+use crate::error::InterpreterError;
+use crate::host::FileMode;
+use crate::scope::{DataType, Slot};
+use crate::value::Value;
+
+pub type Address = usize;
+
+/// A single bytecode instruction. Operands are taken from and results pushed
+/// to the value stack of the VM.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Const(usize),
+    Load(Slot),
+    Store(Slot),
+
+    /// Pops a value and stores it in a variable of the given type, converted
+    /// for it first.
+    Assign(Slot, DataType),
+
+    /// Arithmetic and comparisons pop their left operand and find the right
+    /// one at the [`Source`].
+    Add(Source),
+    Sub(Source),
+    Mul(Source),
+    Div(Source),
+
+    Eq(Source),
+    Ne(Source),
+    Lt(Source),
+    Le(Source),
+    Gt(Source),
+    Ge(Source),
+
+    Jump(Address),
+    JumpIfFalse(Address),
+    JumpIfTrue(Address),
+
+    /// Pops target and step and starts a FOR loop over the given variable.
+    /// The loop body starts right after this instruction.
+    ForInit(Slot, DataType),
+    /// Takes a step like [`Op::Step`], then steps the innermost FOR loop and
+    /// jumps back to its body, or drops the loop and continues with the next
+    /// instruction once it is completed.
+    ForNext,
+    /// Drops the innermost FOR loop, used by `EXIT`.
+    ForPop,
+
+    /// Takes a step of the step budget, emitted on every iteration of the
+    /// loops other than FOR.
+    Step,
+    /// Takes a step like [`Op::Step`], emitted before every statement with the
+    /// line the statement starts on.
+    Statement(usize),
+
+    /// Calls a procedure with the given number of arguments on the stack.
+    Call(usize, usize),
+    /// Calls a native function with the given number of arguments on the
+    /// stack, pushing its result.
+    Function(usize, usize),
+    /// Saves a variable to be restored when the current procedure returns.
+    Local(Slot),
+    /// Returns from the current procedure.
+    Return,
+
+    TraceOn,
+    TraceOff,
+    TraceProc,
+    /// Pops a line number, see [`Trace::lines_below`](crate::Trace::lines_below).
+    TraceBelow,
+
+    Print,
+    PrintNewline,
+    Input(Slot, DataType),
+
+    Time,
+    SetTime,
+    Rnd,
+    RndArg,
+    Get,
+    Inkey,
+    Open(FileMode),
+    Bget,
+    Eof,
+    Bput,
+    Close,
+    Vdu(usize),
+    Plot,
+    Sound,
+
+    /// Pops a size and reserves memory like `DIM`, pushing its address.
+    Dim,
+    /// Pops an address and pushes the value there.
+    Peek(Indirection),
+    /// Pops a value and an address and writes the value there.
+    Poke(Indirection),
+
+    /// `END` statement.
+    End,
+    /// End of the program.
+    Halt,
+}
+
+/// Where an instruction finds its right operand. Variables and constants are
+/// read in place, which saves pushing them first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Source {
+    /// Popped from the stack.
+    Stack,
+    Slot(Slot),
+    Const(usize),
+}
+
+/// Compiled program: instructions and constant pool. Variables are addressed
+/// by the slots they were resolved to.
+#[derive(Clone, Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<Op>,
+    pub constants: Vec<Value>,
+    /// Every procedure that is defined or called, indexed by [`Op::Call`].
+    pub procedures: Vec<CompiledProcedure>,
+    /// Names of the functions called, indexed by [`Op::Function`].
+    pub functions: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompiledProcedure {
+    pub name: String,
+    pub parameters: usize,
+    /// Start of the procedure, `None` if it is called but never defined.
+    pub address: Option<Address>,
+}
+
+impl Chunk {
+    /// Line of the statement the instruction at `address` belongs to, 0 if
+    /// unknown.
+    pub fn line(&self, address: Address) -> usize {
+        self.code[..=address.min(self.code.len().saturating_sub(1))].iter().rev()
+            .find_map(|op| match op {
+                Op::Statement(line) => Some(*line),
+                _ => None
+            })
+            .unwrap_or(0)
+    }
+
+    /// The procedure starting at or containing `address`, `None` for the main
+    /// program.
+    pub fn procedure_at(&self, address: Address) -> Option<&CompiledProcedure> {
+        self.procedures.iter()
+            .filter(|p| matches!(p.address, Some(a) if a <= address))
+            .max_by_key(|p| p.address)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LoopKind {
+    For,
+    While,
+}
+
+struct Loop {
+    kind: LoopKind,
+    exits: Vec<Address>,
+}
+
+pub struct Compiler {
+    chunk: Chunk,
+    loops: Vec<Loop>,
+    procedures: HashMap<String, usize>,
+}
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Compiler::new()
+    }
+}
+
+impl Compiler {
+    pub fn new() -> Compiler {
+        Compiler {
+            chunk: Chunk::default(),
+            loops: Vec::new(),
+            procedures: HashMap::new(),
+        }
+    }
+
+    /// Procedures are compiled after the main program. Each one binds its
+    /// arguments to its parameters, last one first, and returns at its end.
+    pub fn compile(mut self, program: &Program) -> Result<Chunk, InterpreterError> {
+        for p in &program.procedures {
+            let index = self.procedure(&p.name);
+            self.chunk.procedures[index].parameters = p.parameters.len();
+        }
+
+        self.block(&program.body)?;
+        self.emit(Op::Halt);
+
+        for p in &program.procedures {
+            let index = self.procedure(&p.name);
+            if self.chunk.procedures[index].address.is_some() {
+                continue;
+            }
+            self.chunk.procedures[index].address = Some(self.here());
+
+            for parameter in p.parameters.iter().rev() {
+                self.emit(Op::Local(parameter.slot));
+                self.emit(Op::Assign(parameter.slot, parameter.data_type));
+            }
+
+            self.block(&p.body)?;
+            self.emit(Op::Return);
+        }
+
+        Ok(self.chunk)
+    }
+
+    /// Index of a function in the chunk, added when it's seen first.
+    fn function(&mut self, name: &str) -> usize {
+        match self.chunk.functions.iter().position(|f| f == name) {
+            Some(index) => index,
+            None => {
+                self.chunk.functions.push(name.to_string());
+                self.chunk.functions.len() - 1
+            }
+        }
+    }
+
+    /// Index of a procedure in the chunk, added when it's seen first.
+    fn procedure(&mut self, name: &str) -> usize {
+        if let Some(index) = self.procedures.get(name) {
+            return *index;
+        }
+
+        self.chunk.procedures.push(CompiledProcedure { name: name.to_string(), parameters: 0, address: None });
+        self.procedures.insert(name.to_string(), self.chunk.procedures.len() - 1);
+
+        self.chunk.procedures.len() - 1
+    }
+
+    fn emit(&mut self, op: Op) -> Address {
+        self.chunk.code.push(op);
+        self.chunk.code.len() - 1
+    }
+
+    fn here(&self) -> Address {
+        self.chunk.code.len()
+    }
+
+    fn patch_all(&mut self, jumps: Vec<Address>, target: Address) {
+        for jump in jumps {
+            self.patch(jump, target);
+        }
+    }
+
+    fn patch(&mut self, at: Address, target: Address) {
+        self.chunk.code[at] = match self.chunk.code[at] {
+            Op::Jump(_) => Op::Jump(target),
+            Op::JumpIfFalse(_) => Op::JumpIfFalse(target),
+            Op::JumpIfTrue(_) => Op::JumpIfTrue(target),
+            op => op
+        };
+    }
+
+    fn constant(&mut self, value: Value) {
+        let index = self.constant_index(value);
+        self.emit(Op::Const(index));
+    }
+
+    fn constant_index(&mut self, value: Value) -> usize {
+        self.chunk.constants.push(value);
+        self.chunk.constants.len() - 1
+    }
+
+    fn block(&mut self, block: &Block) -> Result<(), InterpreterError> {
+        for statement in block {
+            self.statement(statement)?;
+        }
+
+        Ok(())
+    }
+
+    fn statement(&mut self, located: &Located) -> Result<(), InterpreterError> {
+        self.emit(Op::Statement(located.position.line));
+
+        match &located.statement {
+            Statement::Assignment(variable, value) => self.assignment(variable, value)?,
+
+            Statement::StringAssignment(variable, value) => {
+                self.constant(Value::String(value.to_string()));
+                self.emit(Op::Store(variable.slot));
+            }
+
+            Statement::Print(items) => self.print(items)?,
+
+            Statement::Input { prompt, variable } => {
+                self.constant(Value::String(prompt.to_string()));
+                self.emit(Op::Print);
+                self.emit(Op::Input(variable.slot, variable.data_type));
+            }
+
+            Statement::For(f) => self.for_loop(f)?,
+            Statement::While { condition, body } => self.while_loop(condition, body)?,
+            Statement::If { condition, then_block, else_block } => self.if_statement(condition, then_block, else_block)?,
+            Statement::ExitFor => self.exit(LoopKind::For),
+            Statement::ExitWhile => self.exit(LoopKind::While),
+
+            Statement::End => {
+                self.emit(Op::End);
+            }
+
+            Statement::SetTime(t) => {
+                self.expression(t)?;
+                self.emit(Op::SetTime);
+            }
+
+            Statement::Cls => {
+                self.constant(Value::Integer(12));
+                self.emit(Op::Vdu(1));
+            }
+
+            Statement::Vdu(values) => {
+                for value in values {
+                    self.expression(value)?;
+                }
+                self.emit(Op::Vdu(values.len()));
+            }
+
+            Statement::Plot { mode, x, y } => {
+                self.integers(&[mode, x, y])?;
+                self.emit(Op::Plot);
+            }
+
+            Statement::Move { x, y } => {
+                self.constant(Value::Integer(4));
+                self.integers(&[x, y])?;
+                self.emit(Op::Plot);
+            }
+
+            Statement::Draw { x, y } => {
+                self.constant(Value::Integer(5));
+                self.integers(&[x, y])?;
+                self.emit(Op::Plot);
+            }
+
+            Statement::Sound { channel, amplitude, pitch, duration } => {
+                self.integers(&[channel, amplitude, pitch, duration])?;
+                self.emit(Op::Sound);
+            }
+
+            Statement::Bput { channel, value } => {
+                self.integers(&[channel, value])?;
+                self.emit(Op::Bput);
+            }
+
+            Statement::Close(channel) => {
+                self.expression(channel)?;
+                self.emit(Op::Close);
+            }
+
+            Statement::Dim { variable, size } => {
+                self.expression(size)?;
+                self.emit(Op::Dim);
+                self.emit(Op::Assign(variable.slot, variable.data_type));
+            }
+
+            Statement::Poke { indirection, address, value } => {
+                self.expression(address)?;
+                self.operand(value)?;
+                self.emit(Op::Poke(*indirection));
+            }
+
+            Statement::Call { name, arguments } => {
+                for argument in arguments {
+                    self.operand(argument)?;
+                }
+
+                let index = self.procedure(name);
+                self.emit(Op::Call(index, arguments.len()));
+            }
+
+            Statement::Return => {
+                self.emit(Op::Return);
+            }
+
+            Statement::Trace(mode) => {
+                match mode {
+                    TraceMode::On => self.emit(Op::TraceOn),
+                    TraceMode::Off => self.emit(Op::TraceOff),
+                    TraceMode::Procedures => self.emit(Op::TraceProc),
+                    TraceMode::Below(line) => {
+                        self.expression(line)?;
+                        self.emit(Op::TraceBelow)
+                    }
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn integers(&mut self, values: &[&Expression]) -> Result<(), InterpreterError> {
+        for value in values {
+            self.expression(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn assignment(&mut self, variable: &Variable, value: &Expression) -> Result<(), InterpreterError> {
+        self.expression(value)?;
+        self.emit(Op::Assign(variable.slot, variable.data_type));
+
+        Ok(())
+    }
+
+    fn string(&mut self, value: &StringValue) {
+        match value {
+            StringValue::Literal(s) => self.constant(Value::String(s.to_string())),
+            StringValue::Variable(v) => {
+                self.emit(Op::Load(v.slot));
+            }
+        }
+    }
+
+    fn operand(&mut self, operand: &Operand) -> Result<(), InterpreterError> {
+        match operand {
+            Operand::Number(e) => self.expression(e),
+            Operand::String(s) => {
+                self.string(s);
+                Ok(())
+            }
+        }
+    }
+
+    fn print(&mut self, items: &[PrintItem]) -> Result<(), InterpreterError> {
+        for item in items {
+            self.operand(&item.value)?;
+            self.emit(Op::Print);
+
+            if item.newline {
+                self.emit(Op::PrintNewline);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn for_loop(&mut self, for_loop: &ForStatement) -> Result<(), InterpreterError> {
+        self.assignment(&for_loop.variable, &for_loop.start)?;
+
+        let data_type = match for_loop.variable.data_type {
+            DataType::Byte | DataType::String => return Err(InterpreterError::NotImplemented("Loops for other than integers and floats".to_string())),
+            t => t
+        };
+
+        self.expression(&for_loop.target)?;
+
+        match &for_loop.step {
+            Some(s) => self.expression(s)?,
+            None => self.constant(Value::Integer(1))
+        }
+
+        self.emit(Op::ForInit(for_loop.variable.slot, data_type));
+
+        self.loops.push(Loop { kind: LoopKind::For, exits: Vec::new() });
+        self.block(&for_loop.body)?;
+        self.emit(Op::ForNext);
+        self.end_loop();
+
+        Ok(())
+    }
+
+    fn while_loop(&mut self, condition: &Condition, body: &Block) -> Result<(), InterpreterError> {
+        let start = self.emit(Op::Step);
+
+        let to_end = self.jump_unless(condition)?;
+
+        self.loops.push(Loop { kind: LoopKind::While, exits: to_end });
+        self.block(body)?;
+        self.emit(Op::Jump(start));
+        self.end_loop();
+
+        Ok(())
+    }
+
+    fn end_loop(&mut self) {
+        let end = self.here();
+
+        if let Some(l) = self.loops.pop() {
+            for exit in l.exits {
+                self.patch(exit, end);
+            }
+        }
+    }
+
+    /// `EXIT FOR` and `EXIT WHILE` leave the innermost loop of the given kind
+    /// together with all loops nested in it. Without such a loop the program
+    /// stops.
+    fn exit(&mut self, kind: LoopKind) {
+        let target = match self.loops.iter().rposition(|l| l.kind == kind) {
+            None => {
+                self.emit(Op::Halt);
+                return;
+            }
+            Some(t) => t
+        };
+
+        let nested_fors = self.loops[target..].iter().filter(|l| l.kind == LoopKind::For).count();
+
+        for _ in 0..nested_fors {
+            self.emit(Op::ForPop);
+        }
+
+        let jump = self.emit(Op::Jump(0));
+        self.loops[target].exits.push(jump);
+    }
+
+    fn if_statement(&mut self, condition: &Condition, then_block: &Block, else_block: &Option<Block>) -> Result<(), InterpreterError> {
+        let to_else = self.jump_unless(condition)?;
+
+        self.block(then_block)?;
+
+        match else_block {
+            None => {
+                let end = self.here();
+                self.patch_all(to_else, end);
+            }
+            Some(else_block) => {
+                let to_end = self.emit(Op::Jump(0));
+                let else_start = self.here();
+                self.patch_all(to_else, else_start);
+
+                self.block(else_block)?;
+
+                let end = self.here();
+                self.patch(to_end, end);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn condition(&mut self, condition: &Condition) -> Result<(), InterpreterError> {
+        match condition {
+            Condition::Compare(left, op, right) => {
+                self.operand(left)?;
+                let right = match right {
+                    Operand::Number(e) => self.source(e)?,
+                    Operand::String(StringValue::Literal(s)) => Source::Const(self.constant_index(Value::String(s.to_string()))),
+                    Operand::String(StringValue::Variable(v)) => Source::Slot(v.slot)
+                };
+
+                self.emit(match op {
+                    Comparison::Equal => Op::Eq(right),
+                    Comparison::NotEqual => Op::Ne(right),
+                    Comparison::Greater => Op::Gt(right),
+                    Comparison::GreaterEqual => Op::Ge(right),
+                    Comparison::Lower => Op::Lt(right),
+                    Comparison::LowerEqual => Op::Le(right)
+                });
+            }
+
+            Condition::Or(conjunctions) => {
+                let mut to_true = Vec::new();
+
+                for conjunction in conjunctions {
+                    self.condition(conjunction)?;
+                    to_true.push(self.emit(Op::JumpIfTrue(0)));
+                }
+
+                self.short_circuit(to_true, false, true);
+            }
+
+            Condition::And(terms) => {
+                let mut to_false = Vec::new();
+
+                for term in terms {
+                    self.condition(term)?;
+                    to_false.push(self.emit(Op::JumpIfFalse(0)));
+                }
+
+                self.short_circuit(to_false, true, false);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compiles a condition that only decides a jump, returning the jumps
+    /// taken when it's false. `AND` and `OR` jump right away instead of
+    /// pushing a boolean first.
+    fn jump_unless(&mut self, condition: &Condition) -> Result<Vec<Address>, InterpreterError> {
+        match condition {
+            Condition::And(terms) => {
+                let mut to_false = Vec::new();
+
+                for term in terms {
+                    to_false.extend(self.jump_unless(term)?);
+                }
+
+                Ok(to_false)
+            }
+
+            Condition::Or(conjunctions) if conjunctions.len() > 1 => {
+                let mut to_true = Vec::new();
+
+                for conjunction in &conjunctions[..conjunctions.len() - 1] {
+                    self.condition(conjunction)?;
+                    to_true.push(self.emit(Op::JumpIfTrue(0)));
+                }
+
+                let to_false = self.jump_unless(&conjunctions[conjunctions.len() - 1])?;
+                let target = self.here();
+                self.patch_all(to_true, target);
+
+                Ok(to_false)
+            }
+
+            c => {
+                self.condition(c)?;
+                Ok(vec![self.emit(Op::JumpIfFalse(0))])
+            }
+        }
+    }
+
+    /// Pushes `fall_through` when none of the jumps were taken, `jumped`
+    /// otherwise.
+    fn short_circuit(&mut self, jumps: Vec<Address>, fall_through: bool, jumped: bool) {
+        self.constant(Value::Boolean(fall_through));
+        let to_end = self.emit(Op::Jump(0));
+
+        let target = self.here();
+        for jump in jumps {
+            self.patch(jump, target);
+        }
+
+        self.constant(Value::Boolean(jumped));
+
+        let end = self.here();
+        self.patch(to_end, end);
+    }
+
+    /// Compiles a numeric expression, leaving its value on the stack.
+    fn expression(&mut self, expression: &Expression) -> Result<(), InterpreterError> {
+        match expression {
+            Expression::Add(l, r) => self.arithmetic(l, r, Op::Add)?,
+            Expression::Sub(l, r) => self.arithmetic(l, r, Op::Sub)?,
+            Expression::Mul(l, r) => self.arithmetic(l, r, Op::Mul)?,
+            Expression::Div(l, r) => self.arithmetic(l, r, Op::Div)?,
+
+            Expression::Number(n) => self.constant(n.value()?),
+
+            Expression::Constant(c) => self.constant(c.clone()),
+
+            Expression::Variable(v) => {
+                self.emit(Op::Load(v.slot));
+            }
+
+            Expression::Time => {
+                self.emit(Op::Time);
+            }
+
+            Expression::Rnd(argument) => match argument {
+                None => {
+                    self.emit(Op::Rnd);
+                }
+                Some(a) => {
+                    self.expression(a)?;
+                    self.emit(Op::RndArg);
+                }
+            },
+
+            Expression::Get => {
+                self.emit(Op::Get);
+            }
+
+            Expression::Inkey(argument) => {
+                self.expression(argument)?;
+                self.emit(Op::Inkey);
+            }
+
+            Expression::Openin(name) => {
+                self.string(name);
+                self.emit(Op::Open(FileMode::Read));
+            }
+
+            Expression::Openout(name) => {
+                self.string(name);
+                self.emit(Op::Open(FileMode::Write));
+            }
+
+            Expression::Bget(channel) => {
+                self.expression(channel)?;
+                self.emit(Op::Bget);
+            }
+
+            Expression::Eof(channel) => {
+                self.expression(channel)?;
+                self.emit(Op::Eof);
+            }
+
+            Expression::Function { name, arguments } => {
+                for argument in arguments {
+                    self.operand(argument)?;
+                }
+
+                let index = self.function(name);
+                self.emit(Op::Function(index, arguments.len()));
+            }
+
+            Expression::Indirect(indirection, address) => {
+                self.expression(address)?;
+                self.emit(Op::Peek(*indirection));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn arithmetic(&mut self, l: &Expression, r: &Expression, op: fn(Source) -> Op) -> Result<(), InterpreterError> {
+        self.expression(l)?;
+        let r = self.source(r)?;
+        self.emit(op(r));
+
+        Ok(())
+    }
+
+    /// Right operand of an instruction, left on the stack unless it can be
+    /// read in place.
+    fn source(&mut self, expression: &Expression) -> Result<Source, InterpreterError> {
+        match expression {
+            Expression::Variable(v) => Ok(Source::Slot(v.slot)),
+            Expression::Number(n) => Ok(Source::Const(self.constant_index(n.value()?))),
+            Expression::Constant(c) => Ok(Source::Const(self.constant_index(c.clone()))),
+            e => {
+                self.expression(e)?;
+                Ok(Source::Stack)
+            }
+        }
+    }
+}
+
+impl Program {
+    /// Compiles the program to bytecode for the VM.
+    pub fn compile(&self) -> Result<Chunk, InterpreterError> {
+        Compiler::new().compile(self)
+    }
+}
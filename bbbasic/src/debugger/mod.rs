@@ -0,0 +1,210 @@
+//! Debugger for programs on the bytecode engine. It runs the program one
+//! statement at a time, stopping at breakpoints or once a step is done, and
+//! gives access to the variables and the running procedures in between.
+
+use std::collections::BTreeSet;
+use crate::compiler::Op;
+use crate::error::{BasicError, InterpreterError};
+use crate::host::Host;
+use crate::interpreter::{Interpreter, Outcome, Session, StepResult};
+use crate::value::Value;
+
+/// Why the debugger stopped.
+#[derive(Debug)]
+pub enum Stop {
+    /// In front of the statement on a line with a breakpoint.
+    Breakpoint(usize),
+    /// In front of the statement on the given line, after a step.
+    Step(usize),
+    /// `INPUT` or `GET` is waiting for the host.
+    WaitingForInput,
+    Finished(Outcome),
+    Error(BasicError),
+}
+
+/// A running procedure or the main program, see [`Debugger::call_stack`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// Name of the procedure without `PROC`, `None` for the main program.
+    pub procedure: Option<String>,
+    /// Line the frame is at, for callers the line of the call.
+    pub line: usize,
+}
+
+/// Runs a program under control of the caller.
+///
+/// ```
+/// use bbbasic::{Debugger, Interpreter, MemoryHost, Stop};
+///
+/// let code = "x% = 1\nx% = x% + 1\nPRINT x%\n";
+/// let mut debugger = Debugger::new(Interpreter::new(MemoryHost::new()), code).expect("Program failed");
+///
+/// debugger.add_breakpoint(3);
+/// assert!(matches!(debugger.resume(), Stop::Breakpoint(3)));
+/// assert_eq!(debugger.variable("x%").unwrap().to_string(), "2");
+/// ```
+pub struct Debugger<H: Host> {
+    interpreter: Interpreter<H>,
+    breakpoints: BTreeSet<usize>,
+}
+
+impl<H: Host> Debugger<H> {
+    /// Starts `code` on `interpreter`, stopped in front of its first
+    /// statement. Constant folding is turned off, so every variable can be
+    /// inspected and changed.
+    pub fn new(mut interpreter: Interpreter<H>, code: &str) -> Result<Debugger<H>, BasicError> {
+        interpreter.set_optimization(false);
+        interpreter.start(code)?;
+
+        Ok(Debugger { interpreter, breakpoints: BTreeSet::new() })
+    }
+
+    pub fn add_breakpoint(&mut self, line: usize) {
+        self.breakpoints.insert(line);
+    }
+
+    pub fn remove_breakpoint(&mut self, line: usize) -> bool {
+        self.breakpoints.remove(&line)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Runs until a breakpoint is reached or the program stops.
+    pub fn resume(&mut self) -> Stop {
+        self.run_until(|_| true)
+    }
+
+    /// Runs the current statement, stopping in front of the next one, which
+    /// may be in a called procedure.
+    pub fn step_into(&mut self) -> Stop {
+        self.run_until(|_| false)
+    }
+
+    /// Like [`Debugger::step_into`], but runs called procedures completely.
+    pub fn step_over(&mut self) -> Stop {
+        let depth = self.depth();
+        self.run_until(|d| d > depth)
+    }
+
+    /// Runs until the current procedure has returned.
+    pub fn step_out(&mut self) -> Stop {
+        let depth = self.depth();
+        self.run_until(|d| d >= depth)
+    }
+
+    /// Runs statement by statement while `go_on` holds for the number of
+    /// running procedures, stopping at breakpoints regardless.
+    fn run_until(&mut self, go_on: impl Fn(usize) -> bool) -> Stop {
+        loop {
+            // The program stopped in front of a step, which a budget of one
+            // takes. After waiting for input it stopped in the middle of a
+            // statement, and has to stop at the next step right away.
+            let budget = if self.at_step() { 1 } else { 0 };
+
+            match self.interpreter.step(budget) {
+                StepResult::Running => {}
+                StepResult::WaitingForInput => return Stop::WaitingForInput,
+                StepResult::Finished(o) => return Stop::Finished(o),
+                StepResult::Error(e) => return Stop::Error(e),
+            }
+
+            if let Some(line) = self.line() {
+                if self.breakpoints.contains(&line) {
+                    return Stop::Breakpoint(line);
+                }
+
+                if !go_on(self.depth()) {
+                    return Stop::Step(line);
+                }
+            }
+        }
+    }
+
+    fn session(&self) -> Result<&Session, InterpreterError> {
+        self.interpreter.session.as_ref().ok_or(InterpreterError::NoProgram)
+    }
+
+    /// Line of the statement the program stopped in front of, `None` if it
+    /// isn't in front of a statement or has stopped.
+    pub fn line(&self) -> Option<usize> {
+        let session = self.session().ok()?;
+
+        match session.chunk.code.get(session.vm.pc()) {
+            Some(Op::Statement(line)) => Some(*line),
+            _ => None
+        }
+    }
+
+    fn at_step(&self) -> bool {
+        match self.session() {
+            Err(_) => false,
+            Ok(s) => matches!(s.chunk.code.get(s.vm.pc()), Some(Op::Step | Op::Statement(_) | Op::ForNext))
+        }
+    }
+
+    fn depth(&self) -> usize {
+        self.session().map(|s| s.vm.calls().len()).unwrap_or(0)
+    }
+
+    /// The running procedures, innermost first, ending with the main
+    /// program.
+    pub fn call_stack(&self) -> Vec<Frame> {
+        let session = match self.session() {
+            Err(_) => return Vec::new(),
+            Ok(s) => s
+        };
+
+        let chunk = &session.chunk;
+        let name = |index: usize| Some(chunk.procedures[index].name.clone());
+
+        let calls = session.vm.calls();
+        let mut frames = vec![Frame {
+            procedure: calls.last().and_then(|c| name(c.procedure)),
+            line: chunk.line(session.vm.pc()),
+        }];
+
+        for (i, call) in calls.iter().enumerate().rev() {
+            frames.push(Frame {
+                procedure: if i == 0 { None } else { name(calls[i - 1].procedure) },
+                line: chunk.line(call.return_address - 1),
+            });
+        }
+
+        frames
+    }
+
+    /// Variables holding a value, named with the suffix of their type.
+    pub fn variables(&self) -> Vec<(String, Value)> {
+        match self.session() {
+            Err(_) => Vec::new(),
+            Ok(s) => s.scope.variables().into_iter().map(|(n, v)| (n, v.clone())).collect()
+        }
+    }
+
+    /// Value of a variable given with its suffix, like `name$`.
+    pub fn variable(&self, name: &str) -> Result<Value, InterpreterError> {
+        self.session()?.scope.variable(name).cloned()
+    }
+
+    /// Changes a variable, converting `value` to the type of the variable.
+    pub fn set_variable(&mut self, name: &str, value: Value) -> Result<(), InterpreterError> {
+        match &mut self.interpreter.session {
+            None => Err(InterpreterError::NoProgram),
+            Some(s) => s.scope.set_variable(name, value)
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.interpreter.is_running()
+    }
+
+    pub fn host(&self) -> &H {
+        self.interpreter.host()
+    }
+
+    pub fn host_mut(&mut self) -> &mut H {
+        self.interpreter.host_mut()
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt::{Display, Formatter};
+
 #[derive(Debug)]
 pub enum InterpreterError {
     NotImplemented(String),
@@ -5,5 +7,147 @@ pub enum InterpreterError {
     OperationUnsupported,
     UnknownVariable(String),
     StatementNotFound,
-    Unreachable
-}
\ No newline at end of file
+    Unreachable,
+    DivisionByZero,
+    NumberTooBig,
+    EndOfInput,
+    EndOfFile,
+    /// The program was cancelled through its [`CancelToken`](crate::CancelToken).
+    Escape,
+    /// The program ran out of steps, see [`Limits`](crate::limits::Limits).
+    StepLimitExceeded,
+    /// [`Interpreter::step`](crate::Interpreter::step) was called without a
+    /// started program.
+    NoProgram,
+    /// A `PROC` without a `DEF PROC`.
+    NoSuchProcedure(String),
+    /// A procedure was called with the wrong number of arguments.
+    Arguments,
+    /// `ENDPROC` outside of a procedure.
+    NotInProcedure,
+    /// `RENUMBER` or `AUTO` with a step of 0, or running out of line numbers.
+    Silly,
+    /// A tokenised program file is broken.
+    BadProgram,
+    /// A line is too long to be tokenised.
+    LineTooLong,
+    /// A character that can't be stored in a tokenised program.
+    Unrepresentable(char),
+    /// A snapshot doesn't fit the program it was taken of.
+    BadSnapshot,
+    /// A snapshot was asked for while the program has files open, which
+    /// can't be saved.
+    FilesOpen,
+    /// An indirection outside of the memory reserved with `DIM`.
+    BadAddress,
+    /// `DIM x% n` with `n` below -1.
+    BadDim,
+    /// `DIM` ran out of memory.
+    NoRoom,
+    /// Raised by a native function or procedure, see
+    /// [`Interpreter::register_fn`](crate::Interpreter::register_fn).
+    Native(String),
+    Io(std::io::Error),
+}
+
+impl Display for InterpreterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterpreterError::NotImplemented(what) => write!(f, "Not implemented: {}", what),
+            InterpreterError::TypeMismatch => write!(f, "Type mismatch"),
+            InterpreterError::OperationUnsupported => write!(f, "Operation unsupported"),
+            InterpreterError::UnknownVariable(name) => write!(f, "No such variable: {}", name),
+            InterpreterError::StatementNotFound => write!(f, "Statement not found"),
+            InterpreterError::Unreachable => write!(f, "Unreachable code reached"),
+            InterpreterError::DivisionByZero => write!(f, "Division by zero"),
+            InterpreterError::NumberTooBig => write!(f, "Number too big"),
+            InterpreterError::EndOfInput => write!(f, "End of input"),
+            InterpreterError::EndOfFile => write!(f, "Eof"),
+            InterpreterError::Escape => write!(f, "Escape"),
+            InterpreterError::StepLimitExceeded => write!(f, "Step limit exceeded"),
+            InterpreterError::NoProgram => write!(f, "No program running"),
+            InterpreterError::NoSuchProcedure(name) => write!(f, "No such FN/PROC: {}", name),
+            InterpreterError::Arguments => write!(f, "Incorrect arguments"),
+            InterpreterError::NotInProcedure => write!(f, "Not in a procedure"),
+            InterpreterError::Silly => write!(f, "Silly"),
+            InterpreterError::BadProgram => write!(f, "Bad program"),
+            InterpreterError::LineTooLong => write!(f, "Line too long"),
+            InterpreterError::Unrepresentable(c) => write!(f, "Can't store {} in a tokenised program", c),
+            InterpreterError::BadSnapshot => write!(f, "Bad snapshot"),
+            InterpreterError::FilesOpen => write!(f, "Can't take a snapshot with files open"),
+            InterpreterError::BadAddress => write!(f, "Bad address"),
+            InterpreterError::BadDim => write!(f, "Bad DIM statement"),
+            InterpreterError::NoRoom => write!(f, "DIM space"),
+            InterpreterError::Native(message) => write!(f, "{}", message),
+            InterpreterError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InterpreterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InterpreterError::Io(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+impl From<std::io::Error> for InterpreterError {
+    fn from(e: std::io::Error) -> Self {
+        InterpreterError::Io(e)
+    }
+}
+
+
+/// Error returned by the public entry points: either the program could not be
+/// parsed, or it failed while running.
+#[derive(Debug)]
+pub enum BasicError {
+    Parse { line: usize, column: usize, message: String },
+    Runtime(InterpreterError),
+}
+
+impl BasicError {
+    pub fn from_parse_error(code: &str, error: &peginator::ParseError) -> BasicError {
+        let (line, column) = line_and_column(code, error.position);
+
+        BasicError::Parse { line, column, message: error.specifics.to_string() }
+    }
+}
+
+/// Translates a byte position into a 1-based line and column.
+pub fn line_and_column(code: &str, position: usize) -> (usize, usize) {
+    let before = code.get(..position).unwrap_or(code);
+    let line = before.matches('\n').count() + 1;
+    let column = match before.rfind('\n') {
+        None => before.chars().count() + 1,
+        Some(nl) => before[nl + 1..].chars().count() + 1
+    };
+
+    (line, column)
+}
+
+impl Display for BasicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BasicError::Parse { line, column, message } => write!(f, "Syntax error at line {}, column {}: {}", line, column, message),
+            BasicError::Runtime(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BasicError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BasicError::Runtime(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+impl From<InterpreterError> for BasicError {
+    fn from(e: InterpreterError) -> Self {
+        BasicError::Runtime(e)
+    }
+}
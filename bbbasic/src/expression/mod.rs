@@ -1,321 +1,153 @@
+use crate::ast::{Expression, NumberLiteral, Operand, StringValue};
+use crate::bool_expression::ComputeBool;
 use crate::error::InterpreterError;
-use crate::error::InterpreterError::NotImplemented;
-use crate::parser::{Expression, Factor, Group, NumberLiteral, NumberLiteral_value, NumericVariable, NumericVariable_type_dem, Term};
-use crate::scope::{Byte, DataType, Float, Integer, Scope};
+use crate::host::{Channel, FileMode, Host};
+use crate::scope::{Float, Integer, Scope};
 use crate::value::Value;
 
+/// Computes numeric expressions. Operands are combined following the
+/// promotion rules of [`Value`], the type of the result is only known once
+/// it has been computed.
 pub trait Compute {
-    #[allow(unused_variables)]
-    fn compute_float(&self, scope: &mut Scope) -> Result<Float, InterpreterError> {
-        Err(NotImplemented("Base implementation compute".to_string()))
-    }
-
-    #[allow(unused_variables)]
-    fn compute_integer(&self, scope: &mut Scope) -> Result<Integer, InterpreterError> {
-        Err(NotImplemented("Base implementation compute".to_string()))
-    }
-
-    #[allow(unused_variables)]
-    fn compute_byte(&self, scope: &mut Scope) -> Result<Byte, InterpreterError> {
-        Err(NotImplemented("Base implementation compute".to_string()))
-    }
-
-    fn get_type(&self) -> DataType;
-
-    fn compute(&self, scope: &mut Scope) -> Result<Value, InterpreterError> {
-        match self.get_type() {
-            DataType::Byte => Ok(Value::Byte(self.compute_byte(scope)?)),
-            DataType::Integer => Ok(Value::Integer(self.compute_integer(scope)?)),
-            DataType::Float => Ok(Value::Float(self.compute_float(scope)?)),
-            _ => Err(InterpreterError::TypeMismatch)
-        }
-    }
+    fn compute(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError>;
 }
 
-/*
-impl Compute for IntegerLiteral {
-    fn compute<T>(&self, _: &mut Scope) -> Result<T, InterpreterError> {
-        Ok(self.body.parse::<Integer>().unwrap() as FromStr)
-    }
-}
+impl NumberLiteral {
+    /// The value of the literal. Integers too large for an integer variable
+    /// become floats.
+    pub fn value(&self) -> Result<Value, InterpreterError> {
+        let value = match self {
+            NumberLiteral::Float(f) => f.parse().ok().map(Value::Float),
+            NumberLiteral::Integer(i) => match i.parse() {
+                Ok(i) => Some(Value::Integer(i)),
+                Err(_) => i.parse().ok().map(Value::Float)
+            }
+        };
 
-impl Compute for FloatLiteral {
-    fn compute<T>(&self, _: &mut Scope) -> Result<T, InterpreterError> {
-        Ok(self.body.parse().unwrap())
+        value.ok_or(InterpreterError::TypeMismatch)
     }
-}*/
+}
 
 impl Compute for NumberLiteral {
-    fn compute_float(&self, _: &mut Scope) -> Result<Float, InterpreterError> {
-        match &self.value {
-            NumberLiteral_value::FloatLiteral(f) => match f.body.parse() {
-                Err(_) => Err(InterpreterError::TypeMismatch),
-                Ok(v) => Ok(v)
-            },
-
-            NumberLiteral_value::IntegerLiteral(f) => match f.body.parse::<Integer>() {
-                Err(_) => Err(InterpreterError::TypeMismatch),
-                Ok(v) => Ok(v as Float)
-            },
-        }
-    }
-
-    fn compute_integer(&self, _: &mut Scope) -> Result<Integer, InterpreterError> {
-        match &self.value {
-            NumberLiteral_value::IntegerLiteral(f) => match f.body.parse() {
-                Err(_) => Err(InterpreterError::TypeMismatch),
-                Ok(v) => Ok(v)
-            },
-
-            NumberLiteral_value::FloatLiteral(f) => match f.body.parse::<Float>() {
-                Err(_) => Err(InterpreterError::TypeMismatch),
-                Ok(v) => Ok(v as Integer)
-            },
-        }
-    }
-
-    fn get_type(&self) -> DataType {
-        match self.value {
-            NumberLiteral_value::FloatLiteral(_) => DataType::Float,
-            NumberLiteral_value::IntegerLiteral(_) => DataType::Integer
-        }
+    fn compute(&self, _: &mut Scope, _: &mut dyn Host) -> Result<Value, InterpreterError> {
+        self.value()
     }
 }
 
-impl Compute for NumericVariable {
-    fn compute_float(&self, scope: &mut Scope) -> Result<Float, InterpreterError> {
-        match &self.type_dem {
-            None => Ok(scope.get_float(&self.name)?),
-
-            Some(d) => match d {
-                NumericVariable_type_dem::ByteDenominator(_) => Ok(scope.get_byte(&self.name)? as Float),
-                NumericVariable_type_dem::FloatDenominator(_) => Ok(scope.get_float(&self.name)?),
-                NumericVariable_type_dem::IntegerDenominator(_) => Ok(scope.get_int(&self.name)? as Float)
-            }
-        }
-    }
-
-    fn compute_integer(&self, scope: &mut Scope) -> Result<Integer, InterpreterError> {
-        match &self.type_dem {
-            None => Ok(scope.get_int(&self.name)?),
-
-            Some(d) => match d {
-                NumericVariable_type_dem::ByteDenominator(_) => Ok(scope.get_byte(&self.name)? as Integer),
-                NumericVariable_type_dem::FloatDenominator(_) => Ok(scope.get_float(&self.name)? as Integer),
-                NumericVariable_type_dem::IntegerDenominator(_) => Ok(scope.get_int(&self.name)?)
+impl Compute for Expression {
+    fn compute(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError> {
+        match self {
+            Expression::Number(n) => n.compute(scope, host),
+            Expression::Constant(c) => Ok(c.clone()),
+            Expression::Variable(v) => Ok(scope.get(v.slot)?.clone()),
+            Expression::Add(l, r) => l.compute(scope, host)?.add(&r.compute(scope, host)?, scope.integer_model()),
+            Expression::Sub(l, r) => l.compute(scope, host)?.sub(&r.compute(scope, host)?, scope.integer_model()),
+            Expression::Mul(l, r) => l.compute(scope, host)?.mul(&r.compute(scope, host)?, scope.integer_model()),
+            Expression::Div(l, r) => l.compute(scope, host)?.div(&r.compute(scope, host)?),
+            Expression::Function { name, arguments } => call_function(name, arguments, scope, host),
+            Expression::Indirect(indirection, address) => {
+                let address = address.compute(scope, host)?.to_integer()?;
+                scope.memory().peek(*indirection, address)
             }
+            f => f.call_host_function(scope, host)
         }
     }
+}
 
-    fn compute_byte(&self, scope: &mut Scope) -> Result<Byte, InterpreterError> {
-        match &self.type_dem {
-            None => Ok(scope.get_byte(&self.name)?),
+/// Calls a native function, see
+/// [`Interpreter::register_fn`](crate::Interpreter::register_fn).
+fn call_function(name: &str, arguments: &[Operand], scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError> {
+    let function = scope.native_function(name)?;
 
-            Some(d) => match d {
-                NumericVariable_type_dem::ByteDenominator(_) => Ok(scope.get_byte(&self.name)?),
-                NumericVariable_type_dem::FloatDenominator(_) => Ok(scope.get_float(&self.name)? as Byte),
-                NumericVariable_type_dem::IntegerDenominator(_) => Ok(scope.get_int(&self.name)? as Byte)
-            }
-        }
+    let mut values = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        values.push(argument.compute_bool(scope, host)?);
     }
 
-    fn get_type(&self) -> DataType {
-        match &self.type_dem {
-            None => DataType::Float,
-
-            Some(d) => match d {
-                NumericVariable_type_dem::ByteDenominator(_) => DataType::Byte,
-                NumericVariable_type_dem::FloatDenominator(_) => DataType::Float,
-                NumericVariable_type_dem::IntegerDenominator(_) => DataType::Integer
-            }
-        }
-    }
+    function.call(values, scope.integer_model())
 }
 
-impl Compute for Factor {
-    fn compute_float(&self, scope: &mut Scope) -> Result<Float, InterpreterError> {
-        match &self {
-            Factor::Group(g) => g.compute_float(scope),
-            Factor::NumberLiteral(n) => n.compute_float(scope),
-            Factor::NumericVariable(v) => match &v.type_dem {
-                None => Ok(scope.get_float(&v.name.to_string())?),
-                Some(d) => match d {
-                    NumericVariable_type_dem::ByteDenominator(_) => Ok(scope.get_byte(&v.name.to_string())? as Float),
-                    NumericVariable_type_dem::FloatDenominator(_) => Ok(scope.get_float(&v.name.to_string())?),
-                    NumericVariable_type_dem::IntegerDenominator(_) => Ok(scope.get_int(&v.name.to_string())? as Float),
-                }
-            }
-        }
-    }
-
-    fn compute_integer(&self, scope: &mut Scope) -> Result<Integer, InterpreterError> {
-        match &self {
-            Factor::Group(g) => g.compute_integer(scope),
-            Factor::NumberLiteral(n) => n.compute_integer(scope),
-            Factor::NumericVariable(v) => match &v.type_dem {
-                None => Ok(scope.get_float(&v.name.to_string())? as Integer),
-                Some(d) => match d {
-                    NumericVariable_type_dem::ByteDenominator(_) => Ok(scope.get_byte(&v.name.to_string())? as Integer),
-                    NumericVariable_type_dem::FloatDenominator(_) => Ok(scope.get_float(&v.name.to_string())? as Integer),
-                    NumericVariable_type_dem::IntegerDenominator(_) => Ok(scope.get_int(&v.name.to_string())?),
-                }
-            }
-        }
-    }
-
-    fn compute_byte(&self, scope: &mut Scope) -> Result<Byte, InterpreterError> {
-        match self {
-            Factor::Group(g) => g.compute_byte(scope),
-            Factor::NumberLiteral(n) => n.compute_byte(scope),
-            Factor::NumericVariable(v) => match &v.type_dem {
-                None => Ok(scope.get_float(&v.name.to_string())? as Byte),
-                Some(d) => match d {
-                    NumericVariable_type_dem::ByteDenominator(_) => Ok(scope.get_byte(&v.name.to_string())?),
-                    NumericVariable_type_dem::FloatDenominator(_) => Ok(scope.get_float(&v.name.to_string())? as Byte),
-                    NumericVariable_type_dem::IntegerDenominator(_) => Ok(scope.get_int(&v.name.to_string())? as Byte),
-                }
-            }
-        }
-    }
-
-    fn get_type(&self) -> DataType {
-        match &self {
-            Factor::Group(g) => g.get_type(),
-            Factor::NumberLiteral(l) => l.get_type(),
-            Factor::NumericVariable(v) => match &v.type_dem {
-                None => DataType::Float,
-                Some(d) => match d {
-                    NumericVariable_type_dem::ByteDenominator(_) => DataType::Byte,
-                    NumericVariable_type_dem::FloatDenominator(_) => DataType::Float,
-                    NumericVariable_type_dem::IntegerDenominator(_) => DataType::Integer
-                }
-            }
-        }
+/// Name of the file given to `OPENIN`/`OPENOUT`.
+fn file_name(name: &StringValue, scope: &mut Scope) -> Result<String, InterpreterError> {
+    match name {
+        StringValue::Literal(s) => Ok(s.to_string()),
+        StringValue::Variable(v) => Ok(scope.string(v.slot)?.clone())
     }
 }
 
-impl Compute for Term {
-    fn compute_float(&self, scope: &mut Scope) -> Result<Float, InterpreterError> {
-        match self {
-            Term::Div(d) => Ok(d.left.compute_float(scope)? / d.right.compute_float(scope)?),
-            Term::Factor(f) => f.compute_float(scope),
-            Term::Mul(m) => Ok(m.left.compute_float(scope)? * m.right.compute_float(scope)?)
-        }
+fn open_file(name: &str, mode: FileMode, host: &mut dyn Host) -> Result<Value, InterpreterError> {
+    match host.open_file(name, mode)? {
+        None => Ok(Value::Integer(0)),
+        Some(channel) => Ok(Value::Integer(channel as Integer))
     }
+}
 
-    fn compute_integer(&self, scope: &mut Scope) -> Result<Integer, InterpreterError> {
+impl Expression {
+    /// Evaluates the functions that query the host: `TIME`, `RND`, `GET`,
+    /// `INKEY`, `OPENIN`, `OPENOUT`, `BGET#` and `EOF#`.
+    fn call_host_function(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<Value, InterpreterError> {
         match self {
-            Term::Div(d) => Ok(d.left.compute_integer(scope)? / d.right.compute_integer(scope)?),
-            Term::Factor(f) => f.compute_integer(scope),
-            Term::Mul(m) => Ok(m.left.compute_integer(scope)? * m.right.compute_integer(scope)?)
-        }
-    }
+            Expression::Time => Ok(Value::Integer(host.time())),
+
+            Expression::Rnd(argument) => match argument {
+                None => Ok(Value::Integer((host.random() * u32::MAX as Float) as Integer + i32::MIN as Integer)),
+                Some(a) => {
+                    let n = a.compute(scope, host)?.to_integer()?;
+
+                    match n {
+                        n if n < 0 => {
+                            host.seed_random(n);
+                            Ok(Value::Integer(n))
+                        }
+                        0 | 1 => Ok(Value::Float(host.random())),
+                        n => Ok(Value::Integer((host.random() * n as Float) as Integer + 1))
+                    }
+                }
+            },
 
-    fn compute_byte(&self, scope: &mut Scope) -> Result<Byte, InterpreterError> {
-        match self {
-            Term::Div(d) => Ok(d.left.compute_byte(scope)? / d.right.compute_byte(scope)?),
-            Term::Factor(f) => f.compute_byte(scope),
-            Term::Mul(m) => Ok(m.left.compute_byte(scope)? * m.right.compute_byte(scope)?)
-        }
-    }
+            Expression::Get => match host.read_key(None)? {
+                None => Err(InterpreterError::EndOfInput),
+                Some(key) => Ok(Value::Integer(key as Integer))
+            },
 
-    fn get_type(&self) -> DataType {
-        let l: u16;
-        let r: u16;
+            Expression::Inkey(argument) => {
+                let timeout = argument.compute(scope, host)?.to_integer()?.max(0) as u32;
 
-        match self {
-            Term::Div(d) => {
-                l = d.left.get_type() as u16;
-                r = d.right.get_type() as u16;
+                match host.read_key(Some(timeout))? {
+                    None => Ok(Value::Integer(-1)),
+                    Some(key) => Ok(Value::Integer(key as Integer))
+                }
             }
 
-            Term::Mul(d) => {
-                l = d.left.get_type() as u16;
-                r = d.right.get_type() as u16;
+            Expression::Openin(name) => {
+                let name = file_name(name, scope)?;
+                open_file(&name, FileMode::Read, host)
             }
 
-            Term::Factor(d) => {
-                return d.get_type();
+            Expression::Openout(name) => {
+                let name = file_name(name, scope)?;
+                open_file(&name, FileMode::Write, host)
             }
-        };
 
+            Expression::Bget(channel) => {
+                let channel = channel.compute(scope, host)?.to_integer()?;
 
-        match u16::max(l, r) {
-            1 => DataType::Byte,
-            2 => DataType::Integer,
-            3 => DataType::Float,
-            _ => panic!("Wrong Type!")
-        }
-    }
-}
-
-impl Compute for Group {
-    fn compute_float(&self, scope: &mut Scope) -> Result<Float, InterpreterError> {
-        self.body.compute_float(scope)
-    }
-    fn compute_integer(&self, scope: &mut Scope) -> Result<Integer, InterpreterError> {
-        self.body.compute_integer(scope)
-    }
-    fn compute_byte(&self, scope: &mut Scope) -> Result<Byte, InterpreterError> {
-        self.body.compute_byte(scope)
-    }
-
-    fn get_type(&self) -> DataType {
-        self.body.get_type()
-    }
-}
-
-impl Compute for Expression {
-    fn compute_float(&self, scope: &mut Scope) -> Result<Float, InterpreterError> {
-        match self {
-            Expression::Add(a) => Ok(a.left.compute_float(scope)? + a.right.compute_float(scope)?),
-            Expression::Sub(s) => Ok(s.left.compute_float(scope)? - s.right.compute_float(scope)?),
-            Expression::Term(t) => t.compute_float(scope)
-        }
-    }
-
-    fn compute_integer(&self, scope: &mut Scope) -> Result<Integer, InterpreterError> {
-        match self {
-            Expression::Add(a) => Ok(a.left.compute_integer(scope)? + a.right.compute_integer(scope)?),
-            Expression::Sub(s) => Ok(s.left.compute_integer(scope)? - s.right.compute_integer(scope)?),
-            Expression::Term(t) => t.compute_integer(scope)
-        }
-    }
-
-    fn compute_byte(&self, scope: &mut Scope) -> Result<Byte, InterpreterError> {
-        match self {
-            Expression::Add(a) => Ok(a.left.compute_byte(scope)? + a.right.compute_byte(scope)?),
-            Expression::Sub(s) => Ok(s.left.compute_byte(scope)? - s.right.compute_byte(scope)?),
-            Expression::Term(t) => t.compute_byte(scope)
-        }
-    }
-
-    fn get_type(&self) -> DataType {
-        let l: u16;
-        let r: u16;
-
-        match self {
-            Expression::Add(d) => {
-                l = d.left.get_type() as u16;
-                r = d.right.get_type() as u16;
+                match host.read_byte(channel as Channel)? {
+                    None => Err(InterpreterError::EndOfFile),
+                    Some(byte) => Ok(Value::Integer(byte as Integer))
+                }
             }
 
-            Expression::Sub(d) => {
-                l = d.left.get_type() as u16;
-                r = d.right.get_type() as u16;
-            }
+            Expression::Eof(channel) => {
+                let channel = channel.compute(scope, host)?.to_integer()?;
 
-            Expression::Term(d) => {
-                return d.get_type();
+                match host.at_eof(channel as Channel)? {
+                    true => Ok(Value::Integer(-1)),
+                    false => Ok(Value::Integer(0))
+                }
             }
-        };
 
-
-        match u16::max(l, r) {
-            1 => DataType::Byte,
-            2 => DataType::Integer,
-            3 => DataType::Float,
-            _ => panic!("Wrong Type!")
+            _ => Err(InterpreterError::Unreachable)
         }
     }
 }
@@ -324,25 +156,66 @@ impl Compute for Expression {
 mod tests {
     use peginator::PegParser;
     use super::*;
+    use crate::host::MemoryHost;
+    use crate::parser;
+    use crate::scope::DataType;
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn expression_can_be_parsed_and_computed() {
-        let r = Expression::parse("12 + (23 + 2 / 1)").expect("Parse error");
         let mut s = Scope::new();
-        let v = r.compute_integer(&mut s).expect("Computation error");
+        let r = parser::Expression::parse("12 + (23 + 2 / 1)").expect("Parse error");
+        let r = Expression::resolve(&r, s.symbols_mut());
+        let mut h = MemoryHost::new();
+        let v = r.compute(&mut s, &mut h).expect("Computation error");
 
-        assert_eq!(12 + (23 + 2 / 1), v);
+        assert_eq!(Value::Float(12.0 + (23.0 + 2.0 / 1.0)), v);
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn expression_with_vars() {
-        let r = Expression::parse("12 + (23 + a / 1)").expect("Parse error");
         let mut s = Scope::new();
+        let r = parser::Expression::parse("12 + (23 + a / 1)").expect("Parse error");
+        let r = Expression::resolve(&r, s.symbols_mut());
+        let mut h = MemoryHost::new();
+
+        s.set_float("a", 4.0);
+
+        let v = r.compute(&mut s, &mut h).expect("Computation error");
+
+        assert_eq!(Value::Float(12.0 + (23.0 + 4.0 / 1.0)), v);
+    }
+
+    #[test]
+    fn variables_resolve_to_slots() {
+        let mut s = Scope::new();
+        let r = parser::Expression::parse("a + a% + a# + b").expect("Parse error");
+        Expression::resolve(&r, s.symbols_mut());
 
-        s.set_float(&"a".to_string(), 4.0);
+        assert_eq!(s.symbols().len(), 3);
+        assert_eq!(s.symbols().find("a", DataType::Float), Some(0));
+        assert_eq!(s.symbols().find("a", DataType::Integer), Some(1));
+        assert_eq!(s.symbols().find("b", DataType::Float), Some(2));
+    }
 
-        let v = r.compute_integer(&mut s).expect("Computation error");
+    #[test]
+    fn mixed_expressions_are_promoted() {
+        let mut s = Scope::new();
+        let mut h = MemoryHost::new();
+        let mut compute = |code: &str, s: &mut Scope| {
+            let r = parser::Expression::parse(code).expect("Parse error");
+            let r = Expression::resolve(&r, s.symbols_mut());
+            r.compute(s, &mut h).expect("Computation error")
+        };
+
+        s.set_int("i", 7);
+        s.set_byte("b", 200);
 
-        assert_eq!(12 + (23 + 4 / 1), v);
+        assert_eq!(compute("i% / 2", &mut s), Value::Float(3.5));
+        assert_eq!(compute("i% * 2 - 1", &mut s), Value::Integer(13));
+        assert_eq!(compute("b& + b&", &mut s), Value::Integer(400));
+        assert_eq!(compute("i% + 0.5", &mut s), Value::Float(7.5));
+        assert_eq!(compute("9223372036854775807 + i%", &mut s), Value::Float(9223372036854775807.0 + 7.0));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,533 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Seek, Stdin, Stdout, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub type Channel = u32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileMode {
+    Read,
+    Write,
+}
+
+/// Everything a program can observe or change outside of its own variables.
+///
+/// The interpreter never touches stdin, stdout, the clock or the file system
+/// directly, which allows running programs in the browser or in tests.
+///
+/// A host that can't block while waiting for input, like a browser page,
+/// fails `read_line` and `read_key` with [`ErrorKind::WouldBlock`]. Programs
+/// run with [`Interpreter::step`](crate::Interpreter::step) are then
+/// suspended and retry the read on the next step.
+pub trait Host {
+    /// Writes program output.
+    fn write_text(&mut self, text: &str) -> std::io::Result<()>;
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Writes output of `TRACE`, by default along with the program output.
+    fn trace(&mut self, text: &str) -> std::io::Result<()> {
+        self.write_text(text)
+    }
+
+    /// Reads a line for `INPUT`, without the line terminator. `None` if no
+    /// more input is available.
+    fn read_line(&mut self) -> std::io::Result<Option<String>>;
+
+    /// Reads a key press. `timeout` is given in centiseconds, `None` waits
+    /// indefinitely. Returns `None` if no key was pressed in time.
+    fn read_key(&mut self, timeout: Option<u32>) -> std::io::Result<Option<u8>>;
+
+    /// Elapsed time in centiseconds, as read by `TIME`.
+    fn time(&mut self) -> i64;
+
+    fn set_time(&mut self, centiseconds: i64);
+
+    /// Returns a random number in the range [0, 1).
+    fn random(&mut self) -> f64;
+
+    fn seed_random(&mut self, seed: i64);
+
+    /// State of the random number generator, which a
+    /// [`Snapshot`](crate::snapshot::Snapshot) keeps so that `RND` continues
+    /// the same sequence. `None` if it can't be saved.
+    fn random_state(&self) -> Option<u64> {
+        None
+    }
+
+    /// Continues the sequence of `RND` from a state returned by
+    /// [`Host::random_state`].
+    fn set_random_state(&mut self, _state: u64) {}
+
+    /// Opens a file and returns its channel, `None` if it can't be opened.
+    fn open_file(&mut self, name: &str, mode: FileMode) -> std::io::Result<Option<Channel>>;
+
+    fn close_file(&mut self, channel: Channel) -> std::io::Result<()>;
+
+    /// Reads the next byte, `None` at the end of the file.
+    fn read_byte(&mut self, channel: Channel) -> std::io::Result<Option<u8>>;
+
+    fn write_byte(&mut self, channel: Channel, byte: u8) -> std::io::Result<()>;
+
+    fn at_eof(&mut self, channel: Channel) -> std::io::Result<bool>;
+
+    /// Sends raw VDU codes to the screen (`VDU`, `CLS`).
+    fn vdu(&mut self, codes: &[u8]) -> std::io::Result<()>;
+
+    /// Graphics sink for `PLOT`, `MOVE` and `DRAW`.
+    fn plot(&mut self, mode: i32, x: i32, y: i32) -> std::io::Result<()>;
+
+    /// Sound sink for `SOUND`.
+    fn sound(&mut self, channel: i32, amplitude: i32, pitch: i32, duration: i32) -> std::io::Result<()>;
+}
+
+fn unknown_channel(channel: Channel) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::NotFound, format!("Channel {} is not open", channel))
+}
+
+
+/// Small xorshift generator, good enough for `RND` and reproducible when seeded.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed.max(1) }
+    }
+
+    pub fn next_float(&mut self) -> f64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: u64) {
+        self.state = state.max(1);
+    }
+}
+
+
+/// Host backed by the standard library: text goes to the given streams, files
+/// go to the local file system. Graphics and sound are ignored.
+pub struct StdHost<R: BufRead = BufReader<Stdin>, W: Write = Stdout> {
+    input: R,
+    output: W,
+    started: Instant,
+    time_offset: i64,
+    random: Random,
+    files: HashMap<Channel, File>,
+    next_channel: Channel,
+    trace: Option<Box<dyn Write>>,
+}
+
+impl StdHost {
+    /// Host reading from stdin and writing to stdout.
+    pub fn stdio() -> StdHost {
+        StdHost::new(BufReader::new(std::io::stdin()), std::io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> StdHost<R, W> {
+    pub fn new(input: R, output: W) -> StdHost<R, W> {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(1);
+
+        StdHost {
+            input,
+            output,
+            started: Instant::now(),
+            time_offset: 0,
+            random: Random::new(seed),
+            files: HashMap::new(),
+            next_channel: 1,
+            trace: None,
+        }
+    }
+
+    /// Writes `TRACE` output to `sink` instead of the output stream.
+    pub fn with_trace_sink(mut self, sink: impl Write + 'static) -> StdHost<R, W> {
+        self.trace = Some(Box::new(sink));
+        self
+    }
+
+    pub fn output(&self) -> &W {
+        &self.output
+    }
+
+    pub fn into_inner(self) -> (R, W) {
+        (self.input, self.output)
+    }
+}
+
+impl<R: BufRead, W: Write> Host for StdHost<R, W> {
+    fn write_text(&mut self, text: &str) -> std::io::Result<()> {
+        self.output.write_all(text.as_bytes())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(t) = &mut self.trace {
+            t.flush()?;
+        }
+
+        self.output.flush()
+    }
+
+    fn trace(&mut self, text: &str) -> std::io::Result<()> {
+        match &mut self.trace {
+            None => self.write_text(text),
+            Some(t) => t.write_all(text.as_bytes())
+        }
+    }
+
+    fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        let mut line = String::new();
+
+        if self.input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+    }
+
+    fn read_key(&mut self, timeout: Option<u32>) -> std::io::Result<Option<u8>> {
+        // Without a raw terminal keys only arrive line by line, so a key
+        // with a timeout is never available.
+        if timeout.is_some() {
+            return Ok(None);
+        }
+
+        let mut key = [0u8];
+
+        match self.input.read(&mut key)? {
+            0 => Ok(None),
+            _ => Ok(Some(key[0]))
+        }
+    }
+
+    fn time(&mut self) -> i64 {
+        self.started.elapsed().as_millis() as i64 / 10 + self.time_offset
+    }
+
+    fn set_time(&mut self, centiseconds: i64) {
+        self.time_offset = centiseconds - self.started.elapsed().as_millis() as i64 / 10;
+    }
+
+    fn random(&mut self) -> f64 {
+        self.random.next_float()
+    }
+
+    fn seed_random(&mut self, seed: i64) {
+        self.random.set_state(seed as u64);
+    }
+
+    fn random_state(&self) -> Option<u64> {
+        Some(self.random.state())
+    }
+
+    fn set_random_state(&mut self, state: u64) {
+        self.random.set_state(state);
+    }
+
+    fn open_file(&mut self, name: &str, mode: FileMode) -> std::io::Result<Option<Channel>> {
+        let file = match mode {
+            FileMode::Read => File::open(name),
+            FileMode::Write => File::create(name),
+        };
+
+        match file {
+            Ok(f) => {
+                let channel = self.next_channel;
+                self.next_channel += 1;
+                self.files.insert(channel, f);
+
+                Ok(Some(channel))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e)
+        }
+    }
+
+    fn close_file(&mut self, channel: Channel) -> std::io::Result<()> {
+        match self.files.remove(&channel) {
+            None => Err(unknown_channel(channel)),
+            Some(_) => Ok(())
+        }
+    }
+
+    fn read_byte(&mut self, channel: Channel) -> std::io::Result<Option<u8>> {
+        let file = self.files.get_mut(&channel).ok_or_else(|| unknown_channel(channel))?;
+        let mut byte = [0u8];
+
+        match file.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0]))
+        }
+    }
+
+    fn write_byte(&mut self, channel: Channel, byte: u8) -> std::io::Result<()> {
+        let file = self.files.get_mut(&channel).ok_or_else(|| unknown_channel(channel))?;
+
+        file.write_all(&[byte])
+    }
+
+    fn at_eof(&mut self, channel: Channel) -> std::io::Result<bool> {
+        let file = self.files.get_mut(&channel).ok_or_else(|| unknown_channel(channel))?;
+        let position = file.stream_position()?;
+
+        Ok(position >= file.metadata()?.len())
+    }
+
+    fn vdu(&mut self, codes: &[u8]) -> std::io::Result<()> {
+        for code in codes {
+            match code {
+                12 => self.output.write_all("\x1b[2J\x1b[H".as_bytes())?,
+                c => self.output.write_all(&[*c])?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn plot(&mut self, _mode: i32, _x: i32, _y: i32) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn sound(&mut self, _channel: i32, _amplitude: i32, _pitch: i32, _duration: i32) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HostEvent {
+    Vdu(Vec<u8>),
+    Plot { mode: i32, x: i32, y: i32 },
+    Sound { channel: i32, amplitude: i32, pitch: i32, duration: i32 },
+}
+
+#[derive(Clone, Debug)]
+struct OpenFile {
+    name: String,
+    mode: FileMode,
+    position: usize,
+}
+
+/// Host keeping everything in memory: queued input lines and keys, captured
+/// output, a manual clock, a seeded random generator, a virtual file system
+/// and a log of graphics and sound events.
+#[derive(Clone, Debug)]
+pub struct MemoryHost {
+    pub output: String,
+    pub input: VecDeque<String>,
+    pub keys: VecDeque<u8>,
+    pub files: HashMap<String, Vec<u8>>,
+    pub events: Vec<HostEvent>,
+    pub time: i64,
+    /// Whether `INPUT` and `GET` wait for more input when the queues are
+    /// empty instead of reaching the end of the input.
+    pub non_blocking: bool,
+    /// Output of `TRACE`, `None` if it goes to `output`.
+    pub trace: Option<String>,
+    random: Random,
+    open: HashMap<Channel, OpenFile>,
+    next_channel: Channel,
+}
+
+impl Default for MemoryHost {
+    fn default() -> Self {
+        MemoryHost::new()
+    }
+}
+
+impl MemoryHost {
+    pub fn new() -> MemoryHost {
+        MemoryHost {
+            output: String::new(),
+            input: VecDeque::new(),
+            keys: VecDeque::new(),
+            files: HashMap::new(),
+            events: Vec::new(),
+            time: 0,
+            non_blocking: false,
+            trace: None,
+            random: Random::new(1),
+            open: HashMap::new(),
+            next_channel: 1,
+        }
+    }
+
+    /// Queues lines to be returned by `INPUT`.
+    pub fn with_input(mut self, lines: &[&str]) -> MemoryHost {
+        self.input.extend(lines.iter().map(|l| l.to_string()));
+        self
+    }
+
+    /// Queues key presses to be returned by `GET` and `INKEY`.
+    pub fn with_keys(mut self, keys: &str) -> MemoryHost {
+        self.keys.extend(keys.bytes());
+        self
+    }
+
+    /// Makes `INPUT` and `GET` fail with [`ErrorKind::WouldBlock`] while no
+    /// input is queued, like a host that can't block.
+    pub fn non_blocking(mut self) -> MemoryHost {
+        self.non_blocking = true;
+        self
+    }
+
+    /// Collects `TRACE` output in `trace` instead of `output`.
+    pub fn with_separate_trace(mut self) -> MemoryHost {
+        self.trace = Some(String::new());
+        self
+    }
+
+    pub fn with_file(mut self, name: &str, content: &[u8]) -> MemoryHost {
+        self.files.insert(name.to_string(), content.to_vec());
+        self
+    }
+}
+
+impl Host for MemoryHost {
+    fn write_text(&mut self, text: &str) -> std::io::Result<()> {
+        self.output.push_str(text);
+        Ok(())
+    }
+
+    fn trace(&mut self, text: &str) -> std::io::Result<()> {
+        match &mut self.trace {
+            None => self.output.push_str(text),
+            Some(t) => t.push_str(text)
+        }
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        if self.input.is_empty() && self.non_blocking {
+            return Err(ErrorKind::WouldBlock.into());
+        }
+
+        Ok(self.input.pop_front())
+    }
+
+    fn read_key(&mut self, timeout: Option<u32>) -> std::io::Result<Option<u8>> {
+        if self.keys.is_empty() && timeout.is_none() && self.non_blocking {
+            return Err(ErrorKind::WouldBlock.into());
+        }
+
+        let key = self.keys.pop_front();
+
+        if key.is_none() {
+            if let Some(t) = timeout {
+                self.time += t as i64;
+            }
+        }
+
+        Ok(key)
+    }
+
+    fn time(&mut self) -> i64 {
+        self.time
+    }
+
+    fn set_time(&mut self, centiseconds: i64) {
+        self.time = centiseconds;
+    }
+
+    fn random(&mut self) -> f64 {
+        self.random.next_float()
+    }
+
+    fn seed_random(&mut self, seed: i64) {
+        self.random.set_state(seed as u64);
+    }
+
+    fn random_state(&self) -> Option<u64> {
+        Some(self.random.state())
+    }
+
+    fn set_random_state(&mut self, state: u64) {
+        self.random.set_state(state);
+    }
+
+    fn open_file(&mut self, name: &str, mode: FileMode) -> std::io::Result<Option<Channel>> {
+        match mode {
+            FileMode::Read => if !self.files.contains_key(name) {
+                return Ok(None);
+            },
+            FileMode::Write => {
+                self.files.insert(name.to_string(), Vec::new());
+            }
+        }
+
+        let channel = self.next_channel;
+        self.next_channel += 1;
+        self.open.insert(channel, OpenFile { name: name.to_string(), mode, position: 0 });
+
+        Ok(Some(channel))
+    }
+
+    fn close_file(&mut self, channel: Channel) -> std::io::Result<()> {
+        match self.open.remove(&channel) {
+            None => Err(unknown_channel(channel)),
+            Some(_) => Ok(())
+        }
+    }
+
+    fn read_byte(&mut self, channel: Channel) -> std::io::Result<Option<u8>> {
+        let file = self.open.get_mut(&channel).ok_or_else(|| unknown_channel(channel))?;
+        let byte = self.files.get(&file.name).and_then(|content| content.get(file.position)).copied();
+
+        if byte.is_some() {
+            file.position += 1;
+        }
+
+        Ok(byte)
+    }
+
+    fn write_byte(&mut self, channel: Channel, byte: u8) -> std::io::Result<()> {
+        let file = self.open.get_mut(&channel).ok_or_else(|| unknown_channel(channel))?;
+
+        if file.mode != FileMode::Write {
+            return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "File not open for writing"));
+        }
+
+        self.files.entry(file.name.clone()).or_default().push(byte);
+        file.position += 1;
+
+        Ok(())
+    }
+
+    fn at_eof(&mut self, channel: Channel) -> std::io::Result<bool> {
+        let file = self.open.get(&channel).ok_or_else(|| unknown_channel(channel))?;
+        let length = self.files.get(&file.name).map(|content| content.len()).unwrap_or(0);
+
+        Ok(file.position >= length)
+    }
+
+    fn vdu(&mut self, codes: &[u8]) -> std::io::Result<()> {
+        self.events.push(HostEvent::Vdu(codes.to_vec()));
+        Ok(())
+    }
+
+    fn plot(&mut self, mode: i32, x: i32, y: i32) -> std::io::Result<()> {
+        self.events.push(HostEvent::Plot { mode, x, y });
+        Ok(())
+    }
+
+    fn sound(&mut self, channel: i32, amplitude: i32, pitch: i32, duration: i32) -> std::io::Result<()> {
+        self.events.push(HostEvent::Sound { channel, amplitude, pitch, duration });
+        Ok(())
+    }
+}
@@ -1,166 +1,144 @@
 use std::io::Write;
+use std::rc::Rc;
+use peginator::PegParser;
 use crate::bool_expression::ComputeBool;
-use crate::error::InterpreterError;
+use crate::error::{BasicError, InterpreterError};
 use crate::error::InterpreterError::NotImplemented;
 use crate::expression::Compute;
 use crate::interpreter::ExecutionResult::Exit;
 use crate::interpreter::ExitReason::{For, While};
-use crate::parser::{Assignment, Block, ForAssignment, ForStatement, IfStatement, NumericVariable_type_dem, PrintListItem_value, PrintStatement, Program, Statement, StringAssignment, WhileStatement};
-
-use crate::scope::{Float, Integer, Scope};
+use crate::host::{Channel, Host, StdHost};
+use crate::limits::{CancelToken, Limits};
+use crate::listing::Listing;
+use crate::memory::{self, Memory};
+use crate::native::{Native, Natives};
+use crate::profiler::Profile;
+use crate::resident::{self, Resident};
+use crate::trace::Trace;
+use crate::ast::{Block, Condition, Expression, ForStatement, Operand, PrintItem, Procedure, Program, Statement, StringValue, TraceMode, Variable};
+use crate::checker::{self, Diagnostic, NativeNames};
+use crate::compiler::Chunk;
+use crate::optimizer::optimize;
+use crate::vm::{Status, Vm};
+use crate::parser;
+use crate::scope::{Byte, DataType, Float, Integer, IntegerModel, Scope};
+#[cfg(feature = "serde")]
+use crate::snapshot::{self, Snapshot};
 use crate::value::Value;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum ExitReason {
     For,
     While,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum ExecutionResult {
     Ok,
     ForCompleted,
     Exit(ExitReason),
+    End,
+    /// `ENDPROC`
+    Return,
+    /// The program stops without `END`, like on `EXIT` outside of any loop
+    /// in a procedure.
+    Halt,
 }
 
+/// How a program run came to a stop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The last statement was executed.
+    Finished,
+    /// An `END` statement was executed.
+    Ended,
+    /// The program was stopped through its [`CancelToken`].
+    Escaped,
+    /// The program used up its step limit.
+    StepLimitExceeded,
+}
 
-trait Execute {
-    #[allow(unused_variables)]
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
-        Ok(ExecutionResult::Ok)
-    }
 
-    #[allow(unused_variables)]
-    fn execute(&self, scope: &mut Scope) -> Result<ExecutionResult, InterpreterError> {
-        Ok(ExecutionResult::Ok)
-    }
+trait Execute {
+    fn execute(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError>;
 }
 
-impl Execute for PrintStatement {
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
-        for i in 0..self.list.len() {
-            let item = self.list.get(i).unwrap();
-
-            match &item.value {
-                PrintListItem_value::Expression(e) => {
-                    let v = e.compute(scope)?;
-
-                    match v {
-                        Value::String(s) => stdout.write_all(s.as_bytes()).unwrap(),
-                        Value::Integer(i) => stdout.write_all(format!("{}", i).as_bytes()).unwrap(),
-                        Value::Float(f) => stdout.write_all(format!("{}", f).as_bytes()).unwrap(),
-                        Value::Boolean(b) => stdout.write_all(format!("{}", b).as_bytes()).unwrap(),
-                        _ => {}
-                    }
+fn print(items: &[PrintItem], scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    for item in items {
+        match &item.value {
+            Operand::Number(e) => {
+                let v = e.compute(scope, host)?;
+
+                match v {
+                    Value::String(s) => host.write_text(&s)?,
+                    v => host.write_text(&scope.format(&v))?
                 }
+            }
 
-                PrintListItem_value::StringLiteral(s) => stdout.write_all(s.body.as_bytes()).unwrap(),
-                PrintListItem_value::StringVariable(s) => stdout.write_all(scope.get_string(&s.name)?.as_bytes()).unwrap()
-            };
+            Operand::String(StringValue::Literal(s)) => host.write_text(s)?,
+            Operand::String(StringValue::Variable(v)) => host.write_text(scope.string(v.slot)?)?
+        };
 
-            match item.sep {
-                None => stdout.write_all("\n".as_bytes()).unwrap(),
-                Some(_) => {}
-            };
+        if item.newline {
+            host.write_text("\n")?;
         }
-
-        Ok(ExecutionResult::Ok)
     }
+
+    Ok(ExecutionResult::Ok)
 }
 
-impl Execute for Assignment {
-    fn execute(&self, scope: &mut Scope) -> Result<ExecutionResult, InterpreterError> {
-        match &self.variable.type_dem {
-            None => {
-                let v = self.value.compute_float(scope)?;
-                scope.set_float(&self.variable.name, v);
-            }
-            Some(d) => match d {
-                NumericVariable_type_dem::ByteDenominator(_) => {
-                    let v = self.value.compute_byte(scope)?;
-                    scope.set_byte(&self.variable.name, v);
-                }
-                NumericVariable_type_dem::FloatDenominator(_) => {
-                    let v = self.value.compute_float(scope)?;
-                    scope.set_float(&self.variable.name, v);
-                }
-                NumericVariable_type_dem::IntegerDenominator(_) => {
-                    let v = self.value.compute_integer(scope)?;
-                    scope.set_int(&self.variable.name, v);
-                }
-            }
-        }
+fn input(prompt: &str, variable: &Variable, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    host.write_text(prompt)?;
+    host.flush()?;
 
-        Ok(ExecutionResult::Ok)
-    }
-}
+    let line = match host.read_line()? {
+        None => return Err(InterpreterError::EndOfInput),
+        Some(l) => l
+    };
 
-impl Execute for ForAssignment {
-    fn execute(&self, scope: &mut Scope) -> Result<ExecutionResult, InterpreterError> {
-        match &self.variable.type_dem {
-            None => {
-                let v = self.value.compute_float(scope)?;
-                scope.set_float(&self.variable.name, v);
-            }
-            Some(d) => match d {
-                NumericVariable_type_dem::ByteDenominator(_) => {
-                    let v = self.value.compute_byte(scope)?;
-                    scope.set_byte(&self.variable.name, v);
-                }
-                NumericVariable_type_dem::FloatDenominator(_) => {
-                    let v = self.value.compute_float(scope)?;
-                    scope.set_float(&self.variable.name, v);
-                }
-                NumericVariable_type_dem::IntegerDenominator(_) => {
-                    let v = self.value.compute_integer(scope)?;
-                    scope.set_int(&self.variable.name, v);
-                }
-            }
+    let value = match variable.data_type {
+        DataType::String => Value::String(line),
+        t => {
+            let number = line.trim().parse::<Float>().unwrap_or(0.0);
+            Value::Float(number).convert(t, scope.integer_model())?
         }
+    };
+    scope.set(variable.slot, value);
 
-        Ok(ExecutionResult::Ok)
-    }
+    Ok(ExecutionResult::Ok)
 }
 
-impl Execute for StringAssignment {
-    fn execute(&self, scope: &mut Scope) -> Result<ExecutionResult, InterpreterError> {
-        let v = self.value.body.to_string();
-
-        scope.set_string(&self.variable.name, v);
+fn assign(variable: &Variable, value: &Expression, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    let v = value.compute(scope, host)?.convert(variable.data_type, scope.integer_model())?;
+    scope.set(variable.slot, v);
 
-        Ok(ExecutionResult::Ok)
-    }
+    Ok(ExecutionResult::Ok)
 }
 
 impl Execute for ForStatement {
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
+    fn execute(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
 
-        self.assignment.execute(scope)?;
+        assign(&self.variable, &self.start, scope, host)?;
 
-        match &self.assignment.variable.type_dem {
-            None => self.do_loop_float(scope, stdout),
-
-            Some(d) => match d {
-                // NumericVariable_type_dem::ByteDenominator(_) => self.do_loop::<Byte>(scope, stdout),
-                NumericVariable_type_dem::FloatDenominator(_) => self.do_loop_float(scope, stdout),
-                NumericVariable_type_dem::IntegerDenominator(_) => self.do_loop_integer(scope, stdout),
-                _ => Err(NotImplemented("Loops for other than integers and floats".to_string()))
-            }
+        match self.variable.data_type {
+            DataType::Float => self.do_loop_float(scope, host),
+            DataType::Integer => self.do_loop_integer(scope, host),
+            _ => Err(NotImplemented("Loops for other than integers and floats".to_string()))
         }
     }
 }
 
 impl ForStatement {
-    fn do_loop_integer(&self, scope: &mut Scope, stdout: &mut impl Write)
+    fn do_loop_integer(&self, scope: &mut Scope, host: &mut dyn Host)
                        -> Result<ExecutionResult, InterpreterError> {
-        let target = self.target.compute_integer(scope)?;
+        let target = self.target.compute(scope, host)?.to_integer()?;
         let step: Integer = match &self.step {
-            Some(s) => s.value.compute_integer(scope)?,
+            Some(s) => s.compute(scope, host)?.to_integer()?,
             None => 1,
         };
 
         loop {
-            match self.iterate_integer(target, step, scope, stdout)? {
+            match self.iterate_integer(target, step, scope, host)? {
                 ExecutionResult::Ok => {}
 
                 ExecutionResult::ForCompleted => {
@@ -173,20 +151,23 @@ impl ForStatement {
                 Exit(ExitReason::While) => {
                     return Ok(Exit(While));
                 }
+                r @ (ExecutionResult::End | ExecutionResult::Return | ExecutionResult::Halt) => {
+                    return Ok(r);
+                }
             }
         }
     }
 
-    fn do_loop_float(&self, scope: &mut Scope, stdout: &mut impl Write)
+    fn do_loop_float(&self, scope: &mut Scope, host: &mut dyn Host)
                        -> Result<ExecutionResult, InterpreterError> {
-        let target = self.target.compute_float(scope)?;
+        let target = self.target.compute(scope, host)?.to_float()?;
         let step: Float = match &self.step {
-            Some(s) => s.value.compute_float(scope)?,
+            Some(s) => s.compute(scope, host)?.to_float()?,
             None => 1.0,
         };
 
         loop {
-            match self.iterate_float(target, step, scope, stdout)? {
+            match self.iterate_float(target, step, scope, host)? {
                 ExecutionResult::Ok => {}
 
                 ExecutionResult::ForCompleted => {
@@ -199,26 +180,32 @@ impl ForStatement {
                 Exit(ExitReason::While) => {
                     return Ok(Exit(While));
                 }
+                r @ (ExecutionResult::End | ExecutionResult::Return | ExecutionResult::Halt) => {
+                    return Ok(r);
+                }
             }
         }
     }
 
-    fn iterate_integer(&self, target: Integer, step: Integer, scope: &mut Scope, stdout: &mut impl Write)
+    fn iterate_integer(&self, target: Integer, step: Integer, scope: &mut Scope, host: &mut dyn Host)
                        -> Result<ExecutionResult, InterpreterError> {
-        let result = self.body.execute_stdout(scope, stdout)?;
+        let result = self.body.execute(scope, host)?;
 
 
         match result {
             ExecutionResult::Ok => {
-                let curr = scope.get_int(&self.assignment.variable.name)?;
+                scope.step()?;
 
-                let next = curr + step;
+                let curr = scope.integer(self.variable.slot)?;
+
+                let next = curr.checked_add(step).ok_or(InterpreterError::NumberTooBig)?;
+                let next = scope.integer_model().check(next)?;
 
                 if next > target {
                     return Ok(ExecutionResult::ForCompleted);
                 }
 
-                scope.set_int(&self.assignment.variable.name, next.clone());
+                scope.set(self.variable.slot, Value::Integer(next));
 
                 Ok(ExecutionResult::Ok)
             }
@@ -227,18 +214,22 @@ impl ForStatement {
                 Ok(Exit(reason))
             }
 
+            ExecutionResult::End | ExecutionResult::Return | ExecutionResult::Halt => Ok(result),
+
             ExecutionResult::ForCompleted => Err(InterpreterError::OperationUnsupported)
         }
     }
 
-    fn iterate_float(&self, target: Float, step: Float, scope: &mut Scope, stdout: &mut impl Write)
+    fn iterate_float(&self, target: Float, step: Float, scope: &mut Scope, host: &mut dyn Host)
                        -> Result<ExecutionResult, InterpreterError> {
-        let result = self.body.execute_stdout(scope, stdout)?;
+        let result = self.body.execute(scope, host)?;
 
 
         match result {
             ExecutionResult::Ok => {
-                let curr = scope.get_float(&self.assignment.variable.name)?;
+                scope.step()?;
+
+                let curr = scope.float(self.variable.slot)?;
 
                 let next = curr + step;
 
@@ -246,7 +237,7 @@ impl ForStatement {
                     return Ok(ExecutionResult::ForCompleted);
                 }
 
-                scope.set_float(&self.assignment.variable.name, next.clone());
+                scope.set(self.variable.slot, Value::Float(next));
 
                 Ok(ExecutionResult::Ok)
             }
@@ -255,84 +246,902 @@ impl ForStatement {
                 Ok(Exit(reason))
             }
 
+            ExecutionResult::End | ExecutionResult::Return | ExecutionResult::Halt => Ok(result),
+
             ExecutionResult::ForCompleted => Err(InterpreterError::OperationUnsupported)
         }
     }
 }
 
-impl Execute for IfStatement {
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
-        let c = self.condition.compute_bool(scope)?.as_bool()?;
+fn if_statement(condition: &Condition, then_block: &Block, else_block: &Option<Block>, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    let c = condition.compute_bool(scope, host)?.as_bool()?;
 
-        if c == true {
-            self.then_block.execute_stdout(scope, stdout)
-        } else {
-            match &self.else_block {
-                None => Ok(ExecutionResult::Ok),
-                Some(e) => e.execute_stdout(scope, stdout)
-            }
+    if c {
+        then_block.execute(scope, host)
+    } else {
+        match else_block {
+            None => Ok(ExecutionResult::Ok),
+            Some(e) => e.execute(scope, host)
         }
     }
 }
 
-impl Execute for WhileStatement {
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
-        loop {
-            let c = self.condition.compute_bool(scope)?.as_bool()?;
+fn while_loop(condition: &Condition, body: &Block, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    loop {
+        scope.step()?;
 
-            if c {
-                let r = self.body.execute_stdout(scope, stdout)?;
+        let c = condition.compute_bool(scope, host)?.as_bool()?;
 
-                match r {
-                    ExecutionResult::Ok => {}
-                    Exit(While) => return Ok(ExecutionResult::Ok),
-                    Exit(For) => return Ok(Exit(For)),
-                    ExecutionResult::ForCompleted => return Err(InterpreterError::OperationUnsupported)
-                }
-            } else {
-                break;
+        if c {
+            let r = body.execute(scope, host)?;
+
+            match r {
+                ExecutionResult::Ok => {}
+                Exit(While) => return Ok(ExecutionResult::Ok),
+                Exit(For) => return Ok(Exit(For)),
+                ExecutionResult::End | ExecutionResult::Return | ExecutionResult::Halt => return Ok(r),
+                ExecutionResult::ForCompleted => return Err(InterpreterError::OperationUnsupported)
             }
+        } else {
+            break;
         }
-        Ok(ExecutionResult::Ok)
     }
+    Ok(ExecutionResult::Ok)
+}
+
+/// Runs a procedure. Arguments are computed before the parameters are made
+/// local, so they can refer to variables of the same name.
+fn call(name: &str, arguments: &[Operand], scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    let procedure = match (scope.procedure(name), scope.native_procedure(name)) {
+        (Err(_), Some(native)) => return call_native(name, &native, arguments, scope, host),
+        (procedure, _) => procedure?
+    };
+
+    if arguments.len() != procedure.parameters.len() {
+        return Err(InterpreterError::Arguments);
+    }
+
+    let mut values = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        values.push(argument.compute_bool(scope, host)?);
+    }
+
+    scope.enter();
+    scope.trace().enter(name, host)?;
+    if let Some(p) = scope.profile_mut() {
+        p.enter(name);
+    }
+    for (parameter, value) in procedure.parameters.iter().zip(values) {
+        scope.make_local(parameter.slot)?;
+        scope.set(parameter.slot, value.convert(parameter.data_type, scope.integer_model())?);
+    }
+
+    let result = procedure.body.execute(scope, host);
+    scope.leave()?;
+    scope.trace().leave(host)?;
+    if let Some(p) = scope.profile_mut() {
+        p.leave();
+    }
+
+    match result? {
+        Exit(_) => Ok(ExecutionResult::Halt),
+        r @ (ExecutionResult::End | ExecutionResult::Halt) => Ok(r),
+        _ => Ok(ExecutionResult::Ok)
+    }
+}
+
+/// Calls a native procedure, see [`Interpreter::register_proc`].
+fn call_native(name: &str, native: &Native, arguments: &[Operand], scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+    let mut values = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        values.push(argument.compute_bool(scope, host)?);
+    }
+
+    scope.trace().enter(name, host)?;
+    native.call(values, scope.integer_model())?;
+    scope.trace().leave(host)?;
+
+    Ok(ExecutionResult::Ok)
 }
 
 impl Execute for Statement {
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
-        return match self {
-            Statement::EndStatement(_) => Ok(ExecutionResult::Ok),
-            Statement::PrintStatement(s) => s.execute_stdout(scope, stdout),
-            Statement::Assignment(a) => a.execute(scope),
-            Statement::StringAssignment(a) => a.execute(scope),
-            Statement::ForStatement(f) => f.execute_stdout(scope, stdout),
-            Statement::IfStatement(i) => i.execute_stdout(scope, stdout),
-            Statement::ExitForStatement(_) => Ok(Exit(For)),
-            Statement::WhileStatement(w) => w.execute_stdout(scope, stdout),
-            Statement::ExitWhileStatement(_) => Ok(Exit(While))
-        };
+    fn execute(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+        match self {
+            Statement::End => Ok(ExecutionResult::End),
+            Statement::Print(items) => print(items, scope, host),
+            Statement::Input { prompt, variable } => input(prompt, variable, scope, host),
+            Statement::Assignment(variable, value) => assign(variable, value, scope, host),
+            Statement::StringAssignment(variable, value) => {
+                scope.set(variable.slot, Value::String(value.to_string()));
+                Ok(ExecutionResult::Ok)
+            }
+            Statement::For(f) => f.execute(scope, host),
+            Statement::If { condition, then_block, else_block } => if_statement(condition, then_block, else_block, scope, host),
+            Statement::ExitFor => Ok(Exit(For)),
+            Statement::While { condition, body } => while_loop(condition, body, scope, host),
+            Statement::ExitWhile => Ok(Exit(While)),
+
+            Statement::SetTime(t) => {
+                let t = t.compute(scope, host)?.to_integer()?;
+                host.set_time(t);
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Cls => {
+                host.vdu(&[12])?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Vdu(values) => {
+                let mut codes = Vec::with_capacity(values.len());
+
+                for v in values {
+                    codes.push(v.compute(scope, host)?.to_integer()? as Byte);
+                }
+                host.vdu(&codes)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Plot { mode, x, y } => {
+                let [mode, x, y] = integer_arguments([mode, x, y], scope, host)?;
+                host.plot(mode, x, y)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Move { x, y } => {
+                let [x, y] = integer_arguments([x, y], scope, host)?;
+                host.plot(4, x, y)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Draw { x, y } => {
+                let [x, y] = integer_arguments([x, y], scope, host)?;
+                host.plot(5, x, y)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Sound { channel, amplitude, pitch, duration } => {
+                let [channel, amplitude, pitch, duration] = integer_arguments([channel, amplitude, pitch, duration], scope, host)?;
+                host.sound(channel, amplitude, pitch, duration)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Bput { channel, value } => {
+                let channel = channel.compute(scope, host)?.to_integer()?;
+                let value = value.compute(scope, host)?.to_integer()?;
+                host.write_byte(channel as Channel, value as Byte)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Close(channel) => {
+                let channel = channel.compute(scope, host)?.to_integer()?;
+                host.close_file(channel as Channel)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Dim { variable, size } => {
+                let n = size.compute(scope, host)?.to_integer()?;
+                let address = scope.memory_mut().dim(n)?;
+                scope.set(variable.slot, Value::Integer(address).convert(variable.data_type, scope.integer_model())?);
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Poke { indirection, address, value } => {
+                let address = address.compute(scope, host)?.to_integer()?;
+                let value = value.compute_bool(scope, host)?;
+                scope.memory_mut().poke(*indirection, address, &value)?;
+                Ok(ExecutionResult::Ok)
+            }
+
+            Statement::Call { name, arguments } => call(name, arguments, scope, host),
+            Statement::Return => Ok(ExecutionResult::Return),
+
+            Statement::Trace(mode) => {
+                let trace = match mode {
+                    TraceMode::On => scope.trace().lines(),
+                    TraceMode::Off => Trace::off(),
+                    TraceMode::Procedures => scope.trace().procedures(),
+                    TraceMode::Below(line) => scope.trace().lines_below(line.compute(scope, host)?.to_integer()?.max(0) as usize)
+                };
+                scope.set_trace(trace);
+                Ok(ExecutionResult::Ok)
+            }
+        }
     }
 }
 
 
 impl Execute for Block {
-    fn execute_stdout(&self, scope: &mut Scope, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
-        for i in 0..self.statements.len() {
-            let statement = self.statements.get(i).unwrap();
-            let result = statement.execute_stdout(scope, stdout)?;
-
-            match result {
-                Exit(_) => return Ok(result.clone()),
-                _ => {}
-            };
+    fn execute(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+        for located in self {
+            scope.step()?;
+            scope.trace().line(located.position.line, host)?;
+            if let Some(p) = scope.profile_mut() {
+                p.statement(located.position.line);
+            }
+
+            let result = located.statement.execute(scope, host)?;
+
+            if let Exit(_) | ExecutionResult::End | ExecutionResult::Return | ExecutionResult::Halt = result {
+                return Ok(result);
+            }
         }
 
         Ok(ExecutionResult::Ok)
     }
 }
 
+fn integer_arguments<const N: usize>(values: [&Expression; N], scope: &mut Scope, host: &mut dyn Host) -> Result<[i32; N], InterpreterError> {
+    let mut result = [0; N];
+
+    for (r, v) in result.iter_mut().zip(values) {
+        *r = v.compute(scope, host)?.to_integer()? as i32;
+    }
+
+    Ok(result)
+}
+
 impl Program {
+    /// Runs the program with the tree-walker.
+    pub fn execute_on(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+        scope.set_procedures(&self.procedures);
+
+        let result = match self.body.execute(scope, host) {
+            Ok(ExecutionResult::Return) => Err(InterpreterError::NotInProcedure),
+            r => r
+        };
+        host.flush()?;
+
+        result
+    }
+}
+
+impl parser::Program {
     pub fn execute(&self, stdout: &mut impl Write) -> Result<ExecutionResult, InterpreterError> {
+        self.execute_on(&mut StdHost::new(std::io::empty(), stdout))
+    }
+
+    pub fn execute_on(&self, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
         let mut scope = Scope::new();
-        self.body.execute_stdout(&mut scope, stdout)
+        let program = Program::resolve(self, scope.symbols_mut());
+
+        program.execute_on(&mut scope, host)
+    }
+}
+
+
+fn outcome(result: Result<ExecutionResult, InterpreterError>) -> Result<Outcome, BasicError> {
+    match result {
+        Ok(ExecutionResult::End) => Ok(Outcome::Ended),
+        Ok(_) => Ok(Outcome::Finished),
+        Err(InterpreterError::Escape) => Ok(Outcome::Escaped),
+        Err(InterpreterError::StepLimitExceeded) => Ok(Outcome::StepLimitExceeded),
+        Err(e) => Err(e.into())
+    }
+}
+
+/// Result of [`Interpreter::step`].
+#[derive(Debug)]
+pub enum StepResult {
+    /// The budget was used up, the program continues on the next step.
+    Running,
+    /// `INPUT` or `GET` is waiting for the host, the program continues on
+    /// the next step once input is available.
+    WaitingForInput,
+    Finished(Outcome),
+    Error(BasicError),
+}
+
+/// Program started with [`Interpreter::start`].
+pub(crate) struct Session {
+    /// Source of the program, which a snapshot compiles again.
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    pub(crate) code: String,
+    pub(crate) chunk: Chunk,
+    pub(crate) scope: Scope,
+    pub(crate) vm: Vm,
+}
+
+/// Runs programs against a [`Host`], which provides all input and output.
+///
+/// ```
+/// use bbbasic::{Interpreter, MemoryHost};
+///
+/// let mut interpreter = Interpreter::new(MemoryHost::new());
+///
+/// interpreter.run("PRINT 2 + 2").expect("Program failed");
+/// assert_eq!(interpreter.host().output, "4\n");
+/// ```
+pub struct Interpreter<H: Host = StdHost> {
+    host: H,
+    engine: Engine,
+    optimize: bool,
+    integers: IntegerModel,
+    step_limit: Option<u64>,
+    memory_limit: usize,
+    cancel: CancelToken,
+    trace: Trace,
+    profiling: bool,
+    profile: Option<Profile>,
+    /// Variables and procedures of the last program run, used by
+    /// [`Interpreter::execute`].
+    variables: Option<Scope>,
+    procedures: Vec<Procedure>,
+    /// Variables set by the host, which every program starts with.
+    presets: Vec<(String, DataType, Value)>,
+    /// `@%` and `A%` to `Z%`, handed on from one program to the next.
+    resident: Resident,
+    natives: Rc<Natives>,
+    pub(crate) session: Option<Session>,
+}
+
+/// Selects how [`Interpreter::run`] executes programs. Both engines produce
+/// the same output, which makes it possible to compare them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Engine {
+    /// Walks the syntax tree.
+    #[default]
+    TreeWalker,
+    /// Compiles the program to bytecode and runs it on a stack VM.
+    Bytecode,
+}
+
+impl<H: Host> Interpreter<H> {
+    pub fn new(host: H) -> Interpreter<H> {
+        Interpreter {
+            host,
+            engine: Engine::default(),
+            optimize: true,
+            integers: IntegerModel::default(),
+            step_limit: None,
+            memory_limit: memory::DEFAULT_LIMIT,
+            cancel: CancelToken::new(),
+            trace: Trace::off(),
+            profiling: false,
+            profile: None,
+            variables: None,
+            procedures: Vec::new(),
+            presets: Vec::new(),
+            resident: Resident::new(),
+            natives: Rc::new(Natives::default()),
+            session: None,
+        }
+    }
+
+    pub fn with_engine(mut self, engine: Engine) -> Interpreter<H> {
+        self.engine = engine;
+        self
+    }
+
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.engine = engine;
+    }
+
+    pub fn engine(&self) -> Engine {
+        self.engine
+    }
+
+    /// Constant folding is on by default. Turning it off runs programs
+    /// exactly as written, which helps when debugging the interpreter.
+    pub fn with_optimization(mut self, enabled: bool) -> Interpreter<H> {
+        self.optimize = enabled;
+        self
+    }
+
+    pub fn set_optimization(&mut self, enabled: bool) {
+        self.optimize = enabled;
+    }
+
+    /// Integers are 64-bit like in BBC BASIC for SDL by default.
+    /// [`IntegerModel::Acorn`] runs programs with the 32-bit integers of the
+    /// original BBC Micro.
+    pub fn with_integer_model(mut self, model: IntegerModel) -> Interpreter<H> {
+        self.integers = model;
+        self
+    }
+
+    pub fn set_integer_model(&mut self, model: IntegerModel) {
+        self.integers = model;
+    }
+
+    pub fn integer_model(&self) -> IntegerModel {
+        self.integers
+    }
+
+    /// Stops programs after `steps` steps with [`Outcome::StepLimitExceeded`].
+    /// A step is taken before every statement and on every loop iteration.
+    pub fn with_step_limit(mut self, steps: u64) -> Interpreter<H> {
+        self.step_limit = Some(steps);
+        self
+    }
+
+    pub fn set_step_limit(&mut self, steps: Option<u64>) {
+        self.step_limit = steps;
+    }
+
+    /// Lets programs reserve at most `bytes` bytes with `DIM`, 1 MiB by
+    /// default. `DIM` fails with "DIM space" beyond that.
+    pub fn with_memory_limit(mut self, bytes: usize) -> Interpreter<H> {
+        self.memory_limit = bytes;
+        self
+    }
+
+    pub fn set_memory_limit(&mut self, bytes: usize) {
+        self.memory_limit = bytes;
+    }
+
+    /// Shares `token` with the interpreter, see [`Interpreter::cancel_token`].
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Interpreter<H> {
+        self.cancel = token;
+        self
+    }
+
+    /// Token to stop a running program from another thread, which then ends
+    /// with [`Outcome::Escaped`]. Programs started while the token is
+    /// cancelled stop right away.
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel.clone()
+    }
+
+    /// Starts programs with `trace` on, as if they began with `TRACE`
+    /// statements.
+    pub fn with_trace(mut self, trace: Trace) -> Interpreter<H> {
+        self.trace = trace;
+        self
+    }
+
+    pub fn set_trace(&mut self, trace: Trace) {
+        self.trace = trace;
+    }
+
+    /// Records a [`Profile`] of every program run, see
+    /// [`Interpreter::profile`].
+    pub fn with_profiling(mut self, enabled: bool) -> Interpreter<H> {
+        self.profiling = enabled;
+        self
+    }
+
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiling = enabled;
+    }
+
+    /// Profile of the last program run, once it has stopped. Only recorded
+    /// with profiling turned on.
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    /// Makes `FNname` call `function` with arguments of the types in
+    /// `parameters`, given with or without `FN`. Calls with another number of
+    /// arguments fail with "Incorrect arguments", arguments are converted
+    /// like those of a procedure. [`InterpreterError::Native`] reports errors
+    /// of the function to the program.
+    ///
+    /// ```
+    /// use bbbasic::{DataType, Interpreter, MemoryHost, Value};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new());
+    /// interpreter.register_fn("FNdistance", &[DataType::Float, DataType::Float], |args: &[Value]| {
+    ///     let (x, y) = (args[0].to_float()?, args[1].to_float()?);
+    ///     Ok(Value::Float((x * x + y * y).sqrt()))
+    /// });
+    ///
+    /// interpreter.run("PRINT FNdistance(3, 4)").expect("Program failed");
+    /// assert_eq!(interpreter.host().output, "5\n");
+    /// ```
+    pub fn register_fn(&mut self, name: &str, parameters: &[DataType], function: impl Fn(&[Value]) -> Result<Value, InterpreterError> + 'static) {
+        let name = name.strip_prefix("FN").unwrap_or(name);
+        Rc::make_mut(&mut self.natives).functions.insert(name.to_string(), Native::new(parameters, function));
+    }
+
+    /// Makes `PROCname` call `procedure`, given with or without `PROC`, see
+    /// [`Interpreter::register_fn`]. A procedure the program defines itself
+    /// is called instead.
+    pub fn register_proc(&mut self, name: &str, parameters: &[DataType], procedure: impl Fn(&[Value]) -> Result<(), InterpreterError> + 'static) {
+        let name = name.strip_prefix("PROC").unwrap_or(name);
+        let native = Native::new(parameters, move |arguments| procedure(arguments).map(|_| Value::Integer(0)));
+        Rc::make_mut(&mut self.natives).procedures.insert(name.to_string(), native);
+    }
+
+    /// Checks `code` like [`checker::check_source`], knowing the natives
+    /// registered so far.
+    ///
+    /// ```
+    /// use bbbasic::{Interpreter, MemoryHost};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new());
+    /// interpreter.register_proc("PROCbeep", &[], |_| Ok(()));
+    ///
+    /// assert!(interpreter.check("PROCbeep").unwrap().is_empty());
+    /// assert_eq!(interpreter.check("PROCboop\nPRINT FNx").unwrap().len(), 2);
+    /// ```
+    pub fn check(&self, code: &str) -> Result<Vec<Diagnostic>, BasicError> {
+        let natives = NativeNames {
+            functions: self.natives.functions.keys().cloned().collect(),
+            procedures: self.natives.procedures.keys().cloned().collect(),
+        };
+
+        checker::check_source_with(code, &natives)
+    }
+
+    /// Parses and executes `code`. Neither syntax errors nor runtime errors
+    /// panic, both are reported through [`BasicError`].
+    pub fn run(&mut self, code: &str) -> Result<Outcome, BasicError> {
+        self.run_numbered(code, &[])
+    }
+
+    /// Runs a program with line numbers like [`Interpreter::run`]. Its
+    /// statements are known by the numbers of their lines, which `TRACE`,
+    /// `TRACE n`, the profile and syntax errors use.
+    ///
+    /// ```
+    /// use bbbasic::{Interpreter, Listing, MemoryHost};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new());
+    /// interpreter.run_listing(&Listing::from_source("100 TRACE ON\n200 x = 1\n")).expect("Program failed");
+    ///
+    /// assert_eq!(interpreter.host().output, "[200] ");
+    /// ```
+    pub fn run_listing(&mut self, listing: &Listing) -> Result<Outcome, BasicError> {
+        let numbers: Vec<u32> = listing.lines().map(|(n, _)| n).collect();
+
+        self.run_numbered(&listing.source(), &numbers).map_err(|e| match e {
+            BasicError::Parse { line, column, message } => {
+                let line = listing.line_number(line).map_or(line, |n| n as usize);
+                BasicError::Parse { line, column, message }
+            }
+            e => e
+        })
+    }
+
+    fn run_numbered(&mut self, code: &str, numbers: &[u32]) -> Result<Outcome, BasicError> {
+        self.session = None;
+        self.profile = None;
+
+        let (mut scope, program) = self.prepare(code, numbers)?;
+
+        let result = self.execute_program(&program, &mut scope);
+
+        self.keep(scope);
+        self.procedures = program.procedures;
+
+        outcome(result)
+    }
+
+    /// Executes `code` like a line typed in immediate mode: it runs on the
+    /// variables left by the last program or call, and can call the
+    /// procedures of the last program run. Constant folding is off, as the
+    /// variables live on.
+    ///
+    /// ```
+    /// use bbbasic::{Interpreter, MemoryHost};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new());
+    ///
+    /// interpreter.execute("x% = 20").expect("Statement failed");
+    /// interpreter.execute("PRINT x% + 1").expect("Statement failed");
+    /// assert_eq!(interpreter.host().output, "21\n");
+    /// ```
+    pub fn execute(&mut self, code: &str) -> Result<Outcome, BasicError> {
+        self.session = None;
+        self.profile = None;
+
+        let parsed = parser::Program::parse(code).map_err(|e| BasicError::from_parse_error(code, &e))?;
+
+        let mut scope = match self.variables.take() {
+            Some(mut scope) => {
+                scope.set_limits(self.limits());
+                scope.set_natives(self.natives.clone());
+                if self.profiling {
+                    scope.set_profile(Profile::new());
+                }
+                scope
+            }
+            None => self.scope()
+        };
+
+        let mut program = Program::resolve_source(&parsed, code, scope.symbols_mut());
+        program.procedures.extend(self.procedures.iter().cloned());
+
+        let result = self.execute_program(&program, &mut scope);
+        self.keep(scope);
+
+        outcome(result)
+    }
+
+    /// Assigns a variable given with its suffix, like `width%` or `name$`,
+    /// converting `value` to its type. Every program run or started
+    /// afterwards starts with it, and lines passed to
+    /// [`Interpreter::execute`] see it right away. The resident variables
+    /// `@%` and `A%` to `Z%` are set once instead, programs can change them
+    /// for the ones run after them.
+    ///
+    /// ```
+    /// use bbbasic::{Interpreter, MemoryHost, Value};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new());
+    /// interpreter.set_variable("width%", 80).expect("Not a number");
+    /// interpreter.set_variable("name$", "Ada").expect("Not a string");
+    ///
+    /// interpreter.run("area% = width% * 25\nPRINT name$").expect("Program failed");
+    /// assert_eq!(interpreter.variable("area%").unwrap(), Value::Integer(2000));
+    /// ```
+    pub fn set_variable(&mut self, name: &str, value: impl Into<Value>) -> Result<(), InterpreterError> {
+        let (name, data_type) = DataType::of_name(name);
+        let value = value.into().convert(data_type, self.integers)?;
+
+        if let Some(index) = resident::index(name, data_type) {
+            self.resident.set(index, value.clone());
+        }
+
+        if let Some(scope) = &mut self.variables {
+            scope.set_named(name, data_type, value.clone());
+        }
+
+        self.presets.retain(|(n, t, _)| (n.as_str(), *t) != (name, data_type));
+        if resident::index(name, data_type).is_none() {
+            self.presets.push((name.to_string(), data_type, value));
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the variables set with [`Interpreter::set_variable`], programs
+    /// run afterwards start without them.
+    pub fn clear_presets(&mut self) {
+        self.presets.clear();
+    }
+
+    /// Value of a variable given with its suffix, like `name$`, of the
+    /// running program or else of the last program or line executed.
+    pub fn variable(&self, name: &str) -> Result<Value, InterpreterError> {
+        let (base, data_type) = DataType::of_name(name);
+
+        match (self.variable_scope(), resident::index(base, data_type)) {
+            (Some(scope), _) => scope.variable(name).cloned(),
+            (None, Some(index)) => Ok(self.resident.get(index).clone()),
+            (None, None) => Err(InterpreterError::UnknownVariable(base.to_string()))
+        }
+    }
+
+    /// Variables holding a value, named with the suffix of their type, see
+    /// [`Interpreter::variable`]. Each type has variables of its own, `n%`,
+    /// `n&`, `n$` and `n` are four variables, `n#` is the same as `n`.
+    pub fn variables(&self) -> Vec<(String, Value)> {
+        match self.variable_scope() {
+            None => Vec::new(),
+            Some(scope) => scope.variables().into_iter().map(|(n, v)| (n, v.clone())).collect()
+        }
+    }
+
+    fn variable_scope(&self) -> Option<&Scope> {
+        match &self.session {
+            Some(s) => Some(&s.scope),
+            None => self.variables.as_ref()
+        }
+    }
+
+    /// Keeps `scope` for [`Interpreter::execute`], and its resident variables
+    /// for the next program.
+    fn keep(&mut self, scope: Scope) {
+        self.resident = scope.resident();
+        self.variables = Some(scope);
+    }
+
+    /// Forgets the variables kept for [`Interpreter::execute`], like `CLEAR`.
+    /// The resident variables `@%` and `A%` to `Z%` live on.
+    pub fn clear(&mut self) {
+        if let Some(scope) = &mut self.variables {
+            scope.clear();
+        }
+    }
+
+    fn execute_program(&mut self, program: &Program, scope: &mut Scope) -> Result<ExecutionResult, InterpreterError> {
+        let result = match self.engine {
+            Engine::TreeWalker => program.execute_on(scope, &mut self.host),
+            Engine::Bytecode => program.compile().and_then(|chunk| chunk.execute_on(scope, &mut self.host))
+        };
+        self.profile = scope.take_profile();
+
+        result
+    }
+
+    /// Prepares `code` to be run bit by bit with [`Interpreter::step`],
+    /// always on the bytecode engine. A program started before is dropped.
+    ///
+    /// ```
+    /// use bbbasic::{Interpreter, MemoryHost, Outcome, StepResult};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new().non_blocking());
+    /// interpreter.start("INPUT n%\nPRINT n% * 2").expect("Program failed");
+    ///
+    /// assert!(matches!(interpreter.step(100), StepResult::WaitingForInput));
+    /// interpreter.host_mut().input.push_back("21".to_string());
+    ///
+    /// assert!(matches!(interpreter.step(100), StepResult::Finished(Outcome::Finished)));
+    /// assert_eq!(interpreter.host().output, "?42\n");
+    /// ```
+    pub fn start(&mut self, code: &str) -> Result<(), BasicError> {
+        self.session = None;
+        self.profile = None;
+
+        let (scope, program) = self.prepare(code, &[])?;
+        let chunk = program.compile()?;
+
+        self.procedures = program.procedures;
+        self.session = Some(Session { code: code.to_string(), chunk, scope, vm: Vm::new() });
+
+        Ok(())
+    }
+
+    /// Runs the started program for at most `budget` steps and returns
+    /// control to the caller, which can continue with another call. Once the
+    /// program finished or failed it has to be started again.
+    pub fn step(&mut self, budget: u64) -> StepResult {
+        let session = match &mut self.session {
+            None => return StepResult::Error(InterpreterError::NoProgram.into()),
+            Some(s) => s
+        };
+
+        let status = session.vm.resume(&session.chunk, &mut session.scope, &mut self.host, Some(budget));
+
+        if let Err(e) = self.host.flush() {
+            self.session = None;
+            return StepResult::Error(InterpreterError::Io(e).into());
+        }
+
+        let result = match status {
+            Ok(Status::Yielded) => return StepResult::Running,
+            Ok(Status::WaitingForInput) => return StepResult::WaitingForInput,
+            Ok(Status::Done(result)) => Ok(result),
+            Err(e) => Err(e)
+        };
+
+        self.profile = session.scope.take_profile();
+        if let Some(session) = self.session.take() {
+            self.keep(session.scope);
+        }
+
+        match outcome(result) {
+            Ok(o) => StepResult::Finished(o),
+            Err(e) => StepResult::Error(e)
+        }
+    }
+
+    /// Takes a [`Snapshot`] of the started program, which
+    /// [`Interpreter::restore`] continues, also on another interpreter.
+    /// Fails with [`InterpreterError::FilesOpen`] while the program has files
+    /// open.
+    ///
+    /// ```
+    /// use bbbasic::{Interpreter, MemoryHost, Outcome, StepResult};
+    ///
+    /// let mut interpreter = Interpreter::new(MemoryHost::new());
+    /// interpreter.start("FOR i% = 1 TO 3\n    PRINT i%\nNEXT").expect("Program failed");
+    /// interpreter.step(3);
+    /// let snapshot = interpreter.snapshot().expect("No program");
+    ///
+    /// let mut restored = Interpreter::new(MemoryHost::new());
+    /// restored.restore(&snapshot).expect("Restore failed");
+    /// assert!(matches!(restored.step(100), StepResult::Finished(Outcome::Finished)));
+    /// assert_eq!(restored.host().output, "2\n3\n");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn snapshot(&self) -> Result<Snapshot, InterpreterError> {
+        let session = self.session.as_ref().ok_or(InterpreterError::NoProgram)?;
+        if !session.vm.channels().is_empty() {
+            return Err(InterpreterError::FilesOpen);
+        }
+        let symbols = session.scope.symbols();
+        let (values, locals, resident) = session.scope.state();
+
+        Ok(Snapshot {
+            version: snapshot::VERSION,
+            code: session.code.clone(),
+            integers: self.integers,
+            optimize: self.optimize,
+            trace: session.scope.trace(),
+            variables: (0..symbols.len())
+                .map(|slot| format!("{}{}", symbols.name(slot), symbols.data_type(slot).map(DataType::suffix).unwrap_or("")))
+                .collect(),
+            values: values.to_vec(),
+            locals: locals.to_vec(),
+            memory: session.scope.memory().clone(),
+            resident: resident.clone(),
+            random: self.host.random_state(),
+            vm: session.vm.clone(),
+        })
+    }
+
+    /// Continues the program of `snapshot` with [`Interpreter::step`], in
+    /// place of any program started before. The integer model and constant
+    /// folding are set to those the program was started with.
+    #[cfg(feature = "serde")]
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), BasicError> {
+        self.session = None;
+        self.profile = None;
+        self.integers = snapshot.integers;
+        self.optimize = snapshot.optimize;
+
+        let (mut scope, program) = self.prepare(&snapshot.code, &[])?;
+        let chunk = program.compile()?;
+
+        let symbols = scope.symbols();
+        let matches = symbols.len() == snapshot.variables.len()
+            && snapshot.variables.iter().enumerate().all(|(slot, name)| {
+                let (name, data_type) = DataType::of_name(name);
+                symbols.find(name, data_type) == Some(slot)
+            });
+        let in_range = snapshot.values.len() <= symbols.len()
+            && snapshot.locals.iter().flatten().all(|(slot, _)| *slot < symbols.len());
+
+        if !matches || !in_range || !snapshot.vm.fits(&chunk) || !snapshot.vm.channels().is_empty() || snapshot.locals.len() != snapshot.vm.calls().len() {
+            return Err(InterpreterError::BadSnapshot.into());
+        }
+
+        if let Some(state) = snapshot.random {
+            self.host.set_random_state(state);
+        }
+        scope.set_trace(snapshot.trace);
+        scope.restore_state(snapshot.values.clone(), snapshot.locals.clone(), snapshot.resident.clone());
+        scope.set_memory(snapshot.memory.clone());
+
+        self.session = Some(Session { code: snapshot.code.clone(), chunk, scope, vm: snapshot.vm.clone() });
+
+        Ok(())
+    }
+
+    /// Whether a program started with [`Interpreter::start`] is still
+    /// running.
+    pub fn is_running(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Parses, resolves and optimizes `code`.
+    fn prepare(&self, code: &str, numbers: &[u32]) -> Result<(Scope, Program), BasicError> {
+        let parsed = parser::Program::parse(code).map_err(|e| BasicError::from_parse_error(code, &e))?;
+
+        let mut scope = self.scope();
+        let mut program = Program::resolve_numbered(&parsed, code, numbers, scope.symbols_mut());
+
+        if self.optimize {
+            optimize(&mut program, self.integers);
+        }
+
+        Ok((scope, program))
+    }
+
+    /// Empty scope configured for a program run.
+    fn scope(&self) -> Scope {
+        let mut scope = Scope::with_integer_model(self.integers);
+        scope.set_limits(self.limits());
+        scope.set_trace(self.trace);
+        scope.set_natives(self.natives.clone());
+        scope.set_memory(Memory::new(self.memory_limit));
+        scope.set_resident(self.resident.clone());
+        for (name, data_type, value) in &self.presets {
+            scope.set_named(name, *data_type, value.clone());
+        }
+        if self.profiling {
+            scope.set_profile(Profile::new());
+        }
+
+        scope
+    }
+
+    fn limits(&self) -> Limits {
+        Limits::none().with_steps(self.step_limit).with_cancel_token(self.cancel.clone())
+    }
+
+    pub fn host(&self) -> &H {
+        &self.host
+    }
+
+    pub fn host_mut(&mut self) -> &mut H {
+        &mut self.host
+    }
+
+    pub fn into_host(self) -> H {
+        self.host
     }
 }
@@ -0,0 +1,90 @@
+//! Programs as JSON, for tools built on top of the program tree. Only
+//! available with the `serde` feature.
+//!
+//! A document has the version of the format, the names of the variables
+//! and the program as in [`ast`](crate::ast):
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "variables": ["total%", "name$", "x"],
+//!   "program": { "body": [...], "procedures": [...] }
+//! }
+//! ```
+//!
+//! Variables are `{"slot": 0, "data_type": "Integer"}`, the slot being the
+//! index of their name in `variables`. Names carry their suffix, floats have
+//! none. Statements are `{"position": {"line": 1, "column": 1}, "statement":
+//! ...}`. Variants of enums are written as objects with the name of the
+//! variant as the only key, like `{"Assignment": [variable, expression]}`,
+//! those without fields as strings, like `"End"`. Number literals keep the
+//! text they were written as, `{"Number": {"Integer": "10"}}`.
+//!
+//! Changes to the format that break readers increase the version,
+//! [`import`] only reads documents of [`VERSION`].
+
+use serde::de::Error;
+use serde::{Deserialize, Serialize};
+use crate::ast::Program;
+use crate::error::BasicError;
+use crate::scope::{DataType, Symbols};
+
+/// Version of the format written by [`export`].
+pub const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Document {
+    version: u32,
+    variables: Vec<String>,
+    program: Program,
+}
+
+/// Parses `code` and exports it, see [`export`].
+///
+/// ```
+/// use bbbasic::json::export_source;
+///
+/// let json = export_source("x% = 1\n").unwrap();
+///
+/// assert!(json.contains("\"variables\": [\n    \"x%\"\n  ]"));
+/// assert!(json.contains("\"Integer\": \"1\""));
+/// ```
+pub fn export_source(code: &str) -> Result<String, BasicError> {
+    let (program, symbols) = Program::parse_source(code)?;
+
+    Ok(export(&program, &symbols))
+}
+
+/// `program` as a JSON document, its variables named after `symbols`.
+pub fn export(program: &Program, symbols: &Symbols) -> String {
+    let variables = (0..symbols.len())
+        .map(|slot| format!("{}{}", symbols.name(slot), symbols.data_type(slot).map(DataType::suffix).unwrap_or("")))
+        .collect();
+
+    let document = Document { version: VERSION, variables, program: program.clone() };
+
+    // Programs are plain data, they can always be written.
+    serde_json::to_string_pretty(&document).expect("Program not serializable")
+}
+
+/// Reads a document written by [`export`], returning the program and its
+/// variables. [`pretty_print`](crate::pretty::pretty_print) turns them back
+/// into source.
+pub fn import(json: &str) -> Result<(Program, Symbols), serde_json::Error> {
+    let document: Document = serde_json::from_str(json)?;
+
+    if document.version != VERSION {
+        return Err(serde_json::Error::custom(format!("Unsupported version {}", document.version)));
+    }
+
+    let mut symbols = Symbols::new();
+    for name in &document.variables {
+        let (name, data_type) = DataType::of_name(name);
+        if symbols.find(name, data_type).is_some() {
+            return Err(serde_json::Error::custom(format!("Variable {} listed twice", name)));
+        }
+        symbols.resolve(name, data_type);
+    }
+
+    Ok((document.program, symbols))
+}
@@ -1,18 +1,46 @@
 extern crate peginator;
 
+#[allow(unused_assignments, clippy::all)]
 pub mod parser;
 pub mod interpreter;
-mod value;
-mod error;
+pub mod error;
+pub mod host;
+pub mod limits;
+pub mod trace;
+pub mod profiler;
+pub mod ast;
+pub mod optimizer;
+pub mod compiler;
+pub mod vm;
+pub mod debugger;
+pub mod listing;
+pub mod memory;
+pub mod resident;
+pub mod pretty;
+pub mod checker;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod tokens;
+pub mod value;
+mod native;
 mod expression;
-mod scope;
+pub mod scope;
 mod bool_expression;
 
-use std::io::stdout;
-use peginator::PegParser;
+pub use error::{BasicError, InterpreterError};
+pub use host::{Host, MemoryHost, StdHost};
+pub use interpreter::{Engine, Interpreter, Outcome, StepResult};
+pub use debugger::{Debugger, Frame, Stop};
+pub use listing::Listing;
+pub use limits::CancelToken;
+pub use trace::Trace;
+pub use profiler::{LineProfile, Profile};
+pub use scope::{DataType, IntegerModel};
+pub use value::Value;
 
-pub fn execute(code: &str) {
-    let parse_result = parser::Program::parse(code).expect("Parse Error!");
-
-    parse_result.execute(&mut stdout()).expect("Execution Error");
-}
\ No newline at end of file
+/// Executes `code` reading from stdin and writing to stdout.
+pub fn execute(code: &str) -> Result<Outcome, BasicError> {
+    Interpreter::new(StdHost::stdio()).run(code)
+}
@@ -0,0 +1,106 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use crate::error::InterpreterError;
+
+/// Stops a running program from another thread, like pressing Escape.
+///
+/// Clones share the same flag. A cancelled token stays cancelled until it is
+/// [reset](CancelToken::reset).
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far a program may run. Both engines take a step before every
+/// statement and on every loop iteration, so a loop without a body still
+/// uses up the budget.
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    steps: Option<u64>,
+    cancel: Option<CancelToken>,
+}
+
+impl Limits {
+    /// No step limit and nothing to cancel.
+    pub fn none() -> Limits {
+        Limits::default()
+    }
+
+    pub fn with_steps(mut self, steps: Option<u64>) -> Limits {
+        self.steps = steps;
+        self
+    }
+
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Limits {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Steps left, `None` if unlimited.
+    pub fn steps(&self) -> Option<u64> {
+        self.steps
+    }
+
+    /// Takes a step, failing once the budget is used up or the token has been
+    /// cancelled.
+    #[inline]
+    pub fn step(&mut self) -> Result<(), InterpreterError> {
+        if let Some(token) = &self.cancel {
+            if token.is_cancelled() {
+                return Err(InterpreterError::Escape);
+            }
+        }
+
+        if let Some(steps) = &mut self.steps {
+            if *steps == 0 {
+                return Err(InterpreterError::StepLimitExceeded);
+            }
+            *steps -= 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_run_out() {
+        let mut limits = Limits::none().with_steps(Some(2));
+
+        assert!(limits.step().is_ok());
+        assert!(limits.step().is_ok());
+        assert!(matches!(limits.step(), Err(InterpreterError::StepLimitExceeded)));
+    }
+
+    #[test]
+    fn cancelling_a_clone_cancels_the_token() {
+        let token = CancelToken::new();
+        let mut limits = Limits::none().with_cancel_token(token.clone());
+
+        assert!(limits.step().is_ok());
+        token.clone().cancel();
+        assert!(matches!(limits.step(), Err(InterpreterError::Escape)));
+
+        token.reset();
+        assert!(limits.step().is_ok());
+    }
+}
@@ -0,0 +1,256 @@
+//! Programs with line numbers, as edited at the `>` prompt: typing a line
+//! with a number in front replaces that line of the program.
+//!
+//! The parser works on plain source, the line numbers are only used for
+//! editing. [`Listing::source`] drops them, and maps positions in the source
+//! back to them with [`Listing::line_number`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use crate::error::InterpreterError;
+
+/// Highest line number, as in BBC BASIC for SDL.
+pub const MAX_LINE_NUMBER: u32 = 65279;
+
+/// Keywords followed by line numbers, which `RENUMBER` rewrites. `GOTO` and
+/// `GOSUB` take a list after `ON`.
+pub(crate) const REFERENCES: [(&str, bool); 6] = [("GOTO", true), ("GOSUB", true), ("RESTORE", false), ("TRACE", false), ("THEN", false), ("ELSE", false)];
+
+/// A line number `RENUMBER` couldn't rewrite as there is no such line, see
+/// [`Listing::renumber`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Failed {
+    /// The line the reference is on, with its new number.
+    pub line: u32,
+    /// The number referred to.
+    pub target: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Listing {
+    lines: BTreeMap<u32, String>,
+}
+
+/// Splits `input` like `10 PRINT x` into its line number and text, `None` if
+/// it doesn't start with a number. One space after the number is dropped,
+/// further indentation is kept.
+///
+/// ```
+/// use bbbasic::listing::split_line_number;
+///
+/// assert_eq!(split_line_number("10 PRINT x"), Some((10, "PRINT x")));
+/// assert_eq!(split_line_number("20"), Some((20, "")));
+/// assert_eq!(split_line_number("PRINT 10"), None);
+/// ```
+pub fn split_line_number(input: &str) -> Option<(u32, &str)> {
+    let digits = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let number = input[..digits].parse().ok()?;
+    let text = &input[digits..];
+
+    Some((number, text.strip_prefix(' ').unwrap_or(text)))
+}
+
+impl Listing {
+    pub fn new() -> Listing {
+        Listing::default()
+    }
+
+    /// Numbers the lines of `code` 10, 20, 30 and so on. If every line
+    /// already starts with a number, like in a classic listing, those are
+    /// kept.
+    pub fn from_source(code: &str) -> Listing {
+        match Listing::from_numbered(code) {
+            Some(listing) => listing,
+            None => Listing {
+                lines: code.lines().zip(1..).map(|(l, i)| (i * 10, l.to_string())).collect()
+            }
+        }
+    }
+
+    /// Reads a classic listing, `None` unless every line that isn't blank
+    /// starts with a line number.
+    pub fn from_numbered(code: &str) -> Option<Listing> {
+        let lines: BTreeMap<u32, String> = code.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| split_line_number(l.trim_start()).map(|(n, t)| (n, t.to_string())))
+            .collect::<Option<_>>()?;
+
+        if lines.is_empty() { None } else { Some(Listing { lines }) }
+    }
+
+    /// Numbers the lines `start`, `start + step` and so on, like
+    /// `RENUMBER`, and rewrites the line numbers after `GOTO`, `GOSUB`,
+    /// `RESTORE`, `TRACE`, `THEN` and `ELSE` to match. References to lines that don't
+    /// exist are left alone and returned.
+    ///
+    /// ```
+    /// use bbbasic::Listing;
+    ///
+    /// let mut listing = Listing::from_source("5 PRINT \"HI\"\n7 GOTO 5\n");
+    /// listing.renumber(100, 10).unwrap();
+    ///
+    /// assert_eq!(listing.to_string(), "  100 PRINT \"HI\"\n  110 GOTO 100\n");
+    /// ```
+    pub fn renumber(&mut self, start: u32, step: u32) -> Result<Vec<Failed>, InterpreterError> {
+        let last = (self.lines.len().saturating_sub(1) as u64) * step as u64 + start as u64;
+        if step == 0 || start == 0 || last > MAX_LINE_NUMBER as u64 {
+            return Err(InterpreterError::Silly);
+        }
+
+        let numbers: HashMap<u32, u32> = self.lines.keys().zip((start..).step_by(step as usize))
+            .map(|(old, new)| (*old, new))
+            .collect();
+
+        let mut failed = Vec::new();
+        let mut lines = BTreeMap::new();
+        for (old, text) in &self.lines {
+            let number = numbers[old];
+            let text = rewrite_references(text, |target| match numbers.get(&target) {
+                Some(n) => Some(*n),
+                None => {
+                    failed.push(Failed { line: number, target });
+                    None
+                }
+            });
+
+            lines.insert(number, text);
+        }
+        self.lines = lines;
+
+        Ok(failed)
+    }
+
+    /// Replaces line `number` with `text`, or adds it. Empty text deletes
+    /// the line, like typing just its number.
+    pub fn set_line(&mut self, number: u32, text: &str) {
+        if text.trim().is_empty() {
+            self.lines.remove(&number);
+        } else {
+            self.lines.insert(number, text.to_string());
+        }
+    }
+
+    pub fn line(&self, number: u32) -> Option<&str> {
+        self.lines.get(&number).map(String::as_str)
+    }
+
+    /// Every line with its number, in order.
+    pub fn lines(&self) -> impl Iterator<Item = (u32, &str)> + '_ {
+        self.lines.iter().map(|(n, t)| (*n, t.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// The program without line numbers, for the parser.
+    pub fn source(&self) -> String {
+        let mut source = String::new();
+
+        for text in self.lines.values() {
+            source.push_str(text);
+            source.push('\n');
+        }
+
+        source
+    }
+
+    /// Source with the line numbers in front, as in a classic listing.
+    pub fn numbered_source(&self) -> String {
+        let mut source = String::new();
+
+        for (number, text) in &self.lines {
+            source.push_str(&format!("{} {}\n", number, text));
+        }
+
+        source
+    }
+
+    /// Number of the line that is line `line` of [`Listing::source`],
+    /// counted from 1.
+    pub fn line_number(&self, line: usize) -> Option<u32> {
+        self.lines.keys().nth(line.checked_sub(1)?).copied()
+    }
+}
+
+/// Lists the program with right-aligned line numbers, like `LIST`.
+impl Display for Listing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (number, text) in &self.lines {
+            writeln!(f, "{:>5} {}", number, text)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces the line numbers `text` refers to with what `map` returns for
+/// them, keeping those it returns `None` for. String literals are skipped.
+fn rewrite_references(text: &str, mut map: impl FnMut(u32) -> Option<u32>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            let end = rest[1..].find('"').map(|i| i + 2).unwrap_or(rest.len());
+            result.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let keyword = REFERENCES.iter().find(|(k, _)| rest.starts_with(k) && !ends_in_name(&result));
+        let (keyword, list) = match keyword {
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+            Some(k) => *k
+        };
+
+        result.push_str(keyword);
+        rest = &rest[keyword.len()..];
+
+        loop {
+            let spaces = rest.len() - rest.trim_start_matches(' ').len();
+            let digits = rest[spaces..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - spaces);
+            let number = match rest[spaces..spaces + digits].parse() {
+                Ok(n) => n,
+                Err(_) => break
+            };
+
+            result.push_str(&rest[..spaces]);
+            match map(number) {
+                Some(n) => result.push_str(&n.to_string()),
+                None => result.push_str(&rest[spaces..spaces + digits])
+            }
+            rest = &rest[spaces + digits..];
+
+            // Further numbers of an `ON x GOTO` list.
+            let after = rest.trim_start_matches(' ');
+            match after.strip_prefix(',') {
+                Some(next) if list => {
+                    result.push_str(&rest[..rest.len() - after.len() + 1]);
+                    rest = next;
+                }
+                _ => break
+            }
+        }
+    }
+
+    result
+}
+
+/// Whether `text` ends in a letter, digit or underscore, so a keyword after
+/// it would be part of a name.
+fn ends_in_name(text: &str) -> bool {
+    text.chars().last().map(|c| c.is_ascii_alphanumeric() || c == '_').unwrap_or(false)
+}
@@ -0,0 +1,162 @@
+//! Memory of a program: byte blocks reserved with `DIM x% n` and accessed
+//! with the indirection operators `?`, `!`, `|` and `$`.
+//!
+//! Programs never see the memory of the process. Blocks are taken one after
+//! the other from a virtual address space starting at [`BASE`], up to a
+//! limit set with [`Interpreter::with_memory_limit`](crate::Interpreter::with_memory_limit).
+//! Any access outside of the reserved blocks fails with "Bad address".
+
+use std::ops::Range;
+use crate::ast::Indirection;
+use crate::error::InterpreterError;
+use crate::scope::{Float, Integer};
+use crate::value::Value;
+
+/// Address of the first block reserved.
+pub const BASE: Integer = 0x10000;
+
+/// Bytes programs can reserve unless set otherwise.
+pub const DEFAULT_LIMIT: usize = 1 << 20;
+
+/// Ends strings stored with `$`.
+const CR: u8 = 13;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Memory {
+    bytes: Vec<u8>,
+    limit: usize,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Memory::new(DEFAULT_LIMIT)
+    }
+}
+
+impl Memory {
+    /// Empty memory of which at most `limit` bytes can be reserved.
+    pub fn new(limit: usize) -> Memory {
+        Memory { bytes: Vec::new(), limit }
+    }
+
+    /// Reserves the `n + 1` bytes of `DIM x% n`, set to zero, returning the
+    /// address of the first. `DIM x% -1` reserves nothing and returns the
+    /// address the next block starts at.
+    pub fn dim(&mut self, n: Integer) -> Result<Integer, InterpreterError> {
+        let size = n.checked_add(1)
+            .and_then(|s| usize::try_from(s).ok())
+            .ok_or(InterpreterError::BadDim)?;
+
+        let address = self.bytes.len();
+        if size > self.limit.saturating_sub(address) {
+            return Err(InterpreterError::NoRoom);
+        }
+        self.bytes.resize(address + size, 0);
+
+        Ok(BASE + address as Integer)
+    }
+
+    /// Releases all blocks, like `CLEAR`.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    fn range(&self, address: Integer, len: usize) -> Result<Range<usize>, InterpreterError> {
+        let start = address.checked_sub(BASE)
+            .and_then(|a| usize::try_from(a).ok())
+            .ok_or(InterpreterError::BadAddress)?;
+
+        match start.checked_add(len) {
+            Some(end) if end <= self.bytes.len() => Ok(start..end),
+            _ => Err(InterpreterError::BadAddress)
+        }
+    }
+
+    fn read<const N: usize>(&self, address: Integer) -> Result<[u8; N], InterpreterError> {
+        let range = self.range(address, N)?;
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.bytes[range]);
+
+        Ok(bytes)
+    }
+
+    fn write(&mut self, address: Integer, bytes: &[u8]) -> Result<(), InterpreterError> {
+        let range = self.range(address, bytes.len())?;
+        self.bytes[range].copy_from_slice(bytes);
+
+        Ok(())
+    }
+
+    /// Reads at `address`: `?` an unsigned byte, `!` a signed 32-bit word,
+    /// `|` a 64-bit float, all little-endian, and `$` a string up to a
+    /// carriage return.
+    pub fn peek(&self, indirection: Indirection, address: Integer) -> Result<Value, InterpreterError> {
+        match indirection {
+            Indirection::Byte => Ok(Value::Integer(self.read::<1>(address)?[0] as Integer)),
+            Indirection::Word => Ok(Value::Integer(i32::from_le_bytes(self.read(address)?) as Integer)),
+            Indirection::Float => Ok(Value::Float(Float::from_le_bytes(self.read(address)?))),
+            Indirection::String => {
+                let start = self.range(address, 0)?.start;
+                let length = self.bytes[start..].iter().position(|b| *b == CR).ok_or(InterpreterError::BadAddress)?;
+
+                Ok(Value::String(String::from_utf8_lossy(&self.bytes[start..start + length]).into_owned()))
+            }
+        }
+    }
+
+    /// Writes `value` at `address`, see [`Memory::peek`]. Bytes and words
+    /// keep the lowest bits of integers, strings are followed by a carriage
+    /// return.
+    pub fn poke(&mut self, indirection: Indirection, address: Integer, value: &Value) -> Result<(), InterpreterError> {
+        match indirection {
+            Indirection::Byte => self.write(address, &[value.to_integer()? as u8]),
+            Indirection::Word => self.write(address, &(value.to_integer()? as i32).to_le_bytes()),
+            Indirection::Float => self.write(address, &value.to_float()?.to_le_bytes()),
+            Indirection::String => match value {
+                Value::String(s) => {
+                    let mut bytes = s.as_bytes().to_vec();
+                    bytes.push(CR);
+                    self.write(address, &bytes)
+                }
+                _ => Err(InterpreterError::TypeMismatch)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_follow_each_other() {
+        let mut memory = Memory::new(16);
+
+        assert_eq!(memory.dim(3).unwrap(), BASE);
+        assert_eq!(memory.dim(-1).unwrap(), BASE + 4);
+        assert_eq!(memory.dim(11).unwrap(), BASE + 4);
+        assert!(matches!(memory.dim(0), Err(InterpreterError::NoRoom)));
+        assert!(matches!(memory.dim(-2), Err(InterpreterError::BadDim)));
+    }
+
+    #[test]
+    fn values_are_stored_little_endian() {
+        let mut memory = Memory::new(64);
+        let a = memory.dim(15).unwrap();
+
+        memory.poke(Indirection::Word, a, &Value::Integer(-2)).unwrap();
+        assert_eq!(memory.peek(Indirection::Byte, a).unwrap(), Value::Integer(0xFE));
+        assert_eq!(memory.peek(Indirection::Word, a).unwrap(), Value::Integer(-2));
+
+        memory.poke(Indirection::Byte, a + 3, &Value::Integer(0x17F)).unwrap();
+        assert_eq!(memory.peek(Indirection::Word, a).unwrap(), Value::Integer(0x7FFF_FFFE));
+
+        memory.poke(Indirection::Float, a + 8, &Value::Float(1.5)).unwrap();
+        assert_eq!(memory.peek(Indirection::Float, a + 8).unwrap(), Value::Float(1.5));
+
+        assert!(matches!(memory.peek(Indirection::Word, a + 13), Err(InterpreterError::BadAddress)));
+        assert!(matches!(memory.peek(Indirection::Byte, a - 1), Err(InterpreterError::BadAddress)));
+        assert!(matches!(memory.peek(Indirection::String, a), Err(InterpreterError::BadAddress)));
+    }
+}
@@ -0,0 +1,52 @@
+//! Functions and procedures of the application embedding the interpreter,
+//! which programs call like their own with `FNname` and `PROCname`.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use crate::error::InterpreterError;
+use crate::scope::{DataType, IntegerModel};
+use crate::value::Value;
+
+type Function = dyn Fn(&[Value]) -> Result<Value, InterpreterError>;
+
+/// A function or procedure implemented in Rust, with the types of its
+/// parameters.
+#[derive(Clone)]
+pub(crate) struct Native {
+    parameters: Vec<DataType>,
+    function: Rc<Function>,
+}
+
+impl Native {
+    pub(crate) fn new(parameters: &[DataType], function: impl Fn(&[Value]) -> Result<Value, InterpreterError> + 'static) -> Native {
+        Native { parameters: parameters.to_vec(), function: Rc::new(function) }
+    }
+
+    /// Calls the function with `arguments`, which are converted to the types
+    /// of its parameters like those of a `DEF PROC`.
+    pub(crate) fn call(&self, arguments: Vec<Value>, model: IntegerModel) -> Result<Value, InterpreterError> {
+        if arguments.len() != self.parameters.len() {
+            return Err(InterpreterError::Arguments);
+        }
+
+        let arguments = arguments.iter().zip(&self.parameters)
+            .map(|(a, t)| a.convert(*t, model))
+            .collect::<Result<Vec<Value>, InterpreterError>>()?;
+
+        (self.function)(&arguments)
+    }
+}
+
+impl Debug for Native {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Native").field("parameters", &self.parameters).finish_non_exhaustive()
+    }
+}
+
+/// Registered natives by name, without `FN` or `PROC`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Natives {
+    pub(crate) functions: HashMap<String, Native>,
+    pub(crate) procedures: HashMap<String, Native>,
+}
@@ -0,0 +1,386 @@
+//! Optimisation pass over the resolved program tree: number literals are
+//! parsed once, constant sub-expressions are folded and trivial identities
+//! like `x * 1` are removed.
+//!
+//! Folding computes constant sub-expressions exactly like the interpreter
+//! would, so the result keeps its type. Nothing that could fail at runtime is
+//! folded, the error is left to happen when the program runs.
+
+use std::collections::HashMap;
+use crate::ast::{Block, Condition, Expression, Operand, PrintItem, Program, Statement, TraceMode};
+use crate::expression::Compute;
+use crate::host::MemoryHost;
+use crate::scope::{Integer, IntegerModel, Scope, Slot};
+use crate::value::Value;
+
+/// Optimizes `program` for running with the given integer model, which
+/// decides how constant integer arithmetic overflows.
+pub fn optimize(program: &mut Program, model: IntegerModel) {
+    let mut writes = HashMap::new();
+    count_writes(&program.body, &mut writes);
+
+    for procedure in &program.procedures {
+        // Counted twice, like FOR variables every call writes them.
+        for parameter in &procedure.parameters {
+            *writes.entry(parameter.slot).or_insert(0) += 2;
+        }
+        count_writes(&procedure.body, &mut writes);
+    }
+
+    let mut optimizer = Optimizer {
+        writes,
+        constants: HashMap::new(),
+        scope: Scope::with_integer_model(model),
+        host: MemoryHost::new(),
+    };
+
+    for located in program.body.iter_mut() {
+        optimizer.statement(&mut located.statement);
+        optimizer.remember(&located.statement);
+    }
+
+    // A procedure can be called before any of the constants is assigned.
+    optimizer.constants.clear();
+    for procedure in program.procedures.iter_mut() {
+        optimizer.block(&mut procedure.body);
+    }
+}
+
+struct Optimizer {
+    /// How often each variable is assigned anywhere in the program.
+    writes: HashMap<Slot, usize>,
+    /// Variables that are known to hold a constant from here on.
+    constants: HashMap<Slot, Value>,
+    /// Used to compute folded expressions, constants never touch either.
+    scope: Scope,
+    host: MemoryHost,
+}
+
+fn count_writes(block: &Block, writes: &mut HashMap<Slot, usize>) {
+    for located in block {
+        match &located.statement {
+            Statement::Assignment(v, _)
+            | Statement::StringAssignment(v, _)
+            | Statement::Input { variable: v, .. }
+            | Statement::Dim { variable: v, .. } => *writes.entry(v.slot).or_insert(0) += 1,
+
+            Statement::For(f) => {
+                // Counted twice, the loop writes its variable on every step.
+                *writes.entry(f.variable.slot).or_insert(0) += 2;
+                count_writes(&f.body, writes);
+            }
+
+            Statement::While { body, .. } => count_writes(body, writes),
+
+            Statement::If { then_block, else_block, .. } => {
+                count_writes(then_block, writes);
+                if let Some(e) = else_block {
+                    count_writes(e, writes);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Whether `e` is the integer `value`. Adding an integer zero or
+/// multiplying by an integer one changes neither the value nor the type of
+/// the other side, a byte only becomes an integer.
+fn is_integer(e: &Expression, value: Integer) -> bool {
+    match e {
+        Expression::Constant(Value::Integer(i)) => *i == value,
+        Expression::Constant(Value::Byte(b)) => *b as Integer == value,
+        _ => false
+    }
+}
+
+impl Optimizer {
+    /// A variable assigned a constant once, at the top level of the program,
+    /// can be replaced by the constant in all following statements.
+    fn remember(&mut self, statement: &Statement) {
+        if let Statement::Assignment(variable, Expression::Constant(c)) = statement {
+            if self.writes.get(&variable.slot) != Some(&1) {
+                return;
+            }
+
+            if let Ok(v) = c.convert(variable.data_type, self.scope.integer_model()) {
+                self.constants.insert(variable.slot, v);
+            }
+        }
+    }
+
+    fn block(&mut self, block: &mut Block) {
+        for located in block.iter_mut() {
+            self.statement(&mut located.statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Assignment(_, value) => self.expression(value),
+
+            Statement::Print(items) => {
+                for PrintItem { value, .. } in items.iter_mut() {
+                    self.operand(value);
+                }
+            }
+
+            Statement::For(f) => {
+                self.expression(&mut f.start);
+                self.expression(&mut f.target);
+                if let Some(s) = &mut f.step {
+                    self.expression(s);
+                }
+                self.block(&mut f.body);
+            }
+
+            Statement::While { condition, body } => {
+                self.condition(condition);
+                self.block(body);
+            }
+
+            Statement::If { condition, then_block, else_block } => {
+                self.condition(condition);
+                self.block(then_block);
+                if let Some(e) = else_block {
+                    self.block(e);
+                }
+            }
+
+            Statement::SetTime(e)
+            | Statement::Close(e)
+            | Statement::Trace(TraceMode::Below(e)) => self.expression(e),
+
+            Statement::Vdu(values) => {
+                for v in values.iter_mut() {
+                    self.expression(v);
+                }
+            }
+
+            Statement::Plot { mode, x, y } => {
+                for e in [mode, x, y] {
+                    self.expression(e);
+                }
+            }
+
+            Statement::Move { x, y } | Statement::Draw { x, y } => {
+                for e in [x, y] {
+                    self.expression(e);
+                }
+            }
+
+            Statement::Sound { channel, amplitude, pitch, duration } => {
+                for e in [channel, amplitude, pitch, duration] {
+                    self.expression(e);
+                }
+            }
+
+            Statement::Bput { channel, value } => {
+                for e in [channel, value] {
+                    self.expression(e);
+                }
+            }
+
+            Statement::Dim { size, .. } => self.expression(size),
+
+            Statement::Poke { address, value, .. } => {
+                self.expression(address);
+                self.operand(value);
+            }
+
+            Statement::Call { arguments, .. } => {
+                for a in arguments.iter_mut() {
+                    self.operand(a);
+                }
+            }
+
+            Statement::StringAssignment(..)
+            | Statement::Return
+            | Statement::Trace(_)
+            | Statement::Input { .. }
+            | Statement::ExitFor
+            | Statement::ExitWhile
+            | Statement::End
+            | Statement::Cls => {}
+        }
+    }
+
+    fn condition(&mut self, condition: &mut Condition) {
+        match condition {
+            Condition::Or(c) | Condition::And(c) => {
+                for c in c.iter_mut() {
+                    self.condition(c);
+                }
+            }
+            Condition::Compare(left, _, right) => {
+                self.operand(left);
+                self.operand(right);
+            }
+        }
+    }
+
+    fn operand(&mut self, operand: &mut Operand) {
+        if let Operand::Number(e) = operand {
+            self.expression(e);
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::Number(n) => {
+                if let Ok(v) = n.value() {
+                    *expression = Expression::Constant(v);
+                }
+            }
+
+            Expression::Variable(v) => {
+                if let Some(c) = self.constants.get(&v.slot) {
+                    *expression = Expression::Constant(c.clone());
+                }
+            }
+
+            Expression::Add(l, r)
+            | Expression::Sub(l, r)
+            | Expression::Mul(l, r)
+            | Expression::Div(l, r) => {
+                self.expression(l);
+                self.expression(r);
+
+                if let (Expression::Constant(_), Expression::Constant(_)) = (l.as_ref(), r.as_ref()) {
+                    self.fold(expression);
+                } else {
+                    simplify(expression);
+                }
+            }
+
+            Expression::Rnd(Some(a))
+            | Expression::Inkey(a)
+            | Expression::Bget(a)
+            | Expression::Eof(a)
+            | Expression::Indirect(_, a) => self.expression(a),
+
+            Expression::Function { arguments, .. } => {
+                for a in arguments.iter_mut() {
+                    self.operand(a);
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn fold(&mut self, expression: &mut Expression) {
+        if let Ok(v) = expression.compute(&mut self.scope, &mut self.host) {
+            *expression = Expression::Constant(v);
+        }
+    }
+}
+
+/// Removes `x + 0`, `0 + x`, `x - 0`, `x * 1` and `1 * x`. A float constant
+/// would turn `x` into a float, and `/` always does, so those are kept.
+fn simplify(expression: &mut Expression) {
+    let keep_left = match expression {
+        Expression::Add(l, r) => {
+            if is_integer(r, 0) {
+                true
+            } else if is_integer(l, 0) {
+                false
+            } else {
+                return;
+            }
+        }
+        Expression::Sub(_, r) if is_integer(r, 0) => true,
+        Expression::Mul(l, r) => {
+            if is_integer(r, 1) {
+                true
+            } else if is_integer(l, 1) {
+                false
+            } else {
+                return;
+            }
+        }
+        _ => return
+    };
+
+    let taken = std::mem::replace(expression, Expression::Get);
+    *expression = match taken {
+        Expression::Add(l, r)
+        | Expression::Sub(l, r)
+        | Expression::Mul(l, r) => if keep_left { *l } else { *r },
+        e => e
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use peginator::PegParser;
+    use super::*;
+    use crate::parser;
+
+    fn optimized(code: &str) -> Program {
+        let mut scope = Scope::new();
+        let parsed = parser::Program::parse(code).expect("Parse failed");
+        let mut program = Program::resolve(&parsed, scope.symbols_mut());
+        optimize(&mut program, IntegerModel::default());
+
+        program
+    }
+
+    fn assigned(program: &Program, index: usize) -> &Expression {
+        match &program.body[index].statement {
+            Statement::Assignment(_, e) => e,
+            s => panic!("Not an assignment: {:?}", s)
+        }
+    }
+
+    #[test]
+    fn constant_expressions_are_folded() {
+        let p = optimized("x = (3.0 * 2) + 1\ny% = 7 / 2\nz = 7 / 2 + 0.5");
+
+        assert_eq!(assigned(&p, 0), &Expression::Constant(Value::Float(7.0)));
+        assert_eq!(assigned(&p, 1), &Expression::Constant(Value::Float(3.5)));
+        assert_eq!(assigned(&p, 2), &Expression::Constant(Value::Float(4.0)));
+    }
+
+    #[test]
+    fn constant_variables_are_propagated() {
+        let p = optimized("MW = 3.0\nW = 110\nx = MW / W\nW = 2");
+
+        // W is assigned twice, so only MW is replaced.
+        assert!(matches!(assigned(&p, 2), Expression::Div(l, r)
+            if **l == Expression::Constant(Value::Float(3.0)) && matches!(**r, Expression::Variable(_))));
+
+        let p = optimized("MW = 3.0\nW = 110\nFOR i = 1 TO 2\nx = MW / W\nNEXT i");
+        match &p.body[2].statement {
+            Statement::For(f) => match &f.body[0].statement {
+                Statement::Assignment(_, e) => assert_eq!(e, &Expression::Constant(Value::Float(3.0 / 110.0))),
+                s => panic!("Not an assignment: {:?}", s)
+            },
+            s => panic!("Not a loop: {:?}", s)
+        }
+    }
+
+    #[test]
+    fn identities_are_removed() {
+        let p = optimized("y = 2\ny = x * 1 + 0\nz = x% * 1.0");
+
+        assert!(matches!(assigned(&p, 1), Expression::Variable(_)));
+        assert!(matches!(assigned(&p, 2), Expression::Mul(..)));
+    }
+
+    #[test]
+    fn parameters_are_not_constant() {
+        let p = optimized("x = 1\nPROCp(2)\ny = x\nEND\nDEF PROCp(x)\ny = x\n");
+
+        assert!(matches!(assigned(&p, 2), Expression::Variable(_)));
+        assert!(matches!(&p.procedures[0].body[0].statement, Statement::Assignment(_, Expression::Variable(_))));
+    }
+
+    #[test]
+    fn failing_expressions_are_not_folded() {
+        let p = optimized("x = 1 / 0");
+
+        assert!(matches!(assigned(&p, 0), Expression::Div(..)));
+    }
+}
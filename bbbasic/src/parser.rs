@@ -1,5 +1,5 @@
-// This file was generated by Peginator v0.6.0 built at 1672439020
-// CRC-32/ISO-HDLC of the grammar file: fe07f006
+// This file was generated by Peginator v0.6.0 built at 1792370100
+// CRC-32/ISO-HDLC of the grammar file: 4e7b5669
 // Any changes to it will be lost on regeneration
 
-# [derive (Debug , Clone ,)] pub struct Program { pub body : Block , } # [derive (Debug , Clone ,)] pub struct Block { pub statements : Vec < Statement > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Statement { Assignment (Assignment) , EndStatement (EndStatement) , ExitForStatement (ExitForStatement) , ExitWhileStatement (ExitWhileStatement) , ForStatement (ForStatement) , IfStatement (IfStatement) , PrintStatement (PrintStatement) , StringAssignment (StringAssignment) , WhileStatement (WhileStatement) , } # [derive (Debug , Clone ,)] pub struct NewLine ; # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Expression { Add (Add) , Sub (Sub) , Term (Term) , } # [derive (Debug , Clone ,)] pub struct Add { pub left : Box < Expression > , pub right : Term , } # [derive (Debug , Clone ,)] pub struct Sub { pub left : Box < Expression > , pub right : Term , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Term { Div (Div) , Factor (Factor) , Mul (Mul) , } # [derive (Debug , Clone ,)] pub struct Mul { pub left : Box < Term > , pub right : Factor , } # [derive (Debug , Clone ,)] pub struct Div { pub left : Box < Term > , pub right : Factor , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Factor { Group (Group) , NumberLiteral (NumberLiteral) , NumericVariable (NumericVariable) , } # [derive (Debug , Clone ,)] pub struct Group { pub body : Box < Expression > , } # [derive (Debug , Clone ,)] pub struct BoolExpression { pub disjunction : BoolDisjunction , } # [derive (Debug , Clone ,)] pub struct BoolDisjunction { pub conjunction : Vec < BoolConjunction > , } # [derive (Debug , Clone ,)] pub struct BoolConjunction { pub term : Vec < BoolTerm > , } # [derive (Debug , Clone ,)] pub struct BoolTerm { pub expression : Option < BoolExpression > , pub condition : Option < BoolCondition > , } # [derive (Debug , Clone ,)] pub struct BoolCondition { pub left : BoolOperand , pub op : BoolOperator , pub right : BoolOperand , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum BoolOperator { BoolOpEqual (BoolOpEqual) , BoolOpGreater (BoolOpGreater) , BoolOpGreaterEqual (BoolOpGreaterEqual) , BoolOpLower (BoolOpLower) , BoolOpLowerEqual (BoolOpLowerEqual) , BoolOpNotEqual (BoolOpNotEqual) , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum BoolOperand { Expression (Expression) , StringLiteral (StringLiteral) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct BoolOpNotEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpLowerEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpGreaterEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpLower ; # [derive (Debug , Clone ,)] pub struct BoolOpGreater ; pub type ThenBlock = Block ; pub type ElseBlock = Block ; pub type IfCondition = BoolDisjunction ; # [derive (Debug , Clone ,)] pub struct IfStatement { pub condition : IfCondition , pub then_block : ThenBlock , pub else_block : Option < ElseBlock > , } # [derive (Debug , Clone ,)] pub struct WhileStatement { pub condition : WhileCondition , pub body : Block , } pub type WhileCondition = BoolDisjunction ; # [derive (Debug , Clone ,)] pub struct ExitWhileStatement ; # [derive (Debug , Clone ,)] pub struct Assignment { pub variable : NumericVariable , pub value : Expression , } # [derive (Debug , Clone ,)] pub struct StringAssignment { pub variable : StringVariable , pub value : StringLiteral , } # [derive (Debug , Clone ,)] pub struct PrintStatement { pub list : Vec < PrintListItem > , } # [derive (Debug , Clone ,)] pub struct PrintSkipNl ; # [derive (Debug , Clone ,)] pub struct PrintListItem { pub value : PrintListItem_value , pub sep : Option < PrintListSep > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum PrintListItem_value { Expression (Expression) , StringLiteral (StringLiteral) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct PrintListSep ; # [derive (Debug , Clone ,)] pub struct ForAssignment { pub variable : NumericVariable , pub value : Expression , } # [derive (Debug , Clone ,)] pub struct ForStatement { pub assignment : ForAssignment , pub target : Expression , pub step : Option < ForStep > , pub body : Block , pub next : ForNext , } # [derive (Debug , Clone ,)] pub struct ForStep { pub value : Expression , } # [derive (Debug , Clone ,)] pub struct ForNext ; # [derive (Debug , Clone ,)] pub struct ExitForStatement ; # [derive (Debug , Clone ,)] pub struct EndStatement ; # [derive (Debug , Clone ,)] pub struct KWPrint ; # [derive (Debug , Clone ,)] pub struct KWFor ; # [derive (Debug , Clone ,)] pub struct KWTo ; # [derive (Debug , Clone ,)] pub struct KWStep ; # [derive (Debug , Clone ,)] pub struct KWNext ; # [derive (Debug , Clone ,)] pub struct KWEnd ; # [derive (Debug , Clone ,)] pub struct KWIf ; # [derive (Debug , Clone ,)] pub struct KWThen ; # [derive (Debug , Clone ,)] pub struct KWElse ; # [derive (Debug , Clone ,)] pub struct KWEndif ; # [derive (Debug , Clone ,)] pub struct KWExit ; # [derive (Debug , Clone ,)] pub struct KWWhile ; # [derive (Debug , Clone ,)] pub struct KWEndwhile ; # [derive (Debug , Clone ,)] pub struct NumericVariable { pub name : VariableName , pub type_dem : Option < NumericVariable_type_dem > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum NumericVariable_type_dem { ByteDenominator (ByteDenominator) , FloatDenominator (FloatDenominator) , IntegerDenominator (IntegerDenominator) , } # [derive (Debug , Clone ,)] pub struct FloatDenominator ; # [derive (Debug , Clone ,)] pub struct IntegerDenominator ; # [derive (Debug , Clone ,)] pub struct ByteDenominator ; # [derive (Debug , Clone ,)] pub struct StringDenominator ; # [derive (Debug , Clone ,)] pub struct StringVariable { pub name : VariableName , } pub type VariableName = String ; # [derive (Debug , Clone ,)] pub struct NumberSign ; # [derive (Debug , Clone ,)] pub struct NumberLiteral { pub value : NumberLiteral_value , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum NumberLiteral_value { FloatLiteral (FloatLiteral) , IntegerLiteral (IntegerLiteral) , } # [derive (Debug , Clone ,)] pub struct IntegerLiteral { pub body : IntegerBody , } pub type IntegerBody = String ; # [derive (Debug , Clone ,)] pub struct FloatLiteral { pub body : FloatBody , } pub type FloatBody = String ; # [derive (Debug , Clone ,)] pub struct StringLiteral { pub body : StringBody , } pub type StringBody = String ; # [derive (Debug , Clone ,)] pub struct ALPHA ; # [derive (Debug , Clone ,)] pub struct DIGIT ; # [derive (Debug , Clone ,)] pub struct Whitespace ; impl peginator_generated :: PegParserAdvanced < () > for Program { fn parse_advanced < TT : peginator_generated :: ParseTracer > (s : & str , settings : & peginator_generated :: ParseSettings , user_context : () ,) -> Result < Self , peginator_generated :: ParseError > { Ok (peginator_generated :: parse_Program (peginator_generated :: ParseState :: new (s , settings) , & mut peginator_generated :: ParseGlobal :: < TT , peginator_generated :: ParseCache , () > :: new (Default :: default () , user_context ,) ,) ? . result) } } impl peginator_generated :: PegParserAdvanced < () > for Expression { fn parse_advanced < TT : peginator_generated :: ParseTracer > (s : & str , settings : & peginator_generated :: ParseSettings , user_context : () ,) -> Result < Self , peginator_generated :: ParseError > { Ok (peginator_generated :: parse_Expression (peginator_generated :: ParseState :: new (s , settings) , & mut peginator_generated :: ParseGlobal :: < TT , peginator_generated :: ParseCache , () > :: new (Default :: default () , user_context ,) ,) ? . result) } } impl peginator_generated :: PegParserAdvanced < () > for BoolExpression { fn parse_advanced < TT : peginator_generated :: ParseTracer > (s : & str , settings : & peginator_generated :: ParseSettings , user_context : () ,) -> Result < Self , peginator_generated :: ParseError > { Ok (peginator_generated :: parse_BoolExpression (peginator_generated :: ParseState :: new (s , settings) , & mut peginator_generated :: ParseGlobal :: < TT , peginator_generated :: ParseCache , () > :: new (Default :: default () , user_context ,) ,) ? . result) } } # [allow (non_snake_case , unused_variables , unused_imports , unused_mut , dead_code ,)] mod peginator_generated { use super :: * ; pub use peginator :: { ParseError , ParseSettings , ParseState , PegParser , IndentedTracer , ParseTracer , PegPosition , ParseGlobal , PegParserAdvanced , } ; use peginator :: * ; # [derive (Default)] pub struct ParseCache < 'a > { pub c_Expression : CacheEntries < 'a , Expression > , pub c_Term : CacheEntries < 'a , Term > , pub c_Factor : CacheEntries < 'a , Factor > , _please_dont_complain : std :: marker :: PhantomData < & 'a () > , } mod Program_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_end_of_input (state ,) }) ? ; Ok (ParseOk { result : body , state }) } pub type Parsed = Block ; } # [inline] pub (super) fn parse_Program < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Program > { global . tracer . print_trace_start (& state , "Program") ; let result = (|| { let result = Program_impl :: parse (state , global) ? . map (| r | super :: Program { body : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Block_impl { use super :: * ; mod part_0 { use super :: * ; mod closure { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut statements , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Statement (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : statements , state }) } pub type Parsed = Vec < Statement > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut statements : Vec < Statement > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { statements . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : statements , state }) } pub type Parsed = Vec < Statement > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut statements , state } = part_0 :: parse (state , global) ? ; let ParseOk { result : extend_statements_with , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Statement (state , & mut * global) }) . map_inner (| result | vec ! [result]) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; statements . extend (extend_statements_with) ; Ok (ParseOk { result : statements , state }) } pub type Parsed = Vec < Statement > ; } # [inline] pub (super) fn parse_Block < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Block > { global . tracer . print_trace_start (& state , "Block") ; let result = (|| { let result = Block_impl :: parse (state , global) ? . map (| r | super :: Block { statements : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Statement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ExitWhileStatement (state , global) }) . map_inner (Parsed__override :: ExitWhileStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_WhileStatement (state , global) }) . map_inner (Parsed__override :: WhileStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ExitForStatement (state , global) }) . map_inner (Parsed__override :: ExitForStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IfStatement (state , global) }) . map_inner (Parsed__override :: IfStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForStatement (state , global) }) . map_inner (Parsed__override :: ForStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PrintStatement (state , global) }) . map_inner (Parsed__override :: PrintStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_EndStatement (state , global) }) . map_inner (Parsed__override :: EndStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Assignment (state , global) }) . map_inner (Parsed__override :: Assignment)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringAssignment (state , global) }) . map_inner (Parsed__override :: StringAssignment)) . end () } pub type Parsed = Parsed__override ; use super :: Statement as Parsed__override ; } # [inline] pub (super) fn parse_Statement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Statement > { global . tracer . print_trace_start (& state , "Statement") ; let result = (|| { let result = Statement_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod NewLine_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '\n') }) . discard_result ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "\r\n") }) . discard_result ()) . end () } pub type Parsed = () ; } # [inline] pub (super) fn parse_NewLine < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NewLine > { global . tracer . print_trace_start (& state , "NewLine") ; let result = (|| { let result = NewLine_impl :: parse (state , global) ? . map (| r | super :: NewLine { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Expression_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Add (state , global) }) . map_inner (Parsed__override :: Add)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Sub (state , global) }) . map_inner (Parsed__override :: Sub)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , global) }) . map_inner (Parsed__override :: Term)) . end () } pub type Parsed = Parsed__override ; use super :: Expression as Parsed__override ; } # [inline] pub (super) fn parse_Expression < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Expression > { global . tracer . print_trace_start (& state , "Expression") ; let result = (|| { let cache_key = state . cache_key () ; if let Some (cached) = global . cache . c_Expression . get (& cache_key) { global . tracer . print_informative ("Cache hit (left recursive)") ; cached . clone () } else { let mut best_result = Err (state . clone () . report_error (ParseErrorSpecifics :: LeftRecursionSentinel)) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; loop { global . tracer . print_informative ("Starting new left recursive loop") ; let state = state . clone () ; let new_result = { let result = Expression_impl :: parse (state , global) ? ; Ok (result) } ; match (new_result , & best_result) { (Ok (nro) , Ok (bro)) => { if nro . state . is_further_than (& bro . state) { best_result = Ok (nro) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; } else { break ; } } (Ok (nro) , Err (bre)) => { best_result = Ok (nro) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; } (Err (nre) , Ok (bro)) => { break ; } (Err (nre) , Err (bre)) => { best_result = Err (nre) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; break ; } } } best_result } }) () ; global . tracer . print_trace_result (& result) ; result } mod Add_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '+') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Expression > , pub right : Term , } } # [inline] pub (super) fn parse_Add < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Add > { global . tracer . print_trace_start (& state , "Add") ; let result = (|| { let result = Add_impl :: parse (state , global) ? . map (| r | super :: Add { left : r . left , right : r . right , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Sub_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '-') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Expression > , pub right : Term , } } # [inline] pub (super) fn parse_Sub < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Sub > { global . tracer . print_trace_start (& state , "Sub") ; let result = (|| { let result = Sub_impl :: parse (state , global) ? . map (| r | super :: Sub { left : r . left , right : r . right , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Term_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Mul (state , global) }) . map_inner (Parsed__override :: Mul)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Div (state , global) }) . map_inner (Parsed__override :: Div)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , global) }) . map_inner (Parsed__override :: Factor)) . end () } pub type Parsed = Parsed__override ; use super :: Term as Parsed__override ; } # [inline] pub (super) fn parse_Term < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Term > { global . tracer . print_trace_start (& state , "Term") ; let result = (|| { let cache_key = state . cache_key () ; if let Some (cached) = global . cache . c_Term . get (& cache_key) { global . tracer . print_informative ("Cache hit (left recursive)") ; cached . clone () } else { let mut best_result = Err (state . clone () . report_error (ParseErrorSpecifics :: LeftRecursionSentinel)) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; loop { global . tracer . print_informative ("Starting new left recursive loop") ; let state = state . clone () ; let new_result = { let result = Term_impl :: parse (state , global) ? ; Ok (result) } ; match (new_result , & best_result) { (Ok (nro) , Ok (bro)) => { if nro . state . is_further_than (& bro . state) { best_result = Ok (nro) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; } else { break ; } } (Ok (nro) , Err (bre)) => { best_result = Ok (nro) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; } (Err (nre) , Ok (bro)) => { break ; } (Err (nre) , Err (bre)) => { best_result = Err (nre) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; break ; } } } best_result } }) () ; global . tracer . print_trace_result (& result) ; result } mod Mul_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '*') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Term > , pub right : Factor , } } # [inline] pub (super) fn parse_Mul < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Mul > { global . tracer . print_trace_start (& state , "Mul") ; let result = (|| { let result = Mul_impl :: parse (state , global) ? . map (| r | super :: Mul { left : r . left , right : r . right , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Div_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '/') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Term > , pub right : Factor , } } # [inline] pub (super) fn parse_Div < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Div > { global . tracer . print_trace_start (& state , "Div") ; let result = (|| { let result = Div_impl :: parse (state , global) ? . map (| r | super :: Div { left : r . left , right : r . right , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Factor_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Group (state , global) }) . map_inner (Parsed__override :: Group)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumberLiteral (state , global) }) . map_inner (Parsed__override :: NumberLiteral)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , global) }) . map_inner (Parsed__override :: NumericVariable)) . end () } pub type Parsed = Parsed__override ; use super :: Factor as Parsed__override ; } # [inline] pub (super) fn parse_Factor < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Factor > { global . tracer . print_trace_start (& state , "Factor") ; let result = (|| { let cache_key = state . cache_key () ; if let Some (cached) = global . cache . c_Factor . get (& cache_key) { global . tracer . print_informative ("Cache hit") ; cached . clone () } else { let result = { let result = Factor_impl :: parse (state , global) ? ; Ok (result) } ; global . cache . c_Factor . insert (cache_key , result . clone ()) ; result } }) () ; global . tracer . print_trace_result (& result) ; result } mod Group_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : body , state }) } pub type Parsed = Box < Expression > ; } # [inline] pub (super) fn parse_Group < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Group > { global . tracer . print_trace_start (& state , "Group") ; let result = (|| { let result = Group_impl :: parse (state , global) ? . map (| r | super :: Group { body : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolExpression_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolDisjunction (state , & mut * global) }) } pub type Parsed = BoolDisjunction ; } # [inline] pub (super) fn parse_BoolExpression < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolExpression > { global . tracer . print_trace_start (& state , "BoolExpression") ; let result = (|| { let result = BoolExpression_impl :: parse (state , global) ? . map (| r | super :: BoolExpression { disjunction : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolDisjunction_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "OR") }) . discard_result () ? ; let ParseOk { result : mut conjunction , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolConjunction (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : conjunction , state }) } pub type Parsed = Vec < BoolConjunction > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut conjunction : Vec < BoolConjunction > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { conjunction . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : conjunction , state }) } pub type Parsed = Vec < BoolConjunction > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut conjunction , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolConjunction (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_conjunction_with , state } = part_1 :: parse (state , global) ? ; conjunction . extend (extend_conjunction_with) ; Ok (ParseOk { result : conjunction , state }) } pub type Parsed = Vec < BoolConjunction > ; } # [inline] pub (super) fn parse_BoolDisjunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolDisjunction > { global . tracer . print_trace_start (& state , "BoolDisjunction") ; let result = (|| { let result = BoolDisjunction_impl :: parse (state , global) ? . map (| r | super :: BoolDisjunction { conjunction : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolConjunction_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "AND") }) . discard_result () ? ; let ParseOk { result : mut term , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolTerm (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : term , state }) } pub type Parsed = Vec < BoolTerm > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut term : Vec < BoolTerm > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { term . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : term , state }) } pub type Parsed = Vec < BoolTerm > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut term , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolTerm (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_term_with , state } = part_1 :: parse (state , global) ? ; term . extend (extend_term_with) ; Ok (ParseOk { result : term , state }) } pub type Parsed = Vec < BoolTerm > ; } # [inline] pub (super) fn parse_BoolConjunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolConjunction > { global . tracer . print_trace_start (& state , "BoolConjunction") ; let result = (|| { let result = BoolConjunction_impl :: parse (state , global) ? . map (| r | super :: BoolConjunction { term : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolTerm_impl { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : expression , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolExpression (state , & mut * global) }) . map_inner (Some) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : expression , state }) } pub type Parsed = Option < BoolExpression > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global) . map_inner (| r | Parsed { expression : r , condition : None , })) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolCondition (state , & mut * global) }) . map_inner (Some) . map_inner (| r | Parsed { expression : None , condition : r , })) . end () } pub struct Parsed { pub expression : Option < BoolExpression > , pub condition : Option < BoolCondition > , } } # [inline] pub (super) fn parse_BoolTerm < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolTerm > { global . tracer . print_trace_start (& state , "BoolTerm") ; let result = (|| { let result = BoolTerm_impl :: parse (state , global) ? . map (| r | super :: BoolTerm { expression : r . expression , condition : r . condition , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolCondition_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) ? ; let ParseOk { result : op , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperator (state , & mut * global) }) ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , op , right , } , state }) } pub struct Parsed { pub left : BoolOperand , pub op : BoolOperator , pub right : BoolOperand , } } # [inline] pub (super) fn parse_BoolCondition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolCondition > { global . tracer . print_trace_start (& state , "BoolCondition") ; let result = (|| { let result = BoolCondition_impl :: parse (state , global) ? . map (| r | super :: BoolCondition { left : r . left , op : r . op , right : r . right , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOperator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpNotEqual (state , global) }) . map_inner (Parsed__override :: BoolOpNotEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpEqual (state , global) }) . map_inner (Parsed__override :: BoolOpEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpLowerEqual (state , global) }) . map_inner (Parsed__override :: BoolOpLowerEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpGreaterEqual (state , global) }) . map_inner (Parsed__override :: BoolOpGreaterEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpLower (state , global) }) . map_inner (Parsed__override :: BoolOpLower)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpGreater (state , global) }) . map_inner (Parsed__override :: BoolOpGreater)) . end () } pub type Parsed = Parsed__override ; use super :: BoolOperator as Parsed__override ; } # [inline] pub (super) fn parse_BoolOperator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOperator > { global . tracer . print_trace_start (& state , "BoolOperator") ; let result = (|| { let result = BoolOperator_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOperand_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , global) }) . map_inner (Parsed__override :: StringVariable)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , global) }) . map_inner (Parsed__override :: Expression)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , global) }) . map_inner (Parsed__override :: StringLiteral)) . end () } pub type Parsed = Parsed__override ; use super :: BoolOperand as Parsed__override ; } # [inline] pub (super) fn parse_BoolOperand < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOperand > { global . tracer . print_trace_start (& state , "BoolOperand") ; let result = (|| { let result = BoolOperand_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOpNotEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "<>") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpNotEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpNotEqual > { global . tracer . print_trace_start (& state , "BoolOpNotEqual") ; let result = (|| { let result = BoolOpNotEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpNotEqual { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOpEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpEqual > { global . tracer . print_trace_start (& state , "BoolOpEqual") ; let result = (|| { let result = BoolOpEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpEqual { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOpLowerEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "<=") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpLowerEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpLowerEqual > { global . tracer . print_trace_start (& state , "BoolOpLowerEqual") ; let result = (|| { let result = BoolOpLowerEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpLowerEqual { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOpGreaterEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , ">=") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpGreaterEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpGreaterEqual > { global . tracer . print_trace_start (& state , "BoolOpGreaterEqual") ; let result = (|| { let result = BoolOpGreaterEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpGreaterEqual { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOpLower_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '<') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpLower < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpLower > { global . tracer . print_trace_start (& state , "BoolOpLower") ; let result = (|| { let result = BoolOpLower_impl :: parse (state , global) ? . map (| r | super :: BoolOpLower { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod BoolOpGreater_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '>') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpGreater < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpGreater > { global . tracer . print_trace_start (& state , "BoolOpGreater") ; let result = (|| { let result = BoolOpGreater_impl :: parse (state , global) ? . map (| r | super :: BoolOpGreater { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ThenBlock_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , global) }) } pub type Parsed = Block ; use super :: ThenBlock as Parsed__override ; } # [inline] pub (super) fn parse_ThenBlock < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ThenBlock > { global . tracer . print_trace_start (& state , "ThenBlock") ; let result = (|| { let result = ThenBlock_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ElseBlock_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , global) }) } pub type Parsed = Block ; use super :: ElseBlock as Parsed__override ; } # [inline] pub (super) fn parse_ElseBlock < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ElseBlock > { global . tracer . print_trace_start (& state , "ElseBlock") ; let result = (|| { let result = ElseBlock_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod IfCondition_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolDisjunction (state , global) }) } pub type Parsed = BoolDisjunction ; use super :: IfCondition as Parsed__override ; } # [inline] pub (super) fn parse_IfCondition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IfCondition > { global . tracer . print_trace_start (& state , "IfCondition") ; let result = (|| { let result = IfCondition_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod IfStatement_impl { use super :: * ; mod part_3 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_5 { use super :: * ; mod optional { use super :: * ; mod part_0 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_2 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = part_0 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWElse (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = part_2 :: parse (state , global) ? ; let ParseOk { result : else_block , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ElseBlock (state , & mut * global) }) . map_inner (Some) ? ; Ok (ParseOk { result : else_block , state }) } pub type Parsed = Option < ElseBlock > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) } pub type Parsed = Option < ElseBlock > ; } mod part_6 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWIf (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : condition , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IfCondition (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWThen (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = part_3 :: parse (state , global) ? ; let ParseOk { result : then_block , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ThenBlock (state , & mut * global) }) ? ; let ParseOk { result : else_block , state } = part_5 :: parse (state , global) ? ; let ParseOk { state , .. } = part_6 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEndif (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : Parsed { condition , then_block , else_block , } , state }) } pub struct Parsed { pub condition : IfCondition , pub then_block : ThenBlock , pub else_block : Option < ElseBlock > , } } # [inline] pub (super) fn parse_IfStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IfStatement > { global . tracer . print_trace_start (& state , "IfStatement") ; let result = (|| { let result = IfStatement_impl :: parse (state , global) ? . map (| r | super :: IfStatement { condition : r . condition , then_block : r . then_block , else_block : r . else_block , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod WhileStatement_impl { use super :: * ; mod part_2 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_4 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWWhile (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : condition , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_WhileCondition (state , & mut * global) }) ? ; let ParseOk { state , .. } = part_2 :: parse (state , global) ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; let ParseOk { state , .. } = part_4 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEndwhile (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : Parsed { condition , body , } , state }) } pub struct Parsed { pub condition : WhileCondition , pub body : Block , } } # [inline] pub (super) fn parse_WhileStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , WhileStatement > { global . tracer . print_trace_start (& state , "WhileStatement") ; let result = (|| { let result = WhileStatement_impl :: parse (state , global) ? . map (| r | super :: WhileStatement { condition : r . condition , body : r . body , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod WhileCondition_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolDisjunction (state , global) }) } pub type Parsed = BoolDisjunction ; use super :: WhileCondition as Parsed__override ; } # [inline] pub (super) fn parse_WhileCondition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , WhileCondition > { global . tracer . print_trace_start (& state , "WhileCondition") ; let result = (|| { let result = WhileCondition_impl :: parse (state , global) ? ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ExitWhileStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWExit (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWWhile (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_ExitWhileStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ExitWhileStatement > { global . tracer . print_trace_start (& state , "ExitWhileStatement") ; let result = (|| { let result = ExitWhileStatement_impl :: parse (state , global) ? . map (| r | super :: ExitWhileStatement { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Assignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , value , } , state }) } pub struct Parsed { pub variable : NumericVariable , pub value : Expression , } } # [inline] pub (super) fn parse_Assignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Assignment > { global . tracer . print_trace_start (& state , "Assignment") ; let result = (|| { let result = Assignment_impl :: parse (state , global) ? . map (| r | super :: Assignment { variable : r . variable , value : r . value , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod StringAssignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , value , } , state }) } pub struct Parsed { pub variable : StringVariable , pub value : StringLiteral , } } # [inline] pub (super) fn parse_StringAssignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringAssignment > { global . tracer . print_trace_start (& state , "StringAssignment") ; let result = (|| { let result = StringAssignment_impl :: parse (state , global) ? . map (| r | super :: StringAssignment { variable : r . variable , value : r . value , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod PrintStatement_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut list : Vec < PrintListItem > = Vec :: new () ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PrintListItem (state , & mut * global) }) . map_inner (| result | vec ! [result]) { Ok (ParseOk { result : __result , state : new_state , .. }) => { list . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : list , state }) } pub type Parsed = Vec < PrintListItem > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWPrint (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : mut list , state } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : list , state }) } pub type Parsed = Vec < PrintListItem > ; } # [inline] pub (super) fn parse_PrintStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintStatement > { global . tracer . print_trace_start (& state , "PrintStatement") ; let result = (|| { let result = PrintStatement_impl :: parse (state , global) ? . map (| r | super :: PrintStatement { list : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod PrintSkipNl_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ';') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_PrintSkipNl < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintSkipNl > { global . tracer . print_trace_start (& state , "PrintSkipNl") ; let result = (|| { let result = PrintSkipNl_impl :: parse (state , global) ? . map (| r | super :: PrintSkipNl { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod PrintListItem_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : value , state } = ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) . map_inner (Parsed_value :: StringVariable)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Parsed_value :: Expression)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) . map_inner (Parsed_value :: StringLiteral)) . end () ? ; let ParseOk { result : sep , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PrintListSep (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : Parsed { value , sep , } , state }) } pub struct Parsed { pub value : Parsed_value , pub sep : Option < PrintListSep > , } use super :: PrintListItem_value as Parsed_value ; } # [inline] pub (super) fn parse_PrintListItem < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintListItem > { global . tracer . print_trace_start (& state , "PrintListItem") ; let result = (|| { let result = PrintListItem_impl :: parse (state , global) ? . map (| r | super :: PrintListItem { value : r . value , sep : r . sep , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod PrintListSep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ';') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_PrintListSep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintListSep > { global . tracer . print_trace_start (& state , "PrintListSep") ; let result = (|| { let result = PrintListSep_impl :: parse (state , global) ? . map (| r | super :: PrintListSep { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ForAssignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , value , } , state }) } pub struct Parsed { pub variable : NumericVariable , pub value : Expression , } } # [inline] pub (super) fn parse_ForAssignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForAssignment > { global . tracer . print_trace_start (& state , "ForAssignment") ; let result = (|| { let result = ForAssignment_impl :: parse (state , global) ? . map (| r | super :: ForAssignment { variable : r . variable , value : r . value , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ForStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWFor (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : assignment , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForAssignment (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWTo (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : target , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { result : step , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForStep (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; let ParseOk { result : next , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForNext (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { assignment , target , step , body , next , } , state }) } pub struct Parsed { pub assignment : ForAssignment , pub target : Expression , pub step : Option < ForStep > , pub body : Block , pub next : ForNext , } } # [inline] pub (super) fn parse_ForStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForStatement > { global . tracer . print_trace_start (& state , "ForStatement") ; let result = (|| { let result = ForStatement_impl :: parse (state , global) ? . map (| r | super :: ForStatement { assignment : r . assignment , target : r . target , step : r . step , body : r . body , next : r . next , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ForStep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWStep (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : value , state }) } pub type Parsed = Expression ; } # [inline] pub (super) fn parse_ForStep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForStep > { global . tracer . print_trace_start (& state , "ForStep") ; let result = (|| { let result = ForStep_impl :: parse (state , global) ? . map (| r | super :: ForStep { value : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ForNext_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWNext (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) . discard_result () . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_ForNext < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForNext > { global . tracer . print_trace_start (& state , "ForNext") ; let result = (|| { let result = ForNext_impl :: parse (state , global) ? . map (| r | super :: ForNext { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ExitForStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWExit (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWFor (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_ExitForStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ExitForStatement > { global . tracer . print_trace_start (& state , "ExitForStatement") ; let result = (|| { let result = ExitForStatement_impl :: parse (state , global) ? . map (| r | super :: ExitForStatement { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod EndStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEnd (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_EndStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , EndStatement > { global . tracer . print_trace_start (& state , "EndStatement") ; let result = (|| { let result = EndStatement_impl :: parse (state , global) ? . map (| r | super :: EndStatement { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWPrint_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "PRINT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWPrint < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWPrint > { global . tracer . print_trace_start (& state , "KWPrint") ; let result = (|| { let result = KWPrint_impl :: parse (state , global) ? . map (| r | super :: KWPrint { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWFor_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "FOR") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWFor < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWFor > { global . tracer . print_trace_start (& state , "KWFor") ; let result = (|| { let result = KWFor_impl :: parse (state , global) ? . map (| r | super :: KWFor { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWTo_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "TO") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWTo < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWTo > { global . tracer . print_trace_start (& state , "KWTo") ; let result = (|| { let result = KWTo_impl :: parse (state , global) ? . map (| r | super :: KWTo { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWStep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "STEP") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWStep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWStep > { global . tracer . print_trace_start (& state , "KWStep") ; let result = (|| { let result = KWStep_impl :: parse (state , global) ? . map (| r | super :: KWStep { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWNext_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "NEXT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWNext < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWNext > { global . tracer . print_trace_start (& state , "KWNext") ; let result = (|| { let result = KWNext_impl :: parse (state , global) ? . map (| r | super :: KWNext { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWEnd_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "END") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEnd < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEnd > { global . tracer . print_trace_start (& state , "KWEnd") ; let result = (|| { let result = KWEnd_impl :: parse (state , global) ? . map (| r | super :: KWEnd { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWIf_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "IF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWIf < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWIf > { global . tracer . print_trace_start (& state , "KWIf") ; let result = (|| { let result = KWIf_impl :: parse (state , global) ? . map (| r | super :: KWIf { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWThen_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "THEN") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWThen < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWThen > { global . tracer . print_trace_start (& state , "KWThen") ; let result = (|| { let result = KWThen_impl :: parse (state , global) ? . map (| r | super :: KWThen { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWElse_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ELSE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWElse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWElse > { global . tracer . print_trace_start (& state , "KWElse") ; let result = (|| { let result = KWElse_impl :: parse (state , global) ? . map (| r | super :: KWElse { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWEndif_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ENDIF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEndif < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEndif > { global . tracer . print_trace_start (& state , "KWEndif") ; let result = (|| { let result = KWEndif_impl :: parse (state , global) ? . map (| r | super :: KWEndif { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWExit_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "EXIT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWExit < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWExit > { global . tracer . print_trace_start (& state , "KWExit") ; let result = (|| { let result = KWExit_impl :: parse (state , global) ? . map (| r | super :: KWExit { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWWhile_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "WHILE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWWhile < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWWhile > { global . tracer . print_trace_start (& state , "KWWhile") ; let result = (|| { let result = KWWhile_impl :: parse (state , global) ? . map (| r | super :: KWWhile { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod KWEndwhile_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ENDWHILE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEndwhile < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEndwhile > { global . tracer . print_trace_start (& state , "KWEndwhile") ; let result = (|| { let result = KWEndwhile_impl :: parse (state , global) ? . map (| r | super :: KWEndwhile { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod NumericVariable_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) ? ; let ParseOk { result : type_dem , state } = ChoiceHelper :: new (state . clone ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: FloatDenominator (result)))) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: IntegerDenominator (result)))) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ByteDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: ByteDenominator (result)))) . end () . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : Parsed { name , type_dem , } , state }) } pub struct Parsed { pub name : VariableName , pub type_dem : Option < Parsed_type_dem > , } use super :: NumericVariable_type_dem as Parsed_type_dem ; } # [inline] pub (super) fn parse_NumericVariable < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NumericVariable > { global . tracer . print_trace_start (& state , "NumericVariable") ; let result = (|| { let result = NumericVariable_impl :: parse (state , global) ? . map (| r | super :: NumericVariable { name : r . name , type_dem : r . type_dem , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod FloatDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '#') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_FloatDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatDenominator > { global . tracer . print_trace_start (& state , "FloatDenominator") ; let result = (|| { let result = FloatDenominator_impl :: parse (state , global) ? . map (| r | super :: FloatDenominator { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod IntegerDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '%') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_IntegerDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IntegerDenominator > { global . tracer . print_trace_start (& state , "IntegerDenominator") ; let result = (|| { let result = IntegerDenominator_impl :: parse (state , global) ? . map (| r | super :: IntegerDenominator { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ByteDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '&') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ByteDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ByteDenominator > { global . tracer . print_trace_start (& state , "ByteDenominator") ; let result = (|| { let result = ByteDenominator_impl :: parse (state , global) ? . map (| r | super :: ByteDenominator { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod StringDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '$') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_StringDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringDenominator > { global . tracer . print_trace_start (& state , "StringDenominator") ; let result = (|| { let result = StringDenominator_impl :: parse (state , global) ? . map (| r | super :: StringDenominator { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod StringVariable_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringDenominator (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : name , state }) } pub type Parsed = VariableName ; } # [inline] pub (super) fn parse_StringVariable < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringVariable > { global . tracer . print_trace_start (& state , "StringVariable") ; let result = (|| { let result = StringVariable_impl :: parse (state , global) ? . map (| r | super :: StringVariable { name : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod VariableName_impl { use super :: * ; mod part_0 { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_character_range (state , 'a' , 'z') . discard_result ()) . choice (| state | parse_character_range (state , 'A' , 'Z') . discard_result ()) . end () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global)) . choice (| state | parse_character_literal (state , '_') . discard_result ()) . choice (| state | parse_character_literal (state , '`') . discard_result ()) . end () } pub type Parsed = () ; } mod part_1 { use super :: * ; mod closure { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_character_range (state , 'a' , 'z') . discard_result ()) . choice (| state | parse_character_range (state , 'A' , 'Z') . discard_result ()) . end () } pub type Parsed = () ; } mod choice_1 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global)) . choice (| state | choice_1 :: parse (state , global)) . choice (| state | parse_character_literal (state , '_') . discard_result ()) . end () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = part_0 :: parse (state , global) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_VariableName < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , VariableName > { global . tracer . print_trace_start (& state , "VariableName") ; let result = (|| { let result = VariableName_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod NumberSign_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '+') }) . discard_result ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '-') }) . discard_result ()) . end () } pub type Parsed = () ; } # [inline] pub (super) fn parse_NumberSign < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NumberSign > { global . tracer . print_trace_start (& state , "NumberSign") ; let result = (|| { let result = NumberSign_impl :: parse (state , global) ? . map (| r | super :: NumberSign { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod NumberLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatLiteral (state , & mut * global) }) . map_inner (Parsed_value :: FloatLiteral)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerLiteral (state , & mut * global) }) . map_inner (Parsed_value :: IntegerLiteral)) . end () } pub type Parsed = Parsed_value ; use super :: NumberLiteral_value as Parsed_value ; } # [inline] pub (super) fn parse_NumberLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NumberLiteral > { global . tracer . print_trace_start (& state , "NumberLiteral") ; let result = (|| { let result = NumberLiteral_impl :: parse (state , global) ? . map (| r | super :: NumberLiteral { value : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod IntegerLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerBody (state , & mut * global) }) } pub type Parsed = IntegerBody ; } # [inline] pub (super) fn parse_IntegerLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IntegerLiteral > { global . tracer . print_trace_start (& state , "IntegerLiteral") ; let result = (|| { let result = IntegerLiteral_impl :: parse (state , global) ? . map (| r | super :: IntegerLiteral { body : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod IntegerBody_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_character_range (state . clone () , '0' , '9') . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_NumberSign (state . clone () , & mut * global) . discard_result () . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_IntegerBody < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IntegerBody > { global . tracer . print_trace_start (& state , "IntegerBody") ; let result = (|| { let result = IntegerBody_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod FloatLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatBody (state , & mut * global) }) } pub type Parsed = FloatBody ; } # [inline] pub (super) fn parse_FloatLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatLiteral > { global . tracer . print_trace_start (& state , "FloatLiteral") ; let result = (|| { let result = FloatLiteral_impl :: parse (state , global) ? . map (| r | super :: FloatLiteral { body : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod FloatBody_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_3 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_4 { use super :: * ; mod optional { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_character_literal (state , 'E') . discard_result () ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_NumberSign (state . clone () , & mut * global) . discard_result () . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_character_literal (state , '.') . discard_result () ? ; let ParseOk { state , .. } = part_3 :: parse (state , global) ? ; let ParseOk { state , .. } = part_4 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_FloatBody < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatBody > { global . tracer . print_trace_start (& state , "FloatBody") ; let result = (|| { let result = FloatBody_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod StringLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_character_literal (state , '"') . discard_result () ? ; let ParseOk { result : body , state } = parse_StringBody (state , & mut * global) ? ; let ParseOk { state , .. } = parse_character_literal (state , '"') . discard_result () ? ; Ok (ParseOk { result : body , state }) } pub type Parsed = StringBody ; } # [inline] pub (super) fn parse_StringLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringLiteral > { global . tracer . print_trace_start (& state , "StringLiteral") ; let result = (|| { let result = StringLiteral_impl :: parse (state , global) ? . map (| r | super :: StringLiteral { body : r , }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod StringBody_impl { use super :: * ; mod closure { use super :: * ; mod part_0 { use super :: * ; mod negative_lookahead { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_literal (state , '"') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { match negative_lookahead :: parse (state . clone () , global) { Ok (_) => Err (state . report_error (ParseErrorSpecifics :: NegativeLookaheadFailed)) , Err (_) => Ok (ParseOk { result : () , state }) , } } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = part_0 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_char (state , & mut * global) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_StringBody < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringBody > { global . tracer . print_trace_start (& state , "StringBody") ; let result = (|| { let result = StringBody_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod ALPHA_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_range (state , 'a' , 'z') }) . discard_result ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_range (state , 'A' , 'Z') }) . discard_result ()) . end () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ALPHA < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ALPHA > { global . tracer . print_trace_start (& state , "ALPHA") ; let result = (|| { let result = ALPHA_impl :: parse (state , global) ? . map (| r | super :: ALPHA { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod DIGIT_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_range (state , '0' , '9') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_DIGIT < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , DIGIT > { global . tracer . print_trace_start (& state , "DIGIT") ; let result = (|| { let result = DIGIT_impl :: parse (state , global) ? . map (| r | super :: DIGIT { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } mod Whitespace_impl { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_character_literal (state . clone () , ' ') . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_Whitespace < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Whitespace > { global . tracer . print_trace_start (& state , "Whitespace") ; let result = (|| { let result = Whitespace_impl :: parse (state , global) ? . map (| r | super :: Whitespace { }) ; Ok (result) }) () ; global . tracer . print_trace_result (& result) ; result } }
+# [derive (Debug , Clone ,)] pub struct Program { pub body : Block , pub procedures : Vec < ProcDefinition > , } # [derive (Debug , Clone ,)] pub struct Block { pub statements : Vec < LocatedStatement > , } # [derive (Debug , Clone ,)] pub struct LocatedStatement { pub statement : Statement , pub position : std :: ops :: Range < usize > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Statement { Assignment (Assignment) , BputStatement (BputStatement) , CloseStatement (CloseStatement) , ClsStatement (ClsStatement) , DimStatement (DimStatement) , DrawStatement (DrawStatement) , EndStatement (EndStatement) , EndprocStatement (EndprocStatement) , ExitForStatement (ExitForStatement) , ExitWhileStatement (ExitWhileStatement) , ForStatement (ForStatement) , IfStatement (IfStatement) , IndirectAssignment (IndirectAssignment) , InputStatement (InputStatement) , MoveStatement (MoveStatement) , PlotStatement (PlotStatement) , PrintStatement (PrintStatement) , ProcCall (ProcCall) , SoundStatement (SoundStatement) , StringAssignment (StringAssignment) , TimeAssignment (TimeAssignment) , TraceStatement (TraceStatement) , VduStatement (VduStatement) , WhileStatement (WhileStatement) , } # [derive (Debug , Clone ,)] pub struct NewLine ; # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Expression { Add (Add) , Sub (Sub) , Term (Term) , } # [derive (Debug , Clone ,)] pub struct Add { pub left : Box < Expression > , pub right : Term , } # [derive (Debug , Clone ,)] pub struct Sub { pub left : Box < Expression > , pub right : Term , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Term { Div (Div) , Factor (Factor) , Mul (Mul) , } # [derive (Debug , Clone ,)] pub struct Mul { pub left : Box < Term > , pub right : Factor , } # [derive (Debug , Clone ,)] pub struct Div { pub left : Box < Term > , pub right : Factor , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Factor { BgetFunction (BgetFunction) , EofFunction (EofFunction) , FnCall (FnCall) , GetFunction (GetFunction) , Group (Group) , Indirection (Indirection) , InkeyFunction (InkeyFunction) , NumberLiteral (NumberLiteral) , NumericVariable (NumericVariable) , OpeninFunction (OpeninFunction) , OpenoutFunction (OpenoutFunction) , RndFunction (RndFunction) , TimeFunction (TimeFunction) , } # [derive (Debug , Clone ,)] pub struct Group { pub body : Box < Expression > , } # [derive (Debug , Clone ,)] pub struct BoolExpression { pub disjunction : BoolDisjunction , } # [derive (Debug , Clone ,)] pub struct BoolDisjunction { pub conjunction : Vec < BoolConjunction > , } # [derive (Debug , Clone ,)] pub struct BoolConjunction { pub term : Vec < BoolTerm > , } # [derive (Debug , Clone ,)] pub struct BoolTerm { pub expression : Option < BoolExpression > , pub condition : Option < BoolCondition > , } # [derive (Debug , Clone ,)] pub struct BoolCondition { pub left : BoolOperand , pub op : BoolOperator , pub right : BoolOperand , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum BoolOperator { BoolOpEqual (BoolOpEqual) , BoolOpGreater (BoolOpGreater) , BoolOpGreaterEqual (BoolOpGreaterEqual) , BoolOpLower (BoolOpLower) , BoolOpLowerEqual (BoolOpLowerEqual) , BoolOpNotEqual (BoolOpNotEqual) , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum BoolOperand { Expression (Expression) , StringLiteral (StringLiteral) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct BoolOpNotEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpLowerEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpGreaterEqual ; # [derive (Debug , Clone ,)] pub struct BoolOpLower ; # [derive (Debug , Clone ,)] pub struct BoolOpGreater ; pub type ThenBlock = Block ; pub type ElseBlock = Block ; pub type IfCondition = BoolDisjunction ; # [derive (Debug , Clone ,)] pub struct IfStatement { pub condition : IfCondition , pub then_block : ThenBlock , pub else_block : Option < ElseBlock > , } # [derive (Debug , Clone ,)] pub struct WhileStatement { pub condition : WhileCondition , pub body : Block , } pub type WhileCondition = BoolDisjunction ; # [derive (Debug , Clone ,)] pub struct ExitWhileStatement ; # [derive (Debug , Clone ,)] pub struct Assignment { pub variable : NumericVariable , pub value : Expression , } # [derive (Debug , Clone ,)] pub struct StringAssignment { pub variable : StringVariable , pub value : StringLiteral , } # [derive (Debug , Clone ,)] pub struct PrintStatement { pub list : Vec < PrintListItem > , } # [derive (Debug , Clone ,)] pub struct PrintSkipNl ; # [derive (Debug , Clone ,)] pub struct PrintListItem { pub value : PrintListItem_value , pub sep : Option < PrintListSep > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum PrintListItem_value { Expression (Expression) , StringLiteral (StringLiteral) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct PrintListSep ; # [derive (Debug , Clone ,)] pub struct InputStatement { pub prompt : Option < InputPrompt > , pub variable : InputStatement_variable , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum InputStatement_variable { NumericVariable (NumericVariable) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct InputPrompt { pub text : StringLiteral , pub sep : Option < InputPromptSep > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum InputPromptSep { InputAskSep (InputAskSep) , InputNoAskSep (InputNoAskSep) , } # [derive (Debug , Clone ,)] pub struct InputAskSep ; # [derive (Debug , Clone ,)] pub struct InputNoAskSep ; # [derive (Debug , Clone ,)] pub struct ForAssignment { pub variable : NumericVariable , pub value : Expression , } # [derive (Debug , Clone ,)] pub struct ForStatement { pub assignment : ForAssignment , pub target : Expression , pub step : Option < ForStep > , pub body : Block , pub next : ForNext , } # [derive (Debug , Clone ,)] pub struct ForStep { pub value : Expression , } # [derive (Debug , Clone ,)] pub struct ForNext { pub variable : Option < NumericVariable > , pub position : std :: ops :: Range < usize > , } # [derive (Debug , Clone ,)] pub struct ExitForStatement ; # [derive (Debug , Clone ,)] pub struct ProcDefinition { pub name : VariableName , pub parameters : Vec < Parameter > , pub body : Block , pub position : std :: ops :: Range < usize > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Parameter { NumericVariable (NumericVariable) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct ProcCall { pub name : VariableName , pub arguments : Vec < BoolOperand > , } # [derive (Debug , Clone ,)] pub struct EndprocStatement ; # [derive (Debug , Clone ,)] pub struct FnCall { pub name : VariableName , pub arguments : Vec < BoolOperand > , } # [derive (Debug , Clone ,)] pub struct DimStatement { pub variable : NumericVariable , pub size : Expression , } # [derive (Debug , Clone ,)] pub struct IndirectAssignment { pub target : Indirection , pub value : BoolOperand , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum Indirection { DyadicIndirection (DyadicIndirection) , UnaryIndirection (UnaryIndirection) , } # [derive (Debug , Clone ,)] pub struct UnaryIndirection { pub op : IndirectionOperator , pub address : Box < Factor > , } # [derive (Debug , Clone ,)] pub struct DyadicIndirection { pub base : NumericVariable , pub op : DyadicOperator , pub offset : Box < Factor > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum IndirectionOperator { ByteIndirection (ByteIndirection) , FloatIndirection (FloatIndirection) , StringIndirection (StringIndirection) , WordIndirection (WordIndirection) , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum DyadicOperator { ByteIndirection (ByteIndirection) , WordIndirection (WordIndirection) , } # [derive (Debug , Clone ,)] pub struct ByteIndirection ; # [derive (Debug , Clone ,)] pub struct WordIndirection ; # [derive (Debug , Clone ,)] pub struct FloatIndirection ; # [derive (Debug , Clone ,)] pub struct StringIndirection ; # [derive (Debug , Clone ,)] pub struct TimeFunction ; # [derive (Debug , Clone ,)] pub struct RndFunction { pub argument : Option < Box < Expression >> , } # [derive (Debug , Clone ,)] pub struct GetFunction ; # [derive (Debug , Clone ,)] pub struct InkeyFunction { pub argument : Box < Expression > , } # [derive (Debug , Clone ,)] pub struct OpeninFunction { pub name : OpeninFunction_name , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum OpeninFunction_name { StringLiteral (StringLiteral) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct OpenoutFunction { pub name : OpenoutFunction_name , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum OpenoutFunction_name { StringLiteral (StringLiteral) , StringVariable (StringVariable) , } # [derive (Debug , Clone ,)] pub struct BgetFunction { pub channel : Box < Factor > , } # [derive (Debug , Clone ,)] pub struct EofFunction { pub channel : Box < Factor > , } # [derive (Debug , Clone ,)] pub struct TimeAssignment { pub value : Expression , } # [derive (Debug , Clone ,)] pub struct ClsStatement ; # [derive (Debug , Clone ,)] pub struct VduStatement { pub values : Vec < Expression > , } # [derive (Debug , Clone ,)] pub struct PlotStatement { pub mode : Expression , pub x : Expression , pub y : Expression , } # [derive (Debug , Clone ,)] pub struct MoveStatement { pub x : Expression , pub y : Expression , } # [derive (Debug , Clone ,)] pub struct DrawStatement { pub x : Expression , pub y : Expression , } # [derive (Debug , Clone ,)] pub struct SoundStatement { pub channel : Expression , pub amplitude : Expression , pub pitch : Expression , pub duration : Expression , } # [derive (Debug , Clone ,)] pub struct BputStatement { pub channel : Factor , pub value : Expression , } # [derive (Debug , Clone ,)] pub struct CloseStatement { pub channel : Factor , } # [derive (Debug , Clone ,)] pub struct TraceStatement { pub mode : TraceMode , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum TraceMode { Expression (Expression) , TraceOff (TraceOff) , TraceOn (TraceOn) , TraceProc (TraceProc) , } # [derive (Debug , Clone ,)] pub struct TraceOn ; # [derive (Debug , Clone ,)] pub struct TraceOff ; # [derive (Debug , Clone ,)] pub struct TraceProc ; # [derive (Debug , Clone ,)] pub struct EndStatement ; # [derive (Debug , Clone ,)] pub struct KWPrint ; # [derive (Debug , Clone ,)] pub struct KWInput ; # [derive (Debug , Clone ,)] pub struct KWFor ; # [derive (Debug , Clone ,)] pub struct KWTo ; # [derive (Debug , Clone ,)] pub struct KWStep ; # [derive (Debug , Clone ,)] pub struct KWNext ; # [derive (Debug , Clone ,)] pub struct KWEnd ; # [derive (Debug , Clone ,)] pub struct KWIf ; # [derive (Debug , Clone ,)] pub struct KWThen ; # [derive (Debug , Clone ,)] pub struct KWElse ; # [derive (Debug , Clone ,)] pub struct KWEndif ; # [derive (Debug , Clone ,)] pub struct KWExit ; # [derive (Debug , Clone ,)] pub struct KWWhile ; # [derive (Debug , Clone ,)] pub struct KWEndwhile ; # [derive (Debug , Clone ,)] pub struct KWTime ; # [derive (Debug , Clone ,)] pub struct KWRnd ; # [derive (Debug , Clone ,)] pub struct KWGet ; # [derive (Debug , Clone ,)] pub struct KWInkey ; # [derive (Debug , Clone ,)] pub struct KWOpenin ; # [derive (Debug , Clone ,)] pub struct KWOpenout ; # [derive (Debug , Clone ,)] pub struct KWBget ; # [derive (Debug , Clone ,)] pub struct KWBput ; # [derive (Debug , Clone ,)] pub struct KWEof ; # [derive (Debug , Clone ,)] pub struct KWClose ; # [derive (Debug , Clone ,)] pub struct KWCls ; # [derive (Debug , Clone ,)] pub struct KWVdu ; # [derive (Debug , Clone ,)] pub struct KWPlot ; # [derive (Debug , Clone ,)] pub struct KWMove ; # [derive (Debug , Clone ,)] pub struct KWDraw ; # [derive (Debug , Clone ,)] pub struct KWSound ; # [derive (Debug , Clone ,)] pub struct KWDef ; # [derive (Debug , Clone ,)] pub struct KWDim ; # [derive (Debug , Clone ,)] pub struct KWProc ; # [derive (Debug , Clone ,)] pub struct KWEndproc ; # [derive (Debug , Clone ,)] pub struct KWFn ; # [derive (Debug , Clone ,)] pub struct KWTrace ; # [derive (Debug , Clone ,)] pub struct KWOn ; # [derive (Debug , Clone ,)] pub struct KWOff ; # [derive (Debug , Clone ,)] pub struct NumericVariable { pub name : NumericVariable_name , pub type_dem : Option < NumericVariable_type_dem > , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum NumericVariable_name { ResidentName (ResidentName) , VariableName (VariableName) , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum NumericVariable_type_dem { ByteDenominator (ByteDenominator) , FloatDenominator (FloatDenominator) , IntegerDenominator (IntegerDenominator) , } # [derive (Debug , Clone ,)] pub struct FloatDenominator ; # [derive (Debug , Clone ,)] pub struct IntegerDenominator ; # [derive (Debug , Clone ,)] pub struct ByteDenominator ; # [derive (Debug , Clone ,)] pub struct StringDenominator ; # [derive (Debug , Clone ,)] pub struct StringVariable { pub name : VariableName , } pub type ResidentName = String ; pub type VariableName = String ; # [derive (Debug , Clone ,)] pub struct NumberSign ; # [derive (Debug , Clone ,)] pub struct NumberLiteral { pub value : NumberLiteral_value , } # [allow (non_camel_case_types)] # [derive (Debug , Clone ,)] pub enum NumberLiteral_value { FloatLiteral (FloatLiteral) , IntegerLiteral (IntegerLiteral) , } # [derive (Debug , Clone ,)] pub struct IntegerLiteral { pub body : IntegerBody , } pub type IntegerBody = String ; # [derive (Debug , Clone ,)] pub struct FloatLiteral { pub body : FloatBody , } pub type FloatBody = String ; # [derive (Debug , Clone ,)] pub struct StringLiteral { pub body : StringBody , } pub type StringBody = String ; # [derive (Debug , Clone ,)] pub struct ALPHA ; # [derive (Debug , Clone ,)] pub struct DIGIT ; # [derive (Debug , Clone ,)] pub struct Whitespace ; impl peginator_generated :: PegParserAdvanced < () > for Program { fn parse_advanced < TT : peginator_generated :: ParseTracer > (s : & str , settings : & peginator_generated :: ParseSettings , user_defined : () ,) -> Result < Self , peginator_generated :: ParseError > { Ok (peginator_generated :: parse_Program (peginator_generated :: ParseState :: new (s , settings) , & mut peginator_generated :: ParseGlobal :: < TT , peginator_generated :: ParseCache , () > :: new (Default :: default () , user_defined ,) ,) ? . result) } } impl peginator_generated :: PegParserAdvanced < () > for Expression { fn parse_advanced < TT : peginator_generated :: ParseTracer > (s : & str , settings : & peginator_generated :: ParseSettings , user_defined : () ,) -> Result < Self , peginator_generated :: ParseError > { Ok (peginator_generated :: parse_Expression (peginator_generated :: ParseState :: new (s , settings) , & mut peginator_generated :: ParseGlobal :: < TT , peginator_generated :: ParseCache , () > :: new (Default :: default () , user_defined ,) ,) ? . result) } } impl peginator_generated :: PegParserAdvanced < () > for BoolExpression { fn parse_advanced < TT : peginator_generated :: ParseTracer > (s : & str , settings : & peginator_generated :: ParseSettings , user_defined : () ,) -> Result < Self , peginator_generated :: ParseError > { Ok (peginator_generated :: parse_BoolExpression (peginator_generated :: ParseState :: new (s , settings) , & mut peginator_generated :: ParseGlobal :: < TT , peginator_generated :: ParseCache , () > :: new (Default :: default () , user_defined ,) ,) ? . result) } } # [allow (non_snake_case , unused_variables , unused_imports , unused_mut , dead_code ,)] mod peginator_generated { use super :: * ; pub use peginator :: { ParseError , ParseSettings , ParseState , PegParser , IndentedTracer , ParseTracer , PegPosition , ParseGlobal , PegParserAdvanced , } ; use peginator :: * ; # [derive (Default)] pub struct ParseCache < 'a > { pub c_Expression : CacheEntries < 'a , Expression > , pub c_Term : CacheEntries < 'a , Term > , pub c_Factor : CacheEntries < 'a , Factor > , _please_dont_complain : std :: marker :: PhantomData < & 'a () > , } mod Program_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut procedures : Vec < ProcDefinition > = Vec :: new () ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ProcDefinition (state , & mut * global) }) . map_inner (| result | vec ! [result]) { Ok (ParseOk { result : __result , state : new_state , .. }) => { procedures . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : procedures , state }) } pub type Parsed = Vec < ProcDefinition > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; let ParseOk { result : mut procedures , state } = part_1 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_end_of_input (state ,) }) ? ; Ok (ParseOk { result : Parsed { body , procedures , } , state }) } pub struct Parsed { pub body : Block , pub procedures : Vec < ProcDefinition > , } } # [inline] pub (super) fn parse_Program < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Program > { global . tracer . print_trace_start (& state , "Program") ; let result = { let result = Program_impl :: parse (state , global) ? . map (| r | super :: Program { body : r . body , procedures : r . procedures , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Block_impl { use super :: * ; mod part_0 { use super :: * ; mod closure { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut statements , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_LocatedStatement (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : statements , state }) } pub type Parsed = Vec < LocatedStatement > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut statements : Vec < LocatedStatement > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { statements . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : statements , state }) } pub type Parsed = Vec < LocatedStatement > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut statements , state } = part_0 :: parse (state , global) ? ; let ParseOk { result : extend_statements_with , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_LocatedStatement (state , & mut * global) }) . map_inner (| result | vec ! [result]) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; statements . extend (extend_statements_with) ; Ok (ParseOk { result : statements , state }) } pub type Parsed = Vec < LocatedStatement > ; } # [inline] pub (super) fn parse_Block < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Block > { global . tracer . print_trace_start (& state , "Block") ; let result = { let result = Block_impl :: parse (state , global) ? . map (| r | super :: Block { statements : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod LocatedStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Statement (state , & mut * global) }) } pub type Parsed = Statement ; } # [inline] pub (super) fn parse_LocatedStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , LocatedStatement > { global . tracer . print_trace_start (& state , "LocatedStatement") ; let result = { let result = LocatedStatement_impl :: parse (state . clone () , global) ? . map_with_state (| r , new_state | super :: LocatedStatement { statement : r , position : state . range_until (new_state) , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } impl PegPosition for LocatedStatement { fn position (& self) -> & std :: ops :: Range < usize > { & self . position } } mod Statement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ExitWhileStatement (state , global) }) . map_inner (Parsed__override :: ExitWhileStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_WhileStatement (state , global) }) . map_inner (Parsed__override :: WhileStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ExitForStatement (state , global) }) . map_inner (Parsed__override :: ExitForStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IfStatement (state , global) }) . map_inner (Parsed__override :: IfStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForStatement (state , global) }) . map_inner (Parsed__override :: ForStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PrintStatement (state , global) }) . map_inner (Parsed__override :: PrintStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_InputStatement (state , global) }) . map_inner (Parsed__override :: InputStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_EndprocStatement (state , global) }) . map_inner (Parsed__override :: EndprocStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_EndStatement (state , global) }) . map_inner (Parsed__override :: EndStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ClsStatement (state , global) }) . map_inner (Parsed__override :: ClsStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VduStatement (state , global) }) . map_inner (Parsed__override :: VduStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PlotStatement (state , global) }) . map_inner (Parsed__override :: PlotStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_MoveStatement (state , global) }) . map_inner (Parsed__override :: MoveStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_DrawStatement (state , global) }) . map_inner (Parsed__override :: DrawStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_SoundStatement (state , global) }) . map_inner (Parsed__override :: SoundStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BputStatement (state , global) }) . map_inner (Parsed__override :: BputStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_CloseStatement (state , global) }) . map_inner (Parsed__override :: CloseStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ProcCall (state , global) }) . map_inner (Parsed__override :: ProcCall)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TraceStatement (state , global) }) . map_inner (Parsed__override :: TraceStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TimeAssignment (state , global) }) . map_inner (Parsed__override :: TimeAssignment)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_DimStatement (state , global) }) . map_inner (Parsed__override :: DimStatement)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IndirectAssignment (state , global) }) . map_inner (Parsed__override :: IndirectAssignment)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Assignment (state , global) }) . map_inner (Parsed__override :: Assignment)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringAssignment (state , global) }) . map_inner (Parsed__override :: StringAssignment)) . end () } pub type Parsed = Parsed__override ; use super :: Statement as Parsed__override ; } # [inline] pub (super) fn parse_Statement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Statement > { global . tracer . print_trace_start (& state , "Statement") ; let result = { let result = Statement_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod NewLine_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '\n') }) . discard_result ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "\r\n") }) . discard_result ()) . end () } pub type Parsed = () ; } # [inline] pub (super) fn parse_NewLine < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NewLine > { global . tracer . print_trace_start (& state , "NewLine") ; let result = { let result = NewLine_impl :: parse (state , global) ? . map (| r | super :: NewLine { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Expression_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Add (state , global) }) . map_inner (Parsed__override :: Add)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Sub (state , global) }) . map_inner (Parsed__override :: Sub)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , global) }) . map_inner (Parsed__override :: Term)) . end () } pub type Parsed = Parsed__override ; use super :: Expression as Parsed__override ; } # [inline] pub (super) fn parse_Expression < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Expression > { global . tracer . print_trace_start (& state , "Expression") ; let result = { let cache_key = state . cache_key () ; if let Some (cached) = global . cache . c_Expression . get (& cache_key) { global . tracer . print_informative ("Cache hit (left recursive)") ; cached . clone () } else { let mut best_result = Err (state . clone () . report_error (ParseErrorSpecifics :: LeftRecursionSentinel)) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; loop { global . tracer . print_informative ("Starting new left recursive loop") ; let state = state . clone () ; let new_result = { let result = Expression_impl :: parse (state , global) ? ; Ok (result) } ; match (new_result , & best_result) { (Ok (nro) , Ok (bro)) => { if nro . state . is_further_than (& bro . state) { best_result = Ok (nro) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; } else { break ; } } (Ok (nro) , Err (bre)) => { best_result = Ok (nro) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; } (Err (nre) , Ok (bro)) => { break ; } (Err (nre) , Err (bre)) => { best_result = Err (nre) ; global . cache . c_Expression . insert (cache_key , best_result . clone ()) ; break ; } } } best_result } } ; global . tracer . print_trace_result (& result) ; result } mod Add_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '+') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Expression > , pub right : Term , } } # [inline] pub (super) fn parse_Add < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Add > { global . tracer . print_trace_start (& state , "Add") ; let result = { let result = Add_impl :: parse (state , global) ? . map (| r | super :: Add { left : r . left , right : r . right , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Sub_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '-') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Expression > , pub right : Term , } } # [inline] pub (super) fn parse_Sub < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Sub > { global . tracer . print_trace_start (& state , "Sub") ; let result = { let result = Sub_impl :: parse (state , global) ? . map (| r | super :: Sub { left : r . left , right : r . right , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Term_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Mul (state , global) }) . map_inner (Parsed__override :: Mul)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Div (state , global) }) . map_inner (Parsed__override :: Div)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , global) }) . map_inner (Parsed__override :: Factor)) . end () } pub type Parsed = Parsed__override ; use super :: Term as Parsed__override ; } # [inline] pub (super) fn parse_Term < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Term > { global . tracer . print_trace_start (& state , "Term") ; let result = { let cache_key = state . cache_key () ; if let Some (cached) = global . cache . c_Term . get (& cache_key) { global . tracer . print_informative ("Cache hit (left recursive)") ; cached . clone () } else { let mut best_result = Err (state . clone () . report_error (ParseErrorSpecifics :: LeftRecursionSentinel)) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; loop { global . tracer . print_informative ("Starting new left recursive loop") ; let state = state . clone () ; let new_result = { let result = Term_impl :: parse (state , global) ? ; Ok (result) } ; match (new_result , & best_result) { (Ok (nro) , Ok (bro)) => { if nro . state . is_further_than (& bro . state) { best_result = Ok (nro) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; } else { break ; } } (Ok (nro) , Err (bre)) => { best_result = Ok (nro) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; } (Err (nre) , Ok (bro)) => { break ; } (Err (nre) , Err (bre)) => { best_result = Err (nre) ; global . cache . c_Term . insert (cache_key , best_result . clone ()) ; break ; } } } best_result } } ; global . tracer . print_trace_result (& result) ; result } mod Mul_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '*') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Term > , pub right : Factor , } } # [inline] pub (super) fn parse_Mul < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Mul > { global . tracer . print_trace_start (& state , "Mul") ; let result = { let result = Mul_impl :: parse (state , global) ? . map (| r | super :: Mul { left : r . left , right : r . right , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Div_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Term (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '/') }) . discard_result () ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , right , } , state }) } pub struct Parsed { pub left : Box < Term > , pub right : Factor , } } # [inline] pub (super) fn parse_Div < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Div > { global . tracer . print_trace_start (& state , "Div") ; let result = { let result = Div_impl :: parse (state , global) ? . map (| r | super :: Div { left : r . left , right : r . right , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Factor_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Group (state , global) }) . map_inner (Parsed__override :: Group)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumberLiteral (state , global) }) . map_inner (Parsed__override :: NumberLiteral)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TimeFunction (state , global) }) . map_inner (Parsed__override :: TimeFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_RndFunction (state , global) }) . map_inner (Parsed__override :: RndFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_GetFunction (state , global) }) . map_inner (Parsed__override :: GetFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_InkeyFunction (state , global) }) . map_inner (Parsed__override :: InkeyFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_OpeninFunction (state , global) }) . map_inner (Parsed__override :: OpeninFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_OpenoutFunction (state , global) }) . map_inner (Parsed__override :: OpenoutFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BgetFunction (state , global) }) . map_inner (Parsed__override :: BgetFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_EofFunction (state , global) }) . map_inner (Parsed__override :: EofFunction)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FnCall (state , global) }) . map_inner (Parsed__override :: FnCall)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Indirection (state , global) }) . map_inner (Parsed__override :: Indirection)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , global) }) . map_inner (Parsed__override :: NumericVariable)) . end () } pub type Parsed = Parsed__override ; use super :: Factor as Parsed__override ; } # [inline] pub (super) fn parse_Factor < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Factor > { global . tracer . print_trace_start (& state , "Factor") ; let result = { let cache_key = state . cache_key () ; if let Some (cached) = global . cache . c_Factor . get (& cache_key) { global . tracer . print_informative ("Cache hit") ; cached . clone () } else { let result = { let result = Factor_impl :: parse (state , global) ? ; Ok (result) } ; global . cache . c_Factor . insert (cache_key , result . clone ()) ; result } } ; global . tracer . print_trace_result (& result) ; result } mod Group_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : body , state }) } pub type Parsed = Box < Expression > ; } # [inline] pub (super) fn parse_Group < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Group > { global . tracer . print_trace_start (& state , "Group") ; let result = { let result = Group_impl :: parse (state , global) ? . map (| r | super :: Group { body : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolExpression_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolDisjunction (state , & mut * global) }) } pub type Parsed = BoolDisjunction ; } # [inline] pub (super) fn parse_BoolExpression < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolExpression > { global . tracer . print_trace_start (& state , "BoolExpression") ; let result = { let result = BoolExpression_impl :: parse (state , global) ? . map (| r | super :: BoolExpression { disjunction : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolDisjunction_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "OR") }) . discard_result () ? ; let ParseOk { result : mut conjunction , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolConjunction (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : conjunction , state }) } pub type Parsed = Vec < BoolConjunction > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut conjunction : Vec < BoolConjunction > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { conjunction . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : conjunction , state }) } pub type Parsed = Vec < BoolConjunction > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut conjunction , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolConjunction (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_conjunction_with , state } = part_1 :: parse (state , global) ? ; conjunction . extend (extend_conjunction_with) ; Ok (ParseOk { result : conjunction , state }) } pub type Parsed = Vec < BoolConjunction > ; } # [inline] pub (super) fn parse_BoolDisjunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolDisjunction > { global . tracer . print_trace_start (& state , "BoolDisjunction") ; let result = { let result = BoolDisjunction_impl :: parse (state , global) ? . map (| r | super :: BoolDisjunction { conjunction : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolConjunction_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "AND") }) . discard_result () ? ; let ParseOk { result : mut term , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolTerm (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : term , state }) } pub type Parsed = Vec < BoolTerm > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut term : Vec < BoolTerm > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { term . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : term , state }) } pub type Parsed = Vec < BoolTerm > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : mut term , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolTerm (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_term_with , state } = part_1 :: parse (state , global) ? ; term . extend (extend_term_with) ; Ok (ParseOk { result : term , state }) } pub type Parsed = Vec < BoolTerm > ; } # [inline] pub (super) fn parse_BoolConjunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolConjunction > { global . tracer . print_trace_start (& state , "BoolConjunction") ; let result = { let result = BoolConjunction_impl :: parse (state , global) ? . map (| r | super :: BoolConjunction { term : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolTerm_impl { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : expression , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolExpression (state , & mut * global) }) . map_inner (Some) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : expression , state }) } pub type Parsed = Option < BoolExpression > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global) . map_inner (| r | Parsed { expression : r , condition : None , })) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolCondition (state , & mut * global) }) . map_inner (Some) . map_inner (| r | Parsed { expression : None , condition : r , })) . end () } pub struct Parsed { pub expression : Option < BoolExpression > , pub condition : Option < BoolCondition > , } } # [inline] pub (super) fn parse_BoolTerm < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolTerm > { global . tracer . print_trace_start (& state , "BoolTerm") ; let result = { let result = BoolTerm_impl :: parse (state , global) ? . map (| r | super :: BoolTerm { expression : r . expression , condition : r . condition , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolCondition_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : left , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) ? ; let ParseOk { result : op , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperator (state , & mut * global) }) ? ; let ParseOk { result : right , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { left , op , right , } , state }) } pub struct Parsed { pub left : BoolOperand , pub op : BoolOperator , pub right : BoolOperand , } } # [inline] pub (super) fn parse_BoolCondition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolCondition > { global . tracer . print_trace_start (& state , "BoolCondition") ; let result = { let result = BoolCondition_impl :: parse (state , global) ? . map (| r | super :: BoolCondition { left : r . left , op : r . op , right : r . right , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOperator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpNotEqual (state , global) }) . map_inner (Parsed__override :: BoolOpNotEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpEqual (state , global) }) . map_inner (Parsed__override :: BoolOpEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpLowerEqual (state , global) }) . map_inner (Parsed__override :: BoolOpLowerEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpGreaterEqual (state , global) }) . map_inner (Parsed__override :: BoolOpGreaterEqual)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpLower (state , global) }) . map_inner (Parsed__override :: BoolOpLower)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOpGreater (state , global) }) . map_inner (Parsed__override :: BoolOpGreater)) . end () } pub type Parsed = Parsed__override ; use super :: BoolOperator as Parsed__override ; } # [inline] pub (super) fn parse_BoolOperator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOperator > { global . tracer . print_trace_start (& state , "BoolOperator") ; let result = { let result = BoolOperator_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOperand_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , global) }) . map_inner (Parsed__override :: StringVariable)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , global) }) . map_inner (Parsed__override :: Expression)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , global) }) . map_inner (Parsed__override :: StringLiteral)) . end () } pub type Parsed = Parsed__override ; use super :: BoolOperand as Parsed__override ; } # [inline] pub (super) fn parse_BoolOperand < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOperand > { global . tracer . print_trace_start (& state , "BoolOperand") ; let result = { let result = BoolOperand_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOpNotEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "<>") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpNotEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpNotEqual > { global . tracer . print_trace_start (& state , "BoolOpNotEqual") ; let result = { let result = BoolOpNotEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpNotEqual { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOpEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpEqual > { global . tracer . print_trace_start (& state , "BoolOpEqual") ; let result = { let result = BoolOpEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpEqual { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOpLowerEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "<=") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpLowerEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpLowerEqual > { global . tracer . print_trace_start (& state , "BoolOpLowerEqual") ; let result = { let result = BoolOpLowerEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpLowerEqual { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOpGreaterEqual_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , ">=") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpGreaterEqual < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpGreaterEqual > { global . tracer . print_trace_start (& state , "BoolOpGreaterEqual") ; let result = { let result = BoolOpGreaterEqual_impl :: parse (state , global) ? . map (| r | super :: BoolOpGreaterEqual { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOpLower_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '<') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpLower < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpLower > { global . tracer . print_trace_start (& state , "BoolOpLower") ; let result = { let result = BoolOpLower_impl :: parse (state , global) ? . map (| r | super :: BoolOpLower { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BoolOpGreater_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '>') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_BoolOpGreater < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BoolOpGreater > { global . tracer . print_trace_start (& state , "BoolOpGreater") ; let result = { let result = BoolOpGreater_impl :: parse (state , global) ? . map (| r | super :: BoolOpGreater { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ThenBlock_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , global) }) } pub type Parsed = Block ; use super :: ThenBlock as Parsed__override ; } # [inline] pub (super) fn parse_ThenBlock < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ThenBlock > { global . tracer . print_trace_start (& state , "ThenBlock") ; let result = { let result = ThenBlock_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ElseBlock_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , global) }) } pub type Parsed = Block ; use super :: ElseBlock as Parsed__override ; } # [inline] pub (super) fn parse_ElseBlock < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ElseBlock > { global . tracer . print_trace_start (& state , "ElseBlock") ; let result = { let result = ElseBlock_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IfCondition_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolDisjunction (state , global) }) } pub type Parsed = BoolDisjunction ; use super :: IfCondition as Parsed__override ; } # [inline] pub (super) fn parse_IfCondition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IfCondition > { global . tracer . print_trace_start (& state , "IfCondition") ; let result = { let result = IfCondition_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IfStatement_impl { use super :: * ; mod part_3 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_5 { use super :: * ; mod optional { use super :: * ; mod part_0 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_2 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = part_0 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWElse (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = part_2 :: parse (state , global) ? ; let ParseOk { result : else_block , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ElseBlock (state , & mut * global) }) . map_inner (Some) ? ; Ok (ParseOk { result : else_block , state }) } pub type Parsed = Option < ElseBlock > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) } pub type Parsed = Option < ElseBlock > ; } mod part_6 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWIf (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : condition , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IfCondition (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWThen (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = part_3 :: parse (state , global) ? ; let ParseOk { result : then_block , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ThenBlock (state , & mut * global) }) ? ; let ParseOk { result : else_block , state } = part_5 :: parse (state , global) ? ; let ParseOk { state , .. } = part_6 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEndif (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : Parsed { condition , then_block , else_block , } , state }) } pub struct Parsed { pub condition : IfCondition , pub then_block : ThenBlock , pub else_block : Option < ElseBlock > , } } # [inline] pub (super) fn parse_IfStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IfStatement > { global . tracer . print_trace_start (& state , "IfStatement") ; let result = { let result = IfStatement_impl :: parse (state , global) ? . map (| r | super :: IfStatement { condition : r . condition , then_block : r . then_block , else_block : r . else_block , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod WhileStatement_impl { use super :: * ; mod part_2 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_4 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWWhile (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : condition , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_WhileCondition (state , & mut * global) }) ? ; let ParseOk { state , .. } = part_2 :: parse (state , global) ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; let ParseOk { state , .. } = part_4 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEndwhile (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : Parsed { condition , body , } , state }) } pub struct Parsed { pub condition : WhileCondition , pub body : Block , } } # [inline] pub (super) fn parse_WhileStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , WhileStatement > { global . tracer . print_trace_start (& state , "WhileStatement") ; let result = { let result = WhileStatement_impl :: parse (state , global) ? . map (| r | super :: WhileStatement { condition : r . condition , body : r . body , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod WhileCondition_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolDisjunction (state , global) }) } pub type Parsed = BoolDisjunction ; use super :: WhileCondition as Parsed__override ; } # [inline] pub (super) fn parse_WhileCondition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , WhileCondition > { global . tracer . print_trace_start (& state , "WhileCondition") ; let result = { let result = WhileCondition_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ExitWhileStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWExit (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWWhile (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_ExitWhileStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ExitWhileStatement > { global . tracer . print_trace_start (& state , "ExitWhileStatement") ; let result = { let result = ExitWhileStatement_impl :: parse (state , global) ? . map (| r | super :: ExitWhileStatement { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Assignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , value , } , state }) } pub struct Parsed { pub variable : NumericVariable , pub value : Expression , } } # [inline] pub (super) fn parse_Assignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Assignment > { global . tracer . print_trace_start (& state , "Assignment") ; let result = { let result = Assignment_impl :: parse (state , global) ? . map (| r | super :: Assignment { variable : r . variable , value : r . value , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod StringAssignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , value , } , state }) } pub struct Parsed { pub variable : StringVariable , pub value : StringLiteral , } } # [inline] pub (super) fn parse_StringAssignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringAssignment > { global . tracer . print_trace_start (& state , "StringAssignment") ; let result = { let result = StringAssignment_impl :: parse (state , global) ? . map (| r | super :: StringAssignment { variable : r . variable , value : r . value , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod PrintStatement_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut list : Vec < PrintListItem > = Vec :: new () ; loop { match parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PrintListItem (state , & mut * global) }) . map_inner (| result | vec ! [result]) { Ok (ParseOk { result : __result , state : new_state , .. }) => { list . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : list , state }) } pub type Parsed = Vec < PrintListItem > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWPrint (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : mut list , state } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : list , state }) } pub type Parsed = Vec < PrintListItem > ; } # [inline] pub (super) fn parse_PrintStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintStatement > { global . tracer . print_trace_start (& state , "PrintStatement") ; let result = { let result = PrintStatement_impl :: parse (state , global) ? . map (| r | super :: PrintStatement { list : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod PrintSkipNl_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ';') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_PrintSkipNl < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintSkipNl > { global . tracer . print_trace_start (& state , "PrintSkipNl") ; let result = { let result = PrintSkipNl_impl :: parse (state , global) ? . map (| r | super :: PrintSkipNl { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod PrintListItem_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : value , state } = ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) . map_inner (Parsed_value :: StringVariable)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Parsed_value :: Expression)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) . map_inner (Parsed_value :: StringLiteral)) . end () ? ; let ParseOk { result : sep , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_PrintListSep (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : Parsed { value , sep , } , state }) } pub struct Parsed { pub value : Parsed_value , pub sep : Option < PrintListSep > , } use super :: PrintListItem_value as Parsed_value ; } # [inline] pub (super) fn parse_PrintListItem < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintListItem > { global . tracer . print_trace_start (& state , "PrintListItem") ; let result = { let result = PrintListItem_impl :: parse (state , global) ? . map (| r | super :: PrintListItem { value : r . value , sep : r . sep , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod PrintListSep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ';') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_PrintListSep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PrintListSep > { global . tracer . print_trace_start (& state , "PrintListSep") ; let result = { let result = PrintListSep_impl :: parse (state , global) ? . map (| r | super :: PrintListSep { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod InputStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWInput (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : prompt , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_InputPrompt (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; let ParseOk { result : variable , state } = ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) . map_inner (Parsed_variable :: StringVariable)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) . map_inner (Parsed_variable :: NumericVariable)) . end () ? ; Ok (ParseOk { result : Parsed { prompt , variable , } , state }) } pub struct Parsed { pub prompt : Option < InputPrompt > , pub variable : Parsed_variable , } use super :: InputStatement_variable as Parsed_variable ; } # [inline] pub (super) fn parse_InputStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , InputStatement > { global . tracer . print_trace_start (& state , "InputStatement") ; let result = { let result = InputStatement_impl :: parse (state , global) ? . map (| r | super :: InputStatement { prompt : r . prompt , variable : r . variable , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod InputPrompt_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : text , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) ? ; let ParseOk { result : sep , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_InputPromptSep (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : Parsed { text , sep , } , state }) } pub struct Parsed { pub text : StringLiteral , pub sep : Option < InputPromptSep > , } } # [inline] pub (super) fn parse_InputPrompt < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , InputPrompt > { global . tracer . print_trace_start (& state , "InputPrompt") ; let result = { let result = InputPrompt_impl :: parse (state , global) ? . map (| r | super :: InputPrompt { text : r . text , sep : r . sep , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod InputPromptSep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_InputAskSep (state , global) }) . map_inner (Parsed__override :: InputAskSep)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_InputNoAskSep (state , global) }) . map_inner (Parsed__override :: InputNoAskSep)) . end () } pub type Parsed = Parsed__override ; use super :: InputPromptSep as Parsed__override ; } # [inline] pub (super) fn parse_InputPromptSep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , InputPromptSep > { global . tracer . print_trace_start (& state , "InputPromptSep") ; let result = { let result = InputPromptSep_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod InputAskSep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_InputAskSep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , InputAskSep > { global . tracer . print_trace_start (& state , "InputAskSep") ; let result = { let result = InputAskSep_impl :: parse (state , global) ? . map (| r | super :: InputAskSep { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod InputNoAskSep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ';') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_InputNoAskSep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , InputNoAskSep > { global . tracer . print_trace_start (& state , "InputNoAskSep") ; let result = { let result = InputNoAskSep_impl :: parse (state , global) ? . map (| r | super :: InputNoAskSep { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ForAssignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , value , } , state }) } pub struct Parsed { pub variable : NumericVariable , pub value : Expression , } } # [inline] pub (super) fn parse_ForAssignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForAssignment > { global . tracer . print_trace_start (& state , "ForAssignment") ; let result = { let result = ForAssignment_impl :: parse (state , global) ? . map (| r | super :: ForAssignment { variable : r . variable , value : r . value , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ForStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWFor (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : assignment , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForAssignment (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWTo (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : target , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { result : step , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForStep (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; let ParseOk { result : next , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ForNext (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { assignment , target , step , body , next , } , state }) } pub struct Parsed { pub assignment : ForAssignment , pub target : Expression , pub step : Option < ForStep > , pub body : Block , pub next : ForNext , } } # [inline] pub (super) fn parse_ForStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForStatement > { global . tracer . print_trace_start (& state , "ForStatement") ; let result = { let result = ForStatement_impl :: parse (state , global) ? . map (| r | super :: ForStatement { assignment : r . assignment , target : r . target , step : r . step , body : r . body , next : r . next , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ForStep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWStep (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : value , state }) } pub type Parsed = Expression ; } # [inline] pub (super) fn parse_ForStep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForStep > { global . tracer . print_trace_start (& state , "ForStep") ; let result = { let result = ForStep_impl :: parse (state , global) ? . map (| r | super :: ForStep { value : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ForNext_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWNext (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : variable , state } = parse_Whitespace (state . clone () , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) . map_inner (Some) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : variable , state }) } pub type Parsed = Option < NumericVariable > ; } # [inline] pub (super) fn parse_ForNext < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ForNext > { global . tracer . print_trace_start (& state , "ForNext") ; let result = { let result = ForNext_impl :: parse (state . clone () , global) ? . map_with_state (| r , new_state | super :: ForNext { variable : r , position : state . range_until (new_state) , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } impl PegPosition for ForNext { fn position (& self) -> & std :: ops :: Range < usize > { & self . position } } mod ExitForStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWExit (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWFor (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_ExitForStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ExitForStatement > { global . tracer . print_trace_start (& state , "ExitForStatement") ; let result = { let result = ExitForStatement_impl :: parse (state , global) ? . map (| r | super :: ExitForStatement { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ProcDefinition_impl { use super :: * ; mod part_3 { use super :: * ; mod optional { use super :: * ; mod part_2 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : mut parameters , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Parameter (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : parameters , state }) } pub type Parsed = Vec < Parameter > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut parameters : Vec < Parameter > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { parameters . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : parameters , state }) } pub type Parsed = Vec < Parameter > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : mut parameters , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Parameter (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_parameters_with , state } = part_2 :: parse (state , global) ? ; parameters . extend (extend_parameters_with) ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : parameters , state }) } pub type Parsed = Vec < Parameter > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) } pub type Parsed = Vec < Parameter > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWDef (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWProc (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) ? ; let ParseOk { result : mut parameters , state } = part_3 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : body , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Block (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { name , parameters , body , } , state }) } pub struct Parsed { pub name : VariableName , pub parameters : Vec < Parameter > , pub body : Block , } } # [inline] pub (super) fn parse_ProcDefinition < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ProcDefinition > { global . tracer . print_trace_start (& state , "ProcDefinition") ; let result = { let result = ProcDefinition_impl :: parse (state . clone () , global) ? . map_with_state (| r , new_state | super :: ProcDefinition { name : r . name , parameters : r . parameters , body : r . body , position : state . range_until (new_state) , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } impl PegPosition for ProcDefinition { fn position (& self) -> & std :: ops :: Range < usize > { & self . position } } mod Parameter_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , global) }) . map_inner (Parsed__override :: StringVariable)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , global) }) . map_inner (Parsed__override :: NumericVariable)) . end () } pub type Parsed = Parsed__override ; use super :: Parameter as Parsed__override ; } # [inline] pub (super) fn parse_Parameter < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parameter > { global . tracer . print_trace_start (& state , "Parameter") ; let result = { let result = Parameter_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ProcCall_impl { use super :: * ; mod part_2 { use super :: * ; mod optional { use super :: * ; mod part_2 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : mut arguments , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : arguments , state }) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut arguments : Vec < BoolOperand > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { arguments . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : arguments , state }) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : mut arguments , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_arguments_with , state } = part_2 :: parse (state , global) ? ; arguments . extend (extend_arguments_with) ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : arguments , state }) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWProc (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) ? ; let ParseOk { result : mut arguments , state } = part_2 :: parse (state , global) ? ; Ok (ParseOk { result : Parsed { name , arguments , } , state }) } pub struct Parsed { pub name : VariableName , pub arguments : Vec < BoolOperand > , } } # [inline] pub (super) fn parse_ProcCall < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ProcCall > { global . tracer . print_trace_start (& state , "ProcCall") ; let result = { let result = ProcCall_impl :: parse (state , global) ? . map (| r | super :: ProcCall { name : r . name , arguments : r . arguments , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod EndprocStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEndproc (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_EndprocStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , EndprocStatement > { global . tracer . print_trace_start (& state , "EndprocStatement") ; let result = { let result = EndprocStatement_impl :: parse (state , global) ? . map (| r | super :: EndprocStatement { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod FnCall_impl { use super :: * ; mod part_2 { use super :: * ; mod optional { use super :: * ; mod part_2 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : mut arguments , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : arguments , state }) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut arguments : Vec < BoolOperand > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { arguments . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : arguments , state }) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : mut arguments , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_arguments_with , state } = part_2 :: parse (state , global) ? ; arguments . extend (extend_arguments_with) ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : arguments , state }) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) } pub type Parsed = Vec < BoolOperand > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWFn (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) ? ; let ParseOk { result : mut arguments , state } = part_2 :: parse (state , global) ? ; Ok (ParseOk { result : Parsed { name , arguments , } , state }) } pub struct Parsed { pub name : VariableName , pub arguments : Vec < BoolOperand > , } } # [inline] pub (super) fn parse_FnCall < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FnCall > { global . tracer . print_trace_start (& state , "FnCall") ; let result = { let result = FnCall_impl :: parse (state , global) ? . map (| r | super :: FnCall { name : r . name , arguments : r . arguments , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod DimStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWDim (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : variable , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) ? ; let ParseOk { result : size , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { variable , size , } , state }) } pub struct Parsed { pub variable : NumericVariable , pub size : Expression , } } # [inline] pub (super) fn parse_DimStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , DimStatement > { global . tracer . print_trace_start (& state , "DimStatement") ; let result = { let result = DimStatement_impl :: parse (state , global) ? . map (| r | super :: DimStatement { variable : r . variable , size : r . size , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IndirectAssignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : target , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Indirection (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_BoolOperand (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { target , value , } , state }) } pub struct Parsed { pub target : Indirection , pub value : BoolOperand , } } # [inline] pub (super) fn parse_IndirectAssignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IndirectAssignment > { global . tracer . print_trace_start (& state , "IndirectAssignment") ; let result = { let result = IndirectAssignment_impl :: parse (state , global) ? . map (| r | super :: IndirectAssignment { target : r . target , value : r . value , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Indirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_DyadicIndirection (state , global) }) . map_inner (Parsed__override :: DyadicIndirection)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_UnaryIndirection (state , global) }) . map_inner (Parsed__override :: UnaryIndirection)) . end () } pub type Parsed = Parsed__override ; use super :: Indirection as Parsed__override ; } # [inline] pub (super) fn parse_Indirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Indirection > { global . tracer . print_trace_start (& state , "Indirection") ; let result = { let result = Indirection_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod UnaryIndirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : op , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IndirectionOperator (state , & mut * global) }) ? ; let ParseOk { result : address , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) . map_inner (Box :: new) ? ; Ok (ParseOk { result : Parsed { op , address , } , state }) } pub struct Parsed { pub op : IndirectionOperator , pub address : Box < Factor > , } } # [inline] pub (super) fn parse_UnaryIndirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , UnaryIndirection > { global . tracer . print_trace_start (& state , "UnaryIndirection") ; let result = { let result = UnaryIndirection_impl :: parse (state , global) ? . map (| r | super :: UnaryIndirection { op : r . op , address : r . address , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod DyadicIndirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : base , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NumericVariable (state , & mut * global) }) ? ; let ParseOk { result : op , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_DyadicOperator (state , & mut * global) }) ? ; let ParseOk { result : offset , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) . map_inner (Box :: new) ? ; Ok (ParseOk { result : Parsed { base , op , offset , } , state }) } pub struct Parsed { pub base : NumericVariable , pub op : DyadicOperator , pub offset : Box < Factor > , } } # [inline] pub (super) fn parse_DyadicIndirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , DyadicIndirection > { global . tracer . print_trace_start (& state , "DyadicIndirection") ; let result = { let result = DyadicIndirection_impl :: parse (state , global) ? . map (| r | super :: DyadicIndirection { base : r . base , op : r . op , offset : r . offset , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IndirectionOperator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ByteIndirection (state , global) }) . map_inner (Parsed__override :: ByteIndirection)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_WordIndirection (state , global) }) . map_inner (Parsed__override :: WordIndirection)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatIndirection (state , global) }) . map_inner (Parsed__override :: FloatIndirection)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringIndirection (state , global) }) . map_inner (Parsed__override :: StringIndirection)) . end () } pub type Parsed = Parsed__override ; use super :: IndirectionOperator as Parsed__override ; } # [inline] pub (super) fn parse_IndirectionOperator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IndirectionOperator > { global . tracer . print_trace_start (& state , "IndirectionOperator") ; let result = { let result = IndirectionOperator_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod DyadicOperator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ByteIndirection (state , global) }) . map_inner (Parsed__override :: ByteIndirection)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_WordIndirection (state , global) }) . map_inner (Parsed__override :: WordIndirection)) . end () } pub type Parsed = Parsed__override ; use super :: DyadicOperator as Parsed__override ; } # [inline] pub (super) fn parse_DyadicOperator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , DyadicOperator > { global . tracer . print_trace_start (& state , "DyadicOperator") ; let result = { let result = DyadicOperator_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ByteIndirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '?') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ByteIndirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ByteIndirection > { global . tracer . print_trace_start (& state , "ByteIndirection") ; let result = { let result = ByteIndirection_impl :: parse (state , global) ? . map (| r | super :: ByteIndirection { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod WordIndirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '!') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_WordIndirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , WordIndirection > { global . tracer . print_trace_start (& state , "WordIndirection") ; let result = { let result = WordIndirection_impl :: parse (state , global) ? . map (| r | super :: WordIndirection { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod FloatIndirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '|') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_FloatIndirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatIndirection > { global . tracer . print_trace_start (& state , "FloatIndirection") ; let result = { let result = FloatIndirection_impl :: parse (state , global) ? . map (| r | super :: FloatIndirection { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod StringIndirection_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '$') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_StringIndirection < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringIndirection > { global . tracer . print_trace_start (& state , "StringIndirection") ; let result = { let result = StringIndirection_impl :: parse (state , global) ? . map (| r | super :: StringIndirection { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TimeFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWTime (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_TimeFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TimeFunction > { global . tracer . print_trace_start (& state , "TimeFunction") ; let result = { let result = TimeFunction_impl :: parse (state , global) ? . map (| r | super :: TimeFunction { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod RndFunction_impl { use super :: * ; mod part_1 { use super :: * ; mod optional { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : argument , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (| result | Some (Box :: new (result))) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : argument , state }) } pub type Parsed = Option < Box < Expression >> ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) } pub type Parsed = Option < Box < Expression >> ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWRnd (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : argument , state } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : argument , state }) } pub type Parsed = Option < Box < Expression >> ; } # [inline] pub (super) fn parse_RndFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , RndFunction > { global . tracer . print_trace_start (& state , "RndFunction") ; let result = { let result = RndFunction_impl :: parse (state , global) ? . map (| r | super :: RndFunction { argument : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod GetFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWGet (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_GetFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , GetFunction > { global . tracer . print_trace_start (& state , "GetFunction") ; let result = { let result = GetFunction_impl :: parse (state , global) ? . map (| r | super :: GetFunction { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod InkeyFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWInkey (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '(') }) . discard_result () ? ; let ParseOk { result : argument , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (Box :: new) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ')') }) . discard_result () ? ; Ok (ParseOk { result : argument , state }) } pub type Parsed = Box < Expression > ; } # [inline] pub (super) fn parse_InkeyFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , InkeyFunction > { global . tracer . print_trace_start (& state , "InkeyFunction") ; let result = { let result = InkeyFunction_impl :: parse (state , global) ? . map (| r | super :: InkeyFunction { argument : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod OpeninFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWOpenin (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : name , state } = ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) . map_inner (Parsed_name :: StringLiteral)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) . map_inner (Parsed_name :: StringVariable)) . end () ? ; Ok (ParseOk { result : name , state }) } pub type Parsed = Parsed_name ; use super :: OpeninFunction_name as Parsed_name ; } # [inline] pub (super) fn parse_OpeninFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , OpeninFunction > { global . tracer . print_trace_start (& state , "OpeninFunction") ; let result = { let result = OpeninFunction_impl :: parse (state , global) ? . map (| r | super :: OpeninFunction { name : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod OpenoutFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWOpenout (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : name , state } = ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringLiteral (state , & mut * global) }) . map_inner (Parsed_name :: StringLiteral)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringVariable (state , & mut * global) }) . map_inner (Parsed_name :: StringVariable)) . end () ? ; Ok (ParseOk { result : name , state }) } pub type Parsed = Parsed_name ; use super :: OpenoutFunction_name as Parsed_name ; } # [inline] pub (super) fn parse_OpenoutFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , OpenoutFunction > { global . tracer . print_trace_start (& state , "OpenoutFunction") ; let result = { let result = OpenoutFunction_impl :: parse (state , global) ? . map (| r | super :: OpenoutFunction { name : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BgetFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWBget (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '#') }) . discard_result () ? ; let ParseOk { result : channel , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) . map_inner (Box :: new) ? ; Ok (ParseOk { result : channel , state }) } pub type Parsed = Box < Factor > ; } # [inline] pub (super) fn parse_BgetFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BgetFunction > { global . tracer . print_trace_start (& state , "BgetFunction") ; let result = { let result = BgetFunction_impl :: parse (state , global) ? . map (| r | super :: BgetFunction { channel : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod EofFunction_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEof (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '#') }) . discard_result () ? ; let ParseOk { result : channel , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) . map_inner (Box :: new) ? ; Ok (ParseOk { result : channel , state }) } pub type Parsed = Box < Factor > ; } # [inline] pub (super) fn parse_EofFunction < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , EofFunction > { global . tracer . print_trace_start (& state , "EofFunction") ; let result = { let result = EofFunction_impl :: parse (state , global) ? . map (| r | super :: EofFunction { channel : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TimeAssignment_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWTime (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '=') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : value , state }) } pub type Parsed = Expression ; } # [inline] pub (super) fn parse_TimeAssignment < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TimeAssignment > { global . tracer . print_trace_start (& state , "TimeAssignment") ; let result = { let result = TimeAssignment_impl :: parse (state , global) ? . map (| r | super :: TimeAssignment { value : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ClsStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWCls (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ClsStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ClsStatement > { global . tracer . print_trace_start (& state , "ClsStatement") ; let result = { let result = ClsStatement_impl :: parse (state , global) ? . map (| r | super :: ClsStatement { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod VduStatement_impl { use super :: * ; mod part_2 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : mut values , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; Ok (ParseOk { result : values , state }) } pub type Parsed = Vec < Expression > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; let mut values : Vec < Expression > = Vec :: new () ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { values . extend (__result) ; state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : values , state }) } pub type Parsed = Vec < Expression > ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWVdu (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : mut values , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) . map_inner (| result | vec ! [result]) ? ; let ParseOk { result : extend_values_with , state } = part_2 :: parse (state , global) ? ; values . extend (extend_values_with) ; Ok (ParseOk { result : values , state }) } pub type Parsed = Vec < Expression > ; } # [inline] pub (super) fn parse_VduStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , VduStatement > { global . tracer . print_trace_start (& state , "VduStatement") ; let result = { let result = VduStatement_impl :: parse (state , global) ? . map (| r | super :: VduStatement { values : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod PlotStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWPlot (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : mode , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : x , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : y , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { mode , x , y , } , state }) } pub struct Parsed { pub mode : Expression , pub x : Expression , pub y : Expression , } } # [inline] pub (super) fn parse_PlotStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , PlotStatement > { global . tracer . print_trace_start (& state , "PlotStatement") ; let result = { let result = PlotStatement_impl :: parse (state , global) ? . map (| r | super :: PlotStatement { mode : r . mode , x : r . x , y : r . y , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod MoveStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWMove (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : x , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : y , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { x , y , } , state }) } pub struct Parsed { pub x : Expression , pub y : Expression , } } # [inline] pub (super) fn parse_MoveStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , MoveStatement > { global . tracer . print_trace_start (& state , "MoveStatement") ; let result = { let result = MoveStatement_impl :: parse (state , global) ? . map (| r | super :: MoveStatement { x : r . x , y : r . y , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod DrawStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWDraw (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : x , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : y , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { x , y , } , state }) } pub struct Parsed { pub x : Expression , pub y : Expression , } } # [inline] pub (super) fn parse_DrawStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , DrawStatement > { global . tracer . print_trace_start (& state , "DrawStatement") ; let result = { let result = DrawStatement_impl :: parse (state , global) ? . map (| r | super :: DrawStatement { x : r . x , y : r . y , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod SoundStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWSound (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : channel , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : amplitude , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : pitch , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : duration , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { channel , amplitude , pitch , duration , } , state }) } pub struct Parsed { pub channel : Expression , pub amplitude : Expression , pub pitch : Expression , pub duration : Expression , } } # [inline] pub (super) fn parse_SoundStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , SoundStatement > { global . tracer . print_trace_start (& state , "SoundStatement") ; let result = { let result = SoundStatement_impl :: parse (state , global) ? . map (| r | super :: SoundStatement { channel : r . channel , amplitude : r . amplitude , pitch : r . pitch , duration : r . duration , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod BputStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWBput (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '#') }) . discard_result () ? ; let ParseOk { result : channel , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , ',') }) . discard_result () ? ; let ParseOk { result : value , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , & mut * global) }) ? ; Ok (ParseOk { result : Parsed { channel , value , } , state }) } pub struct Parsed { pub channel : Factor , pub value : Expression , } } # [inline] pub (super) fn parse_BputStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , BputStatement > { global . tracer . print_trace_start (& state , "BputStatement") ; let result = { let result = BputStatement_impl :: parse (state , global) ? . map (| r | super :: BputStatement { channel : r . channel , value : r . value , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod CloseStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWClose (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '#') }) . discard_result () ? ; let ParseOk { result : channel , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Factor (state , & mut * global) }) ? ; Ok (ParseOk { result : channel , state }) } pub type Parsed = Factor ; } # [inline] pub (super) fn parse_CloseStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , CloseStatement > { global . tracer . print_trace_start (& state , "CloseStatement") ; let result = { let result = CloseStatement_impl :: parse (state , global) ? . map (| r | super :: CloseStatement { channel : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TraceStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWTrace (state , & mut * global) }) . discard_result () ? ; let ParseOk { result : mode , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TraceMode (state , & mut * global) }) ? ; Ok (ParseOk { result : mode , state }) } pub type Parsed = TraceMode ; } # [inline] pub (super) fn parse_TraceStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TraceStatement > { global . tracer . print_trace_start (& state , "TraceStatement") ; let result = { let result = TraceStatement_impl :: parse (state , global) ? . map (| r | super :: TraceStatement { mode : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TraceMode_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TraceOn (state , global) }) . map_inner (Parsed__override :: TraceOn)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TraceOff (state , global) }) . map_inner (Parsed__override :: TraceOff)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_TraceProc (state , global) }) . map_inner (Parsed__override :: TraceProc)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_Expression (state , global) }) . map_inner (Parsed__override :: Expression)) . end () } pub type Parsed = Parsed__override ; use super :: TraceMode as Parsed__override ; } # [inline] pub (super) fn parse_TraceMode < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TraceMode > { global . tracer . print_trace_start (& state , "TraceMode") ; let result = { let result = TraceMode_impl :: parse (state , global) ? ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TraceOn_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWOn (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_TraceOn < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TraceOn > { global . tracer . print_trace_start (& state , "TraceOn") ; let result = { let result = TraceOn_impl :: parse (state , global) ? . map (| r | super :: TraceOn { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TraceOff_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWOff (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_TraceOff < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TraceOff > { global . tracer . print_trace_start (& state , "TraceOff") ; let result = { let result = TraceOff_impl :: parse (state , global) ? . map (| r | super :: TraceOff { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod TraceProc_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWProc (state , & mut * global) }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_TraceProc < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , TraceProc > { global . tracer . print_trace_start (& state , "TraceProc") ; let result = { let result = TraceProc_impl :: parse (state , global) ? . map (| r | super :: TraceProc { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod EndStatement_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_KWEnd (state , & mut * global) }) . discard_result () ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_NewLine (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_EndStatement < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , EndStatement > { global . tracer . print_trace_start (& state , "EndStatement") ; let result = { let result = EndStatement_impl :: parse (state , global) ? . map (| r | super :: EndStatement { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWPrint_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "PRINT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWPrint < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWPrint > { global . tracer . print_trace_start (& state , "KWPrint") ; let result = { let result = KWPrint_impl :: parse (state , global) ? . map (| r | super :: KWPrint { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWInput_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "INPUT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWInput < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWInput > { global . tracer . print_trace_start (& state , "KWInput") ; let result = { let result = KWInput_impl :: parse (state , global) ? . map (| r | super :: KWInput { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWFor_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "FOR") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWFor < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWFor > { global . tracer . print_trace_start (& state , "KWFor") ; let result = { let result = KWFor_impl :: parse (state , global) ? . map (| r | super :: KWFor { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWTo_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "TO") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWTo < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWTo > { global . tracer . print_trace_start (& state , "KWTo") ; let result = { let result = KWTo_impl :: parse (state , global) ? . map (| r | super :: KWTo { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWStep_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "STEP") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWStep < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWStep > { global . tracer . print_trace_start (& state , "KWStep") ; let result = { let result = KWStep_impl :: parse (state , global) ? . map (| r | super :: KWStep { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWNext_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "NEXT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWNext < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWNext > { global . tracer . print_trace_start (& state , "KWNext") ; let result = { let result = KWNext_impl :: parse (state , global) ? . map (| r | super :: KWNext { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWEnd_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "END") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEnd < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEnd > { global . tracer . print_trace_start (& state , "KWEnd") ; let result = { let result = KWEnd_impl :: parse (state , global) ? . map (| r | super :: KWEnd { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWIf_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "IF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWIf < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWIf > { global . tracer . print_trace_start (& state , "KWIf") ; let result = { let result = KWIf_impl :: parse (state , global) ? . map (| r | super :: KWIf { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWThen_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "THEN") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWThen < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWThen > { global . tracer . print_trace_start (& state , "KWThen") ; let result = { let result = KWThen_impl :: parse (state , global) ? . map (| r | super :: KWThen { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWElse_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ELSE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWElse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWElse > { global . tracer . print_trace_start (& state , "KWElse") ; let result = { let result = KWElse_impl :: parse (state , global) ? . map (| r | super :: KWElse { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWEndif_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ENDIF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEndif < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEndif > { global . tracer . print_trace_start (& state , "KWEndif") ; let result = { let result = KWEndif_impl :: parse (state , global) ? . map (| r | super :: KWEndif { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWExit_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "EXIT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWExit < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWExit > { global . tracer . print_trace_start (& state , "KWExit") ; let result = { let result = KWExit_impl :: parse (state , global) ? . map (| r | super :: KWExit { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWWhile_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "WHILE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWWhile < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWWhile > { global . tracer . print_trace_start (& state , "KWWhile") ; let result = { let result = KWWhile_impl :: parse (state , global) ? . map (| r | super :: KWWhile { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWEndwhile_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ENDWHILE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEndwhile < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEndwhile > { global . tracer . print_trace_start (& state , "KWEndwhile") ; let result = { let result = KWEndwhile_impl :: parse (state , global) ? . map (| r | super :: KWEndwhile { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWTime_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "TIME") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWTime < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWTime > { global . tracer . print_trace_start (& state , "KWTime") ; let result = { let result = KWTime_impl :: parse (state , global) ? . map (| r | super :: KWTime { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWRnd_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "RND") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWRnd < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWRnd > { global . tracer . print_trace_start (& state , "KWRnd") ; let result = { let result = KWRnd_impl :: parse (state , global) ? . map (| r | super :: KWRnd { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWGet_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "GET") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWGet < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWGet > { global . tracer . print_trace_start (& state , "KWGet") ; let result = { let result = KWGet_impl :: parse (state , global) ? . map (| r | super :: KWGet { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWInkey_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "INKEY") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWInkey < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWInkey > { global . tracer . print_trace_start (& state , "KWInkey") ; let result = { let result = KWInkey_impl :: parse (state , global) ? . map (| r | super :: KWInkey { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWOpenin_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "OPENIN") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWOpenin < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWOpenin > { global . tracer . print_trace_start (& state , "KWOpenin") ; let result = { let result = KWOpenin_impl :: parse (state , global) ? . map (| r | super :: KWOpenin { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWOpenout_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "OPENOUT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWOpenout < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWOpenout > { global . tracer . print_trace_start (& state , "KWOpenout") ; let result = { let result = KWOpenout_impl :: parse (state , global) ? . map (| r | super :: KWOpenout { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWBget_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "BGET") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWBget < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWBget > { global . tracer . print_trace_start (& state , "KWBget") ; let result = { let result = KWBget_impl :: parse (state , global) ? . map (| r | super :: KWBget { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWBput_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "BPUT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWBput < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWBput > { global . tracer . print_trace_start (& state , "KWBput") ; let result = { let result = KWBput_impl :: parse (state , global) ? . map (| r | super :: KWBput { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWEof_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "EOF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEof < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEof > { global . tracer . print_trace_start (& state , "KWEof") ; let result = { let result = KWEof_impl :: parse (state , global) ? . map (| r | super :: KWEof { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWClose_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "CLOSE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWClose < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWClose > { global . tracer . print_trace_start (& state , "KWClose") ; let result = { let result = KWClose_impl :: parse (state , global) ? . map (| r | super :: KWClose { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWCls_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "CLS") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWCls < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWCls > { global . tracer . print_trace_start (& state , "KWCls") ; let result = { let result = KWCls_impl :: parse (state , global) ? . map (| r | super :: KWCls { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWVdu_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "VDU") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWVdu < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWVdu > { global . tracer . print_trace_start (& state , "KWVdu") ; let result = { let result = KWVdu_impl :: parse (state , global) ? . map (| r | super :: KWVdu { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWPlot_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "PLOT") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWPlot < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWPlot > { global . tracer . print_trace_start (& state , "KWPlot") ; let result = { let result = KWPlot_impl :: parse (state , global) ? . map (| r | super :: KWPlot { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWMove_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "MOVE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWMove < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWMove > { global . tracer . print_trace_start (& state , "KWMove") ; let result = { let result = KWMove_impl :: parse (state , global) ? . map (| r | super :: KWMove { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWDraw_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "DRAW") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWDraw < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWDraw > { global . tracer . print_trace_start (& state , "KWDraw") ; let result = { let result = KWDraw_impl :: parse (state , global) ? . map (| r | super :: KWDraw { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWSound_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "SOUND") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWSound < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWSound > { global . tracer . print_trace_start (& state , "KWSound") ; let result = { let result = KWSound_impl :: parse (state , global) ? . map (| r | super :: KWSound { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWDef_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "DEF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWDef < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWDef > { global . tracer . print_trace_start (& state , "KWDef") ; let result = { let result = KWDef_impl :: parse (state , global) ? . map (| r | super :: KWDef { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWDim_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "DIM") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWDim < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWDim > { global . tracer . print_trace_start (& state , "KWDim") ; let result = { let result = KWDim_impl :: parse (state , global) ? . map (| r | super :: KWDim { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWProc_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "PROC") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWProc < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWProc > { global . tracer . print_trace_start (& state , "KWProc") ; let result = { let result = KWProc_impl :: parse (state , global) ? . map (| r | super :: KWProc { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWEndproc_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ENDPROC") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWEndproc < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWEndproc > { global . tracer . print_trace_start (& state , "KWEndproc") ; let result = { let result = KWEndproc_impl :: parse (state , global) ? . map (| r | super :: KWEndproc { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWFn_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "FN") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWFn < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWFn > { global . tracer . print_trace_start (& state , "KWFn") ; let result = { let result = KWFn_impl :: parse (state , global) ? . map (| r | super :: KWFn { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWTrace_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "TRACE") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWTrace < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWTrace > { global . tracer . print_trace_start (& state , "KWTrace") ; let result = { let result = KWTrace_impl :: parse (state , global) ? . map (| r | super :: KWTrace { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWOn_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "ON") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWOn < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWOn > { global . tracer . print_trace_start (& state , "KWOn") ; let result = { let result = KWOn_impl :: parse (state , global) ? . map (| r | super :: KWOn { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod KWOff_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_string_literal (state , "OFF") }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_KWOff < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , KWOff > { global . tracer . print_trace_start (& state , "KWOff") ; let result = { let result = KWOff_impl :: parse (state , global) ? . map (| r | super :: KWOff { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod NumericVariable_impl { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ResidentName (state , & mut * global) }) . map_inner (Parsed_name :: ResidentName) ? ; let ParseOk { result : type_dem , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: IntegerDenominator (result))) ? ; Ok (ParseOk { result : Parsed { name , type_dem , } , state }) } pub struct Parsed { pub name : Parsed_name , pub type_dem : Option < Parsed_type_dem > , } } mod choice_1 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) . map_inner (Parsed_name :: VariableName) ? ; let ParseOk { result : type_dem , state } = ChoiceHelper :: new (state . clone ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: FloatDenominator (result)))) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: IntegerDenominator (result)))) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_ByteDenominator (state , & mut * global) }) . map_inner (| result | Some (Parsed_type_dem :: ByteDenominator (result)))) . end () . or_else (| err | Ok (ParseOk { result : Default :: default () , state : state . record_error (err) , })) ? ; Ok (ParseOk { result : Parsed { name , type_dem , } , state }) } pub struct Parsed { pub name : Parsed_name , pub type_dem : Option < Parsed_type_dem > , } } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global) . map_inner (| r | Parsed { name : r . name , type_dem : r . type_dem , })) . choice (| state | choice_1 :: parse (state , global) . map_inner (| r | Parsed { name : r . name , type_dem : r . type_dem , })) . end () } pub struct Parsed { pub name : Parsed_name , pub type_dem : Option < Parsed_type_dem > , } use super :: NumericVariable_name as Parsed_name ; use super :: NumericVariable_type_dem as Parsed_type_dem ; } # [inline] pub (super) fn parse_NumericVariable < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NumericVariable > { global . tracer . print_trace_start (& state , "NumericVariable") ; let result = { let result = NumericVariable_impl :: parse (state , global) ? . map (| r | super :: NumericVariable { name : r . name , type_dem : r . type_dem , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod FloatDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '#') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_FloatDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatDenominator > { global . tracer . print_trace_start (& state , "FloatDenominator") ; let result = { let result = FloatDenominator_impl :: parse (state , global) ? . map (| r | super :: FloatDenominator { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IntegerDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '%') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_IntegerDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IntegerDenominator > { global . tracer . print_trace_start (& state , "IntegerDenominator") ; let result = { let result = IntegerDenominator_impl :: parse (state , global) ? . map (| r | super :: IntegerDenominator { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ByteDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '&') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ByteDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ByteDenominator > { global . tracer . print_trace_start (& state , "ByteDenominator") ; let result = { let result = ByteDenominator_impl :: parse (state , global) ? . map (| r | super :: ByteDenominator { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod StringDenominator_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '$') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_StringDenominator < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringDenominator > { global . tracer . print_trace_start (& state , "StringDenominator") ; let result = { let result = StringDenominator_impl :: parse (state , global) ? . map (| r | super :: StringDenominator { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod StringVariable_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { result : name , state } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_VariableName (state , & mut * global) }) ? ; let ParseOk { state , .. } = parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_StringDenominator (state , & mut * global) }) . discard_result () ? ; Ok (ParseOk { result : name , state }) } pub type Parsed = VariableName ; } # [inline] pub (super) fn parse_StringVariable < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringVariable > { global . tracer . print_trace_start (& state , "StringVariable") ; let result = { let result = StringVariable_impl :: parse (state , global) ? . map (| r | super :: StringVariable { name : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ResidentName_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '@') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ResidentName < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ResidentName > { global . tracer . print_trace_start (& state , "ResidentName") ; let result = { let result = ResidentName_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod VariableName_impl { use super :: * ; mod part_0 { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_character_range (state , 'a' , 'z') . discard_result ()) . choice (| state | parse_character_range (state , 'A' , 'Z') . discard_result ()) . end () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global)) . choice (| state | parse_character_literal (state , '_') . discard_result ()) . choice (| state | parse_character_literal (state , '`') . discard_result ()) . end () } pub type Parsed = () ; } mod part_1 { use super :: * ; mod closure { use super :: * ; mod choice_0 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_character_range (state , 'a' , 'z') . discard_result ()) . choice (| state | parse_character_range (state , 'A' , 'Z') . discard_result ()) . end () } pub type Parsed = () ; } mod choice_1 { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | choice_0 :: parse (state , global)) . choice (| state | choice_1 :: parse (state , global)) . choice (| state | parse_character_literal (state , '_') . discard_result ()) . end () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = part_0 :: parse (state , global) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_VariableName < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , VariableName > { global . tracer . print_trace_start (& state , "VariableName") ; let result = { let result = VariableName_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod NumberSign_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '+') }) . discard_result ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_literal (state , '-') }) . discard_result ()) . end () } pub type Parsed = () ; } # [inline] pub (super) fn parse_NumberSign < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NumberSign > { global . tracer . print_trace_start (& state , "NumberSign") ; let result = { let result = NumberSign_impl :: parse (state , global) ? . map (| r | super :: NumberSign { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod NumberLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatLiteral (state , & mut * global) }) . map_inner (Parsed_value :: FloatLiteral)) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerLiteral (state , & mut * global) }) . map_inner (Parsed_value :: IntegerLiteral)) . end () } pub type Parsed = Parsed_value ; use super :: NumberLiteral_value as Parsed_value ; } # [inline] pub (super) fn parse_NumberLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , NumberLiteral > { global . tracer . print_trace_start (& state , "NumberLiteral") ; let result = { let result = NumberLiteral_impl :: parse (state , global) ? . map (| r | super :: NumberLiteral { value : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IntegerLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_IntegerBody (state , & mut * global) }) } pub type Parsed = IntegerBody ; } # [inline] pub (super) fn parse_IntegerLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IntegerLiteral > { global . tracer . print_trace_start (& state , "IntegerLiteral") ; let result = { let result = IntegerLiteral_impl :: parse (state , global) ? . map (| r | super :: IntegerLiteral { body : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod IntegerBody_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_character_range (state . clone () , '0' , '9') . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_NumberSign (state . clone () , & mut * global) . discard_result () . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_IntegerBody < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , IntegerBody > { global . tracer . print_trace_start (& state , "IntegerBody") ; let result = { let result = IntegerBody_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod FloatLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_FloatBody (state , & mut * global) }) } pub type Parsed = FloatBody ; } # [inline] pub (super) fn parse_FloatLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatLiteral > { global . tracer . print_trace_start (& state , "FloatLiteral") ; let result = { let result = FloatLiteral_impl :: parse (state , global) ? . map (| r | super :: FloatLiteral { body : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod FloatBody_impl { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_3 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } mod part_4 { use super :: * ; mod optional { use super :: * ; mod part_1 { use super :: * ; mod closure { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_range (state , '0' , '9') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } if iterations == 0 { return Err (state . report_farthest_error ()) ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_character_literal (state , 'E') . discard_result () ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { optional :: parse (state . clone () , global) . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_NumberSign (state . clone () , & mut * global) . discard_result () . or_else (| err | Ok (ParseOk { result : () , state : state . record_error (err) , })) ? ; let ParseOk { state , .. } = part_1 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_character_literal (state , '.') . discard_result () ? ; let ParseOk { state , .. } = part_3 :: parse (state , global) ? ; let ParseOk { state , .. } = part_4 :: parse (state , global) ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_FloatBody < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , FloatBody > { global . tracer . print_trace_start (& state , "FloatBody") ; let result = { let result = FloatBody_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod StringLiteral_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = parse_character_literal (state , '"') . discard_result () ? ; let ParseOk { result : body , state } = parse_StringBody (state , & mut * global) ? ; let ParseOk { state , .. } = parse_character_literal (state , '"') . discard_result () ? ; Ok (ParseOk { result : body , state }) } pub type Parsed = StringBody ; } # [inline] pub (super) fn parse_StringLiteral < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringLiteral > { global . tracer . print_trace_start (& state , "StringLiteral") ; let result = { let result = StringLiteral_impl :: parse (state , global) ? . map (| r | super :: StringLiteral { body : r , }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod StringBody_impl { use super :: * ; mod closure { use super :: * ; mod part_0 { use super :: * ; mod negative_lookahead { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_character_literal (state , '"') . discard_result () } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { match negative_lookahead :: parse (state . clone () , global) { Ok (_) => Err (state . report_error (ParseErrorSpecifics :: NegativeLookaheadFailed)) , Err (_) => Ok (ParseOk { result : () , state }) , } } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let ParseOk { state , .. } = part_0 :: parse (state , global) ? ; let ParseOk { state , .. } = parse_char (state , & mut * global) . discard_result () ? ; Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match closure :: parse (state . clone () , global) { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_StringBody < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , StringBody > { global . tracer . print_trace_start (& state , "StringBody") ; let result = { let result = StringBody_impl :: parse (state . clone () , global) ? . map_with_state (| _ , new_state | { let string = state . slice_until (new_state) . to_string () ; string }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod ALPHA_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { ChoiceHelper :: new (state) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_range (state , 'a' , 'z') }) . discard_result ()) . choice (| state | parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_range (state , 'A' , 'Z') }) . discard_result ()) . end () } pub type Parsed = () ; } # [inline] pub (super) fn parse_ALPHA < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , ALPHA > { global . tracer . print_trace_start (& state , "ALPHA") ; let result = { let result = ALPHA_impl :: parse (state , global) ? . map (| r | super :: ALPHA { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod DIGIT_impl { use super :: * ; # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { parse_Whitespace (state , & mut * global) . and_then (| ParseOk { state , .. } | { parse_character_range (state , '0' , '9') }) . discard_result () } pub type Parsed = () ; } # [inline] pub (super) fn parse_DIGIT < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , DIGIT > { global . tracer . print_trace_start (& state , "DIGIT") ; let result = { let result = DIGIT_impl :: parse (state , global) ? . map (| r | super :: DIGIT { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } mod Whitespace_impl { use super :: * ; mod closure { use super :: * ; } # [inline (always)] pub fn parse < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Parsed > { let mut iterations : usize = 0 ; let mut state = state ; loop { match parse_character_literal (state . clone () , ' ') . discard_result () { Ok (ParseOk { result : __result , state : new_state , .. }) => { state = new_state ; } , Err (err) => { state = state . record_error (err) ; break ; } } iterations += 1 ; } Ok (ParseOk { result : () , state }) } pub type Parsed = () ; } # [inline] pub (super) fn parse_Whitespace < 'a , TT : ParseTracer > (state : ParseState < 'a > , global : & mut ParseGlobal < TT , ParseCache < 'a > , () > ,) -> ParseResult < 'a , Whitespace > { global . tracer . print_trace_start (& state , "Whitespace") ; let result = { let result = Whitespace_impl :: parse (state , global) ? . map (| r | super :: Whitespace { }) ; Ok (result) } ; global . tracer . print_trace_result (& result) ; result } }
@@ -0,0 +1,366 @@
+//! Turns programs back into source, with one layout for all: upper case
+//! keywords, single spaces around operators and the bodies of `FOR`,
+//! `WHILE` and `IF` indented by four spaces, much like `LISTO 7` on a BBC
+//! Micro. Parentheses are only written where they are needed, so the source
+//! parses to the same program again.
+
+use crate::ast::{Block, Comparison, Condition, Expression, Indirection, Located, NumberLiteral, Operand, Procedure, Program, Statement, StringValue, TraceMode, Variable};
+use crate::error::BasicError;
+use crate::listing::Listing;
+use crate::parser;
+use crate::scope::Symbols;
+use peginator::PegParser;
+
+const INDENT: &str = "    ";
+
+/// Formats `code`, keeping a blank line wherever it had blank lines between
+/// statements. Blank lines in front of closing keywords like `NEXT` end up
+/// behind them.
+///
+/// ```
+/// use bbbasic::pretty::format_source;
+///
+/// let code = "FOR i%=1 TO 3\nPRINT (i%*2)+1\nNEXT\n";
+/// assert_eq!(format_source(code).unwrap(), "FOR i% = 1 TO 3\n    PRINT i% * 2 + 1\nNEXT i%\n");
+/// ```
+pub fn format_source(code: &str) -> Result<String, BasicError> {
+    let parsed = parser::Program::parse(code).map_err(|e| BasicError::from_parse_error(code, &e))?;
+
+    let mut symbols = Symbols::new();
+    let program = Program::resolve_source(&parsed, code, &mut symbols);
+
+    Ok(pretty_print(&program, &symbols))
+}
+
+/// Source of `program`, its variables named after `symbols`. Statements
+/// with known positions are separated by a blank line where the source they
+/// were parsed from had one.
+pub fn pretty_print(program: &Program, symbols: &Symbols) -> String {
+    let mut printer = Printer { symbols, output: String::new(), blank_lines: true };
+    printer.program(program);
+
+    printer.output
+}
+
+impl Listing {
+    /// The listing with every line formatted like [`pretty_print`] does,
+    /// keeping the line numbers. Fails if the program doesn't parse.
+    pub fn pretty(&self) -> Result<Listing, BasicError> {
+        let code = self.source();
+        let parsed = parser::Program::parse(&code).map_err(|e| BasicError::from_parse_error(&code, &e))?;
+
+        let mut symbols = Symbols::new();
+        let program = Program::resolve(&parsed, &mut symbols);
+
+        let mut printer = Printer { symbols: &symbols, output: String::new(), blank_lines: false };
+        printer.program(&program);
+
+        // Every statement and every closing keyword has a line of its own,
+        // in the listing as well as in the formatted program.
+        let mut formatted = printer.output.lines();
+        let mut listing = Listing::new();
+        for (number, text) in self.lines() {
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(line) = formatted.next() {
+                listing.set_line(number, line);
+            }
+        }
+
+        Ok(listing)
+    }
+}
+
+struct Printer<'a> {
+    symbols: &'a Symbols,
+    output: String,
+    blank_lines: bool,
+}
+
+impl Printer<'_> {
+    fn program(&mut self, program: &Program) {
+        let mut last = self.block(&program.body, 0, 0);
+
+        for procedure in &program.procedures {
+            self.blank_line(last, procedure.position.line);
+            last = self.procedure(procedure);
+        }
+    }
+
+    /// Writes `DEF PROC` and the body, returning the last line of the body.
+    fn procedure(&mut self, procedure: &Procedure) -> usize {
+        let parameters: Vec<String> = procedure.parameters.iter().map(|p| self.variable(p)).collect();
+
+        if parameters.is_empty() {
+            self.line(0, &format!("DEF PROC{}", procedure.name));
+        } else {
+            self.line(0, &format!("DEF PROC{}({})", procedure.name, parameters.join(", ")));
+        }
+
+        self.block(&procedure.body, 0, procedure.position.line)
+    }
+
+    /// Writes the statements of `block`, which follows `last`, the line
+    /// the enclosing statement starts on. Returns the last line of the
+    /// block, including any closing keywords.
+    fn block(&mut self, block: &Block, depth: usize, mut last: usize) -> usize {
+        for (i, located) in block.iter().enumerate() {
+            if i > 0 {
+                self.blank_line(last, located.position.line);
+            }
+
+            last = self.statement(located, depth);
+        }
+
+        last
+    }
+
+    /// Separates statements with a blank line if the source had any
+    /// between `last` and `next`.
+    fn blank_line(&mut self, last: usize, next: usize) {
+        if self.blank_lines && last > 0 && next > last + 1 {
+            self.output.push('\n');
+        }
+    }
+
+    fn line(&mut self, depth: usize, text: &str) {
+        for _ in 0..depth {
+            self.output.push_str(INDENT);
+        }
+
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    /// Writes a statement, returning the line its source ends on.
+    fn statement(&mut self, located: &Located, depth: usize) -> usize {
+        let line = located.position.line;
+
+        let text = match &located.statement {
+            Statement::Assignment(v, e) => format!("{} = {}", self.variable(v), self.expression(e)),
+            Statement::StringAssignment(v, s) => format!("{} = \"{}\"", self.variable(v), s),
+
+            Statement::Print(items) => {
+                let mut text = "PRINT".to_string();
+                for item in items {
+                    text.push(' ');
+                    text.push_str(&self.operand(&item.value));
+                    if !item.newline {
+                        text.push(';');
+                    }
+                }
+                text
+            }
+
+            Statement::Input { prompt, variable } => {
+                let variable = self.variable(variable);
+
+                match prompt.strip_suffix('?') {
+                    Some("") => format!("INPUT {}", variable),
+                    Some(p) => format!("INPUT \"{}\", {}", p, variable),
+                    None => format!("INPUT \"{}\"; {}", prompt, variable)
+                }
+            }
+
+            Statement::For(f) => {
+                let mut text = format!("FOR {} = {} TO {}", self.variable(&f.variable), self.expression(&f.start), self.expression(&f.target));
+                if let Some(step) = &f.step {
+                    text.push_str(&format!(" STEP {}", self.expression(step)));
+                }
+                self.line(depth, &text);
+
+                let last = self.block(&f.body, depth + 1, line);
+                let next = format!("NEXT {}", self.variable(&f.variable));
+                self.line(depth, &next);
+
+                return closed(last);
+            }
+
+            Statement::While { condition, body } => {
+                let text = format!("WHILE {}", self.condition(condition));
+                self.line(depth, &text);
+
+                let last = self.block(body, depth + 1, line);
+                self.line(depth, "ENDWHILE");
+
+                return closed(last);
+            }
+
+            Statement::If { condition, then_block, else_block } => {
+                let text = format!("IF {} THEN", self.condition(condition));
+                self.line(depth, &text);
+
+                let mut last = self.block(then_block, depth + 1, line);
+                if let Some(else_block) = else_block {
+                    self.line(depth, "ELSE");
+                    last = self.block(else_block, depth + 1, closed(last));
+                }
+                self.line(depth, "ENDIF");
+
+                return closed(last);
+            }
+
+            Statement::ExitFor => "EXIT FOR".to_string(),
+            Statement::ExitWhile => "EXIT WHILE".to_string(),
+            Statement::End => "END".to_string(),
+            Statement::SetTime(e) => format!("TIME = {}", self.expression(e)),
+            Statement::Cls => "CLS".to_string(),
+            Statement::Vdu(values) => format!("VDU {}", self.expressions(values)),
+            Statement::Plot { mode, x, y } => format!("PLOT {}", self.expressions([mode, x, y])),
+            Statement::Move { x, y } => format!("MOVE {}", self.expressions([x, y])),
+            Statement::Draw { x, y } => format!("DRAW {}", self.expressions([x, y])),
+            Statement::Sound { channel, amplitude, pitch, duration } => format!("SOUND {}", self.expressions([channel, amplitude, pitch, duration])),
+            Statement::Dim { variable, size } => format!("DIM {} {}", self.variable(variable), self.expression(size)),
+            Statement::Poke { indirection, address, value } => format!("{} = {}", self.indirection(*indirection, address), self.operand(value)),
+            Statement::Bput { channel, value } => format!("BPUT #{}, {}", self.factor(channel), self.expression(value)),
+            Statement::Close(channel) => format!("CLOSE #{}", self.factor(channel)),
+
+            Statement::Call { name, arguments } if arguments.is_empty() => format!("PROC{}", name),
+            Statement::Call { name, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|a| self.operand(a)).collect();
+                format!("PROC{}({})", name, arguments.join(", "))
+            }
+            Statement::Return => "ENDPROC".to_string(),
+
+            Statement::Trace(mode) => match mode {
+                TraceMode::On => "TRACE ON".to_string(),
+                TraceMode::Off => "TRACE OFF".to_string(),
+                TraceMode::Procedures => "TRACE PROC".to_string(),
+                TraceMode::Below(e) => format!("TRACE {}", self.expression(e))
+            }
+        };
+
+        self.line(depth, &text);
+
+        line
+    }
+
+    fn variable(&self, variable: &Variable) -> String {
+        format!("{}{}", self.symbols.name(variable.slot), variable.data_type.suffix())
+    }
+
+    fn expressions<'e>(&self, expressions: impl IntoIterator<Item = &'e Expression>) -> String {
+        let expressions: Vec<String> = expressions.into_iter().map(|e| self.expression(e)).collect();
+        expressions.join(", ")
+    }
+
+    fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Add(l, r) => format!("{} + {}", self.expression(l), self.term(r)),
+            Expression::Sub(l, r) => format!("{} - {}", self.expression(l), self.term(r)),
+            e => self.term(e)
+        }
+    }
+
+    /// An operand of `*` and `/`, in parentheses if it is a sum.
+    fn term(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Mul(l, r) => format!("{} * {}", self.term(l), self.factor(r)),
+            Expression::Div(l, r) => format!("{} / {}", self.term(l), self.factor(r)),
+            e => self.factor(e)
+        }
+    }
+
+    /// A single value, anything else in parentheses.
+    fn factor(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Number(NumberLiteral::Float(n) | NumberLiteral::Integer(n)) => n.clone(),
+            Expression::Constant(v) => v.to_string(),
+            Expression::Variable(v) => self.variable(v),
+            Expression::Time => "TIME".to_string(),
+            Expression::Rnd(None) => "RND".to_string(),
+            Expression::Rnd(Some(e)) => format!("RND({})", self.expression(e)),
+            Expression::Get => "GET".to_string(),
+            Expression::Inkey(e) => format!("INKEY({})", self.expression(e)),
+            Expression::Openin(name) => format!("OPENIN {}", self.string(name)),
+            Expression::Openout(name) => format!("OPENOUT {}", self.string(name)),
+            Expression::Bget(channel) => format!("BGET #{}", self.factor(channel)),
+            Expression::Eof(channel) => format!("EOF #{}", self.factor(channel)),
+            Expression::Function { name, arguments } if arguments.is_empty() => format!("FN{}", name),
+            Expression::Function { name, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|a| self.operand(a)).collect();
+                format!("FN{}({})", name, arguments.join(", "))
+            }
+            Expression::Indirect(indirection, address) => self.indirection(*indirection, address),
+            e => format!("({})", self.expression(e))
+        }
+    }
+
+    /// `?address`, or `base?offset` for a variable plus an offset.
+    fn indirection(&self, indirection: Indirection, address: &Expression) -> String {
+        let operator = match indirection {
+            Indirection::Byte => "?",
+            Indirection::Word => "!",
+            Indirection::Float => "|",
+            Indirection::String => "$"
+        };
+
+        match (indirection, address) {
+            (Indirection::Byte | Indirection::Word, Expression::Add(base, offset)) if matches!(**base, Expression::Variable(_)) =>
+                format!("{}{}{}", self.factor(base), operator, self.factor(offset)),
+            _ => format!("{}{}", operator, self.factor(address))
+        }
+    }
+
+    fn string(&self, value: &StringValue) -> String {
+        match value {
+            StringValue::Literal(s) => format!("\"{}\"", s),
+            StringValue::Variable(v) => self.variable(v)
+        }
+    }
+
+    fn operand(&self, operand: &Operand) -> String {
+        match operand {
+            Operand::Number(e) => self.expression(e),
+            Operand::String(s) => self.string(s)
+        }
+    }
+
+    fn condition(&self, condition: &Condition) -> String {
+        match condition {
+            Condition::Or(conjunctions) => {
+                let conjunctions: Vec<String> = conjunctions.iter().map(|c| self.conjunction(c)).collect();
+                conjunctions.join(" OR ")
+            }
+            c => self.conjunction(c)
+        }
+    }
+
+    /// An operand of `OR`, in parentheses if it is one itself.
+    fn conjunction(&self, condition: &Condition) -> String {
+        match condition {
+            Condition::And(terms) => {
+                let terms: Vec<String> = terms.iter().map(|t| self.comparison(t)).collect();
+                terms.join(" AND ")
+            }
+            c => self.comparison(c)
+        }
+    }
+
+    /// An operand of `AND`, in parentheses unless it is a comparison.
+    fn comparison(&self, condition: &Condition) -> String {
+        match condition {
+            Condition::Compare(l, comparison, r) => {
+                let comparison = match comparison {
+                    Comparison::Equal => "=",
+                    Comparison::NotEqual => "<>",
+                    Comparison::Lower => "<",
+                    Comparison::LowerEqual => "<=",
+                    Comparison::Greater => ">",
+                    Comparison::GreaterEqual => ">="
+                };
+
+                format!("{} {} {}", self.operand(l), comparison, self.operand(r))
+            }
+            c => format!("({})", self.condition(c))
+        }
+    }
+}
+
+/// Last line of a statement whose body ends on `last`, followed by its
+/// closing keyword.
+fn closed(last: usize) -> usize {
+    if last == 0 { 0 } else { last + 1 }
+}
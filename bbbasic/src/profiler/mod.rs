@@ -0,0 +1,190 @@
+//! Per-statement profile of a program run: how often every line was
+//! executed and how much time was spent on it.
+//!
+//! A statement's time is the wall time until the next statement starts,
+//! so it doesn't include the procedures it calls, but the loop condition
+//! checked after it.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// Counts and time of one line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineProfile {
+    pub line: usize,
+    pub count: u64,
+    pub time: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Sample {
+    count: u64,
+    time: Duration,
+}
+
+/// Statement that is running: its call stack, its line and when it started.
+#[derive(Clone, Copy, Debug)]
+struct Running {
+    stack: usize,
+    line: usize,
+    started: Instant,
+}
+
+#[derive(Clone, Debug)]
+pub struct Profile {
+    /// Every distinct call stack seen, procedure names without `PROC`.
+    stacks: Vec<Vec<String>>,
+    stack_ids: HashMap<Vec<String>, usize>,
+    calls: Vec<String>,
+    current: usize,
+    samples: HashMap<(usize, usize), Sample>,
+    running: Option<Running>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile::new()
+    }
+}
+
+impl Profile {
+    pub fn new() -> Profile {
+        Profile {
+            stacks: vec![Vec::new()],
+            stack_ids: HashMap::from([(Vec::new(), 0)]),
+            calls: Vec::new(),
+            current: 0,
+            samples: HashMap::new(),
+            running: None,
+        }
+    }
+
+    /// A statement on `line` starts, the one before is done.
+    pub(crate) fn statement(&mut self, line: usize) {
+        let now = Instant::now();
+        self.stop(now);
+
+        self.samples.entry((self.current, line)).or_default().count += 1;
+        self.running = Some(Running { stack: self.current, line, started: now });
+    }
+
+    pub(crate) fn enter(&mut self, procedure: &str) {
+        self.calls.push(procedure.to_string());
+        self.current = self.stack_id();
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.calls.pop();
+        self.current = self.stack_id();
+    }
+
+    /// The program stopped, the last statement is done.
+    pub(crate) fn finish(&mut self) {
+        self.stop(Instant::now());
+    }
+
+    fn stop(&mut self, now: Instant) {
+        if let Some(r) = self.running.take() {
+            self.samples.entry((r.stack, r.line)).or_default().time += now - r.started;
+        }
+    }
+
+    fn stack_id(&mut self) -> usize {
+        if let Some(id) = self.stack_ids.get(&self.calls) {
+            return *id;
+        }
+
+        self.stacks.push(self.calls.clone());
+        self.stack_ids.insert(self.calls.clone(), self.stacks.len() - 1);
+
+        self.stacks.len() - 1
+    }
+
+    /// Every line executed, in order.
+    pub fn lines(&self) -> Vec<LineProfile> {
+        let mut lines: HashMap<usize, LineProfile> = HashMap::new();
+
+        for ((_, line), sample) in &self.samples {
+            let l = lines.entry(*line).or_insert(LineProfile { line: *line, ..LineProfile::default() });
+            l.count += sample.count;
+            l.time += sample.time;
+        }
+
+        let mut lines: Vec<LineProfile> = lines.into_values().collect();
+        lines.sort_by_key(|l| l.line);
+
+        lines
+    }
+
+    /// The `n` lines the most time was spent on.
+    pub fn hotspots(&self, n: usize) -> Vec<LineProfile> {
+        let mut lines = self.lines();
+        lines.sort_by(|a, b| b.time.cmp(&a.time).then(a.line.cmp(&b.line)));
+        lines.truncate(n);
+
+        lines
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.samples.values().map(|s| s.time).sum()
+    }
+
+    /// Folded stacks as read by flame graph tools, one line per call stack
+    /// and line like `main;PROCdraw;line 12 1500`, weighted in microseconds.
+    pub fn folded(&self) -> String {
+        let mut stacks: Vec<(String, u128)> = self.samples.iter()
+            .map(|((stack, line), sample)| {
+                let mut frames = vec!["main".to_string()];
+                frames.extend(self.stacks[*stack].iter().map(|p| format!("PROC{}", p)));
+                frames.push(format!("line {}", line));
+
+                (frames.join(";"), sample.time.as_micros())
+            })
+            .collect();
+        stacks.sort();
+
+        let mut folded = String::new();
+        for (stack, micros) in stacks {
+            let _ = writeln!(folded, "{} {}", stack, micros);
+        }
+
+        folded
+    }
+
+    /// The lines as JSON: `{"lines":[{"line":1,"count":1,"time_us":12}]}`.
+    pub fn to_json(&self) -> String {
+        let lines: Vec<String> = self.lines().iter()
+            .map(|l| format!("{{\"line\":{},\"count\":{},\"time_us\":{}}}", l.line, l.count, l.time.as_micros()))
+            .collect();
+
+        format!("{{\"lines\":[{}]}}", lines.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statements_are_counted_per_stack() {
+        let mut p = Profile::new();
+
+        p.statement(1);
+        p.enter("a");
+        p.statement(5);
+        p.statement(6);
+        p.leave();
+        p.statement(1);
+        p.finish();
+
+        let counts: Vec<(usize, u64)> = p.lines().iter().map(|l| (l.line, l.count)).collect();
+        assert_eq!(counts, vec![(1, 2), (5, 1), (6, 1)]);
+
+        let folded = p.folded();
+        let stacks: Vec<&str> = folded.lines().map(|l| l.rsplit_once(' ').unwrap().0).collect();
+        assert_eq!(stacks, vec!["main;PROCa;line 5", "main;PROCa;line 6", "main;line 1"]);
+
+        assert!(p.to_json().starts_with("{\"lines\":[{\"line\":1,\"count\":2,"));
+    }
+}
@@ -0,0 +1,194 @@
+//! The resident integer variables `@%` and `A%` to `Z%`.
+//!
+//! Like on the BBC Micro they are kept apart from the other variables: they
+//! always exist, starting at zero, `CLEAR` leaves them alone and the
+//! [`Interpreter`](crate::Interpreter) hands them on from one program run to
+//! the next, which lets programs and hosts pass values to each other.
+//!
+//! `@%` sets how `PRINT` shows numbers, with the layout of BBC BASIC: the
+//! lowest byte is the width of the field numbers are right-aligned in, the
+//! next one the number of digits and the third the format, `0` for general,
+//! `1` for exponent and `2` for fixed. `@% = 2314`, which is `&90A`, prints
+//! like a BBC Micro, `@% = 131594` or `&2020A` with two decimals in ten
+//! columns. While `@%` is zero numbers print as short as possible without
+//! losing precision.
+
+use crate::scope::{DataType, Integer};
+use crate::value::Value;
+
+/// Number of resident variables, `@%` and `A%` to `Z%`.
+pub const COUNT: usize = 27;
+
+/// Index of the resident variable `name` of type `data_type`, `0` for `@%`,
+/// `1` to `26` for `A%` to `Z%`. Lower case names are ordinary variables.
+pub fn index(name: &str, data_type: DataType) -> Option<usize> {
+    match (name.as_bytes(), data_type) {
+        ([b'@'], DataType::Integer) => Some(0),
+        ([c @ b'A'..=b'Z'], DataType::Integer) => Some((c - b'A') as usize + 1),
+        _ => None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Resident {
+    values: [Value; COUNT],
+}
+
+impl Default for Resident {
+    fn default() -> Self {
+        Resident { values: std::array::from_fn(|_| Value::Integer(0)) }
+    }
+}
+
+impl Resident {
+    pub fn new() -> Resident {
+        Resident::default()
+    }
+
+    pub fn get(&self, index: usize) -> &Value {
+        &self.values[index]
+    }
+
+    /// Assigns a resident variable, `value` already being an integer.
+    pub fn set(&mut self, index: usize, value: Value) {
+        self.values[index] = value;
+    }
+
+    /// Value of `@%`.
+    pub fn print_format(&self) -> Integer {
+        self.values[0].to_integer().unwrap_or(0)
+    }
+
+    /// Text `PRINT` shows for `value`, formatted following `@%`.
+    pub fn format(&self, value: &Value) -> String {
+        format(self.print_format(), value)
+    }
+}
+
+/// Text `PRINT` shows for `value` with `@%` set to `format`.
+pub fn format(format: Integer, value: &Value) -> String {
+    let number = match value {
+        Value::Integer(i) => Number::Integer(*i),
+        Value::Byte(b) => Number::Integer(*b as Integer),
+        Value::Float(f) => Number::Float(*f),
+        v => return v.to_string()
+    };
+
+    match format {
+        0 => value.to_string(),
+        format => number.format(format)
+    }
+}
+
+enum Number {
+    Integer(Integer),
+    Float(f64),
+}
+
+impl Number {
+    fn format(&self, format: Integer) -> String {
+        let width = (format & 0xff) as usize;
+        let digits = ((format >> 8) & 0xff) as usize;
+
+        let text = match ((format >> 16) & 0xff, self) {
+            (1, n) => exponent(n.to_float(), digits.clamp(1, 17), false),
+            (2, n) => format!("{:.*}", digits.min(17), n.to_float()),
+            (_, Number::Integer(i)) => i.to_string(),
+            (_, Number::Float(f)) => general(*f, if digits == 0 { 10 } else { digits.min(17) })
+        };
+
+        format!("{:>width$}", text)
+    }
+
+    fn to_float(&self) -> f64 {
+        match self {
+            Number::Integer(i) => *i as f64,
+            Number::Float(f) => *f
+        }
+    }
+}
+
+/// `f` with `digits` significant digits, like `1.50E3`. Trailing zeros are
+/// dropped if `trim` is set.
+fn exponent(f: f64, digits: usize, trim: bool) -> String {
+    let text = format!("{:.*e}", digits - 1, f);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+
+    let mantissa = if trim { trim_zeros(mantissa) } else { mantissa };
+    format!("{}E{}", mantissa, exponent)
+}
+
+/// `f` with up to `digits` significant digits, switching to the exponent
+/// form below 0.1 and from 10 to the power of `digits` on.
+fn general(f: f64, digits: usize) -> String {
+    if f == 0.0 || !f.is_finite() {
+        return f.to_string();
+    }
+
+    let text = format!("{:.*e}", digits - 1, f);
+    let magnitude: i32 = text.split_once('e').and_then(|(_, e)| e.parse().ok()).unwrap_or(0);
+
+    if magnitude < -1 || magnitude >= digits as i32 {
+        return exponent(f, digits, true);
+    }
+
+    let decimals = (digits as i32 - 1 - magnitude) as usize;
+    trim_zeros(&format!("{:.*}", decimals, f)).to_string()
+}
+
+fn trim_zeros(text: &str) -> &str {
+    match text.contains('.') {
+        true => text.trim_end_matches('0').trim_end_matches('.'),
+        false => text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(at: Integer, value: Value) -> String {
+        let mut resident = Resident::new();
+        resident.set(0, Value::Integer(at));
+        resident.format(&value)
+    }
+
+    #[test]
+    fn resident_names() {
+        assert_eq!(index("@", DataType::Integer), Some(0));
+        assert_eq!(index("A", DataType::Integer), Some(1));
+        assert_eq!(index("Z", DataType::Integer), Some(26));
+        assert_eq!(index("a", DataType::Integer), None);
+        assert_eq!(index("A", DataType::Float), None);
+        assert_eq!(index("AB", DataType::Integer), None);
+    }
+
+    #[test]
+    fn zero_keeps_the_shortest_form() {
+        assert_eq!(format(0, Value::Float(0.1)), "0.1");
+        assert_eq!(format(0, Value::Float(1.0 / 3.0)), "0.3333333333333333");
+        assert_eq!(format(0, Value::Integer(42)), "42");
+        assert_eq!(format(0, Value::String("x".to_string())), "x");
+    }
+
+    #[test]
+    fn general_format() {
+        assert_eq!(format(0x90A, Value::Integer(42)), "        42");
+        assert_eq!(format(0x90A, Value::Float(1.0 / 3.0)), "0.333333333");
+        assert_eq!(format(0x900, Value::Float(2.5)), "2.5");
+        assert_eq!(format(0x900, Value::Float(0.01)), "1E-2");
+        assert_eq!(format(0x900, Value::Float(1e10)), "1E10");
+        assert_eq!(format(0x300, Value::Float(12345.0)), "1.23E4");
+        assert_eq!(format(0x900, Value::Float(9.9999999999)), "10");
+    }
+
+    #[test]
+    fn exponent_and_fixed_formats() {
+        assert_eq!(format(0x10300, Value::Float(1500.0)), "1.50E3");
+        assert_eq!(format(0x10300, Value::Integer(-2)), "-2.00E0");
+        assert_eq!(format(0x2020A, Value::Float(12.3456)), "     12.35");
+        assert_eq!(format(0x20200, Value::Integer(7)), "7.00");
+        assert_eq!(format(0x20000, Value::Float(2.7)), "3");
+    }
+}
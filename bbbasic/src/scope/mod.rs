@@ -1,171 +1,530 @@
 use std::collections::HashMap;
-use std::str::FromStr;
+use std::rc::Rc;
+use crate::ast::Procedure;
 use crate::error::InterpreterError;
+use crate::limits::Limits;
+use crate::memory::Memory;
+use crate::native::{Native, Natives};
+use crate::profiler::Profile;
+use crate::resident::{self, Resident};
+use crate::trace::Trace;
+use crate::value::Value;
 
 pub type Float = f64;
 pub type Integer = i64;
 pub type Byte = u8;
 
-
-pub trait One {
-    fn one() -> Self;
+/// Index of a variable in the value vector of a [`Scope`].
+pub type Slot = usize;
+
+/// Variables made local by a running procedure, with the values they get
+/// back once it returns.
+pub(crate) type Locals = Vec<(Slot, Option<Value>)>;
+
+
+/// Width of integer variables and how integer arithmetic overflows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntegerModel {
+    /// 32-bit integers of Acorn BBC BASIC. Addition and subtraction wrap
+    /// around, multiplication results that don't fit become floats.
+    Acorn,
+    /// 64-bit integers of BBC BASIC for Windows and BBC BASIC for SDL. Any
+    /// result that doesn't fit becomes a float.
+    #[default]
+    BbcSdl,
 }
 
-impl One for Float {
-    fn one() -> Self {
-        1.0
+impl IntegerModel {
+    pub fn min(self) -> Integer {
+        match self {
+            IntegerModel::Acorn => i32::MIN as Integer,
+            IntegerModel::BbcSdl => Integer::MIN
+        }
     }
-}
 
-impl One for Integer {
-    fn one() -> Self {
-        1
+    pub fn max(self) -> Integer {
+        match self {
+            IntegerModel::Acorn => i32::MAX as Integer,
+            IntegerModel::BbcSdl => Integer::MAX
+        }
     }
-}
 
-impl One for Byte {
-    fn one() -> Self {
-        1
+    pub fn contains(self, i: Integer) -> bool {
+        i >= self.min() && i <= self.max()
+    }
+
+    /// Checks that `i` fits an integer variable.
+    pub fn check(self, i: Integer) -> Result<Integer, InterpreterError> {
+        if self.contains(i) {
+            Ok(i)
+        } else {
+            Err(InterpreterError::NumberTooBig)
+        }
     }
 }
 
-pub trait DataTypeQuery {
-    fn get_type() -> DataType;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataType {
+    Byte = 1,
+    Integer = 2,
+    Float = 3,
+    String = 99
 }
 
+impl DataType {
+    /// Suffix of variables of this type, `#` is left out for floats.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            DataType::Byte => "&",
+            DataType::Integer => "%",
+            DataType::Float => "",
+            DataType::String => "$"
+        }
+    }
+
+    /// Splits a variable name like `count%` into name and type.
+    pub fn of_name(name: &str) -> (&str, DataType) {
+        for (suffix, data_type) in [("%", DataType::Integer), ("&", DataType::Byte), ("$", DataType::String), ("#", DataType::Float)] {
+            if let Some(n) = name.strip_suffix(suffix) {
+                return (n, data_type);
+            }
+        }
 
-impl DataTypeQuery for Float {
-    fn get_type() -> DataType {
-        DataType::Float
+        (name, DataType::Float)
     }
 }
 
-impl DataTypeQuery for Byte {
-    fn get_type() -> DataType {
-        DataType::Byte
-    }
+
+/// Assigns every distinct variable a slot. Variables are told apart by name
+/// and type suffix, `a%` and `a` are different variables, `a` and `a#` are
+/// the same.
+#[derive(Clone, Debug, Default)]
+pub struct Symbols {
+    names: Vec<(String, DataType)>,
+    slots: HashMap<(String, DataType), Slot>,
+    /// Index in the [`Resident`] store by slot, worked out once here.
+    residents: Vec<Option<u8>>,
 }
 
-impl DataTypeQuery for Integer {
-    fn get_type() -> DataType {
-        DataType::Integer
+impl Symbols {
+    pub fn new() -> Symbols {
+        Symbols::default()
     }
-}
 
-impl DataTypeQuery for String {
-    fn get_type() -> DataType {
-        DataType::String
+    /// Returns the slot of a variable, assigning a new one if it hasn't been
+    /// seen before.
+    pub fn resolve(&mut self, name: &str, data_type: DataType) -> Slot {
+        if let Some(slot) = self.find(name, data_type) {
+            return slot;
+        }
+
+        let slot = self.names.len();
+        self.names.push((name.to_string(), data_type));
+        self.slots.insert((name.to_string(), data_type), slot);
+        self.residents.push(resident::index(name, data_type).map(|i| i as u8));
+
+        slot
     }
-}
 
+    pub fn find(&self, name: &str, data_type: DataType) -> Option<Slot> {
+        self.slots.get(&(name.to_string(), data_type)).copied()
+    }
 
-pub trait ScopeValue : std::ops::Add + std::ops::Sub + std::ops::Div + std::ops::Mul + One + FromStr + Copy + DataTypeQuery {}
-impl <T: std::ops::Add<Output = T> + std::ops::Sub + std::ops::Div + std::ops::Div<Output=T> + std::ops::Mul + One + FromStr + Copy + DataTypeQuery>  ScopeValue for T {}
+    pub fn name(&self, slot: Slot) -> &str {
+        match self.names.get(slot) {
+            None => "",
+            Some((name, _)) => name
+        }
+    }
 
+    pub fn data_type(&self, slot: Slot) -> Option<DataType> {
+        self.names.get(slot).map(|(_, t)| *t)
+    }
 
-pub enum DataType {
-    Byte = 1,
-    Integer = 2,
-    Float = 3,
-    String = 99
+    /// Index of the variable in the [`Resident`] store, for `@%` and `A%` to
+    /// `Z%`.
+    pub fn resident(&self, slot: Slot) -> Option<usize> {
+        self.residents.get(slot).copied().flatten().map(usize::from)
+    }
+
+    /// Slots of the resident variables, with their index.
+    fn resident_slots(&self) -> impl Iterator<Item = (Slot, usize)> + '_ {
+        self.residents.iter().enumerate().filter_map(|(slot, i)| Some((slot, usize::from((*i)?))))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
 }
 
 
-#[derive(Debug)]
+/// Variable storage. The interpreter addresses variables by slot, the named
+/// accessors are meant for debugging and for the host.
+#[derive(Debug, Default)]
 pub struct Scope {
-    floats: HashMap<String, Float>,
-    ints: HashMap<String, Integer>,
-    bytes: HashMap<String, Byte>,
-    strings: HashMap<String, String>
+    symbols: Symbols,
+    values: Vec<Option<Value>>,
+    integers: IntegerModel,
+    limits: Limits,
+    trace: Trace,
+    profile: Option<Box<Profile>>,
+    procedures: HashMap<String, Rc<Procedure>>,
+    natives: Rc<Natives>,
+    memory: Memory,
+    /// `@%` and `A%` to `Z%`. Those the program mentions hold their value in
+    /// `values` like any variable, until they are first assigned they read
+    /// it from here.
+    resident: Resident,
+    /// Values of the local variables of every running procedure, saved to be
+    /// restored once it returns.
+    locals: Vec<Locals>,
 }
 
 impl Scope {
 
-    pub fn new() ->Scope {
+    pub fn new() -> Scope {
+        Scope::default()
+    }
+
+    pub fn with_integer_model(model: IntegerModel) -> Scope {
         Scope {
-            floats: HashMap::new(),
-            ints: HashMap::new(),
-            bytes: HashMap::new(),
-            strings: HashMap::new()
+            integers: model,
+            ..Scope::default()
         }
     }
-/*
-    pub fn set<T: DataTypeQuery>(&mut self, name: &String, value: T) {
-        match T::get_type() {
-            DataType::Float => self.set_float(name, value as Float),
-            _ => {}
+
+    pub fn integer_model(&self) -> IntegerModel {
+        self.integers
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn trace(&self) -> Trace {
+        self.trace
+    }
+
+    pub fn set_trace(&mut self, trace: Trace) {
+        self.trace = trace;
+    }
+
+    /// Starts recording a [`Profile`] of the running program.
+    pub fn set_profile(&mut self, profile: Profile) {
+        self.profile = Some(Box::new(profile));
+    }
+
+    /// Whether statements are traced or profiled, so the engines can skip
+    /// both checks while they're off.
+    pub fn instrumented(&self) -> bool {
+        !self.trace.is_off() || self.profile.is_some()
+    }
+
+    pub fn profile_mut(&mut self) -> Option<&mut Profile> {
+        self.profile.as_deref_mut()
+    }
+
+    /// Stops recording, returning the profile.
+    pub fn take_profile(&mut self) -> Option<Profile> {
+        let mut profile = self.profile.take()?;
+        profile.finish();
+
+        Some(*profile)
+    }
+
+    /// Takes a step of the running program, see [`Limits::step`].
+    #[inline]
+    pub fn step(&mut self) -> Result<(), InterpreterError> {
+        self.limits.step()
+    }
+
+    pub fn symbols(&self) -> &Symbols {
+        &self.symbols
+    }
+
+    /// Symbols to resolve a program against. The value vector grows with it.
+    pub fn symbols_mut(&mut self) -> &mut Symbols {
+        &mut self.symbols
+    }
+
+    /// Makes `procedures` known to the tree-walker, like BBC BASIC the first
+    /// definition of a name wins.
+    pub fn set_procedures(&mut self, procedures: &[Procedure]) {
+        self.procedures.clear();
+
+        for p in procedures.iter().rev() {
+            self.procedures.insert(p.name.clone(), Rc::new(p.clone()));
         }
     }
 
-    pub fn get<T: DataTypeQuery>(&mut self, name: &String) -> Result<T, InterpreterError> {
-        match T::get_type() {
-            DataType::Float => self.get_float(name),
-            _ => {}
+    pub fn procedure(&self, name: &str) -> Result<Rc<Procedure>, InterpreterError> {
+        self.procedures.get(name).cloned().ok_or_else(|| InterpreterError::NoSuchProcedure(name.to_string()))
+    }
+
+    /// Memory reserved with `DIM`.
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    pub fn memory_mut(&mut self) -> &mut Memory {
+        &mut self.memory
+    }
+
+    /// Replaces the memory, to set its limit.
+    pub fn set_memory(&mut self, memory: Memory) {
+        self.memory = memory;
+    }
+
+    /// The resident integer variables, kept by [`Scope::clear`].
+    pub fn resident(&self) -> Resident {
+        let mut resident = self.resident.clone();
+        for (slot, index) in self.symbols.resident_slots() {
+            if let Some(Some(value)) = self.values.get(slot) {
+                resident.set(index, value.clone());
+            }
         }
+
+        resident
+    }
+
+    /// Sets the resident variables the program hasn't assigned yet, meant
+    /// for a new scope.
+    pub fn set_resident(&mut self, resident: Resident) {
+        self.resident = resident;
+    }
+
+    /// Text `PRINT` shows for `value`, following `@%`.
+    pub fn format(&self, value: &Value) -> String {
+        let format = match self.symbols.resident_slots().find(|(_, index)| *index == 0) {
+            Some((slot, _)) => self.get(slot).ok().and_then(|v| v.to_integer().ok()).unwrap_or(0),
+            None => self.resident.print_format()
+        };
+
+        resident::format(format, value)
     }
-*/
-    pub fn set_float(&mut self, name: &String, value: Float) {
-        self.floats.insert(name.clone(), value);
+
+    /// Makes the natives registered with the interpreter callable.
+    pub(crate) fn set_natives(&mut self, natives: Rc<Natives>) {
+        self.natives = natives;
     }
 
-    pub fn set_int(&mut self, name: &String, value: Integer) {
-        self.ints.insert(name.clone(), value);
+    pub(crate) fn native_function(&self, name: &str) -> Result<Native, InterpreterError> {
+        self.natives.functions.get(name).cloned().ok_or_else(|| InterpreterError::NoSuchProcedure(name.to_string()))
     }
 
-    pub fn set_byte(&mut self, name: &String, value: Byte) {
-        self.bytes.insert(name.clone(), value);
+    /// The native procedure `name`, which is only called if the program
+    /// doesn't define one.
+    pub(crate) fn native_procedure(&self, name: &str) -> Option<Native> {
+        self.natives.procedures.get(name).cloned()
     }
 
-    pub fn set_string(&mut self, name: &String, value: String) {
-        self.strings.insert(name.clone(), value);
+    /// Starts a procedure call, see [`Scope::make_local`].
+    pub fn enter(&mut self) {
+        self.locals.push(Vec::new());
     }
 
-    pub fn get_float(&self, name: &String) -> Result<Float, InterpreterError> {
-        match self.floats.get(name) {
-            None => Err(InterpreterError::UnknownVariable(name.clone())),
-            Some(v) => Ok(*v)
+    /// Saves the value of a variable, which gets it back when the current
+    /// procedure returns.
+    pub fn make_local(&mut self, slot: Slot) -> Result<(), InterpreterError> {
+        let saved = self.values.get(slot).cloned().flatten();
+
+        match self.locals.last_mut() {
+            None => Err(InterpreterError::NotInProcedure),
+            Some(frame) => {
+                frame.push((slot, saved));
+                Ok(())
+            }
         }
     }
 
-    pub fn get_int(&self, name: &String) -> Result<Integer, InterpreterError> {
-        match self.ints.get(name) {
-            None => Err(InterpreterError::UnknownVariable(name.clone())),
-            Some(v) => Ok(*v)
+    /// Ends a procedure call, restoring its local variables.
+    pub fn leave(&mut self) -> Result<(), InterpreterError> {
+        let frame = self.locals.pop().ok_or(InterpreterError::NotInProcedure)?;
+
+        for (slot, value) in frame.into_iter().rev() {
+            if let Some(v) = self.values.get_mut(slot) {
+                *v = value;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of running procedures.
+    pub fn depth(&self) -> usize {
+        self.locals.len()
+    }
+
+    fn unknown(&self, slot: Slot) -> InterpreterError {
+        InterpreterError::UnknownVariable(self.symbols.name(slot).to_string())
+    }
+
+    /// Values by slot, the saved locals of every running procedure and the
+    /// resident variables not assigned yet, what a snapshot keeps of the
+    /// scope.
+    #[cfg(feature = "serde")]
+    pub(crate) fn state(&self) -> (&[Option<Value>], &[Locals], &Resident) {
+        (&self.values, &self.locals, &self.resident)
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn restore_state(&mut self, values: Vec<Option<Value>>, locals: Vec<Locals>, resident: Resident) {
+        self.values = values;
+        self.locals = locals;
+        self.resident = resident;
+    }
+
+    /// Forgets the values of all variables, keeping their slots, and
+    /// releases the memory reserved with `DIM`. The resident variables keep
+    /// their values, like in BBC BASIC.
+    pub fn clear(&mut self) {
+        self.resident = self.resident();
+        self.values.clear();
+        self.locals.clear();
+        self.memory.clear();
+    }
+
+    #[inline]
+    pub fn get(&self, slot: Slot) -> Result<&Value, InterpreterError> {
+        match self.values.get(slot) {
+            Some(Some(v)) => Ok(v),
+            _ => match self.symbols.resident(slot) {
+                Some(index) => Ok(self.resident.get(index)),
+                None => Err(self.unknown(slot))
+            }
         }
     }
 
-    pub fn get_byte(&self, name: &String) -> Result<Byte, InterpreterError> {
-        match self.bytes.get(name) {
-            None => Err(InterpreterError::UnknownVariable(name.clone())),
-            Some(v) => Ok(*v)
+    #[inline]
+    pub fn set(&mut self, slot: Slot, value: Value) {
+        if slot >= self.values.len() {
+            self.values.resize(self.symbols.len().max(slot + 1), None);
         }
+
+        self.values[slot] = Some(value);
     }
 
-    pub fn get_string(&self, name: &String) -> Result<&String, InterpreterError> {
-        match self.strings.get(name) {
-            None => Err(InterpreterError::UnknownVariable(name.clone())),
-            Some(v) => Ok(v)
+    pub fn float(&self, slot: Slot) -> Result<Float, InterpreterError> {
+        match self.get(slot)? {
+            Value::Float(f) => Ok(*f),
+            _ => Err(InterpreterError::TypeMismatch)
         }
     }
 
-    #[allow(dead_code)]
-    pub fn is(&self, name: &String) -> Result<DataType, InterpreterError> {
-        if self.floats.contains_key(name) {
-            return Ok(DataType::Float)
+    pub fn integer(&self, slot: Slot) -> Result<Integer, InterpreterError> {
+        match self.get(slot)? {
+            Value::Integer(i) => Ok(*i),
+            _ => Err(InterpreterError::TypeMismatch)
         }
+    }
 
-        if self.ints.contains_key(name) {
-            return Ok(DataType::Integer)
+    pub fn byte(&self, slot: Slot) -> Result<Byte, InterpreterError> {
+        match self.get(slot)? {
+            Value::Byte(b) => Ok(*b),
+            _ => Err(InterpreterError::TypeMismatch)
         }
+    }
 
-        if self.bytes.contains_key(name) {
-            return Ok(DataType::Byte)
+    pub fn string(&self, slot: Slot) -> Result<&String, InterpreterError> {
+        match self.get(slot)? {
+            Value::String(s) => Ok(s),
+            _ => Err(InterpreterError::TypeMismatch)
         }
+    }
+
+    /// Variables holding a value, with the suffix of their type. Resident
+    /// variables are only listed once a program mentions them.
+    pub fn variables(&self) -> Vec<(String, &Value)> {
+        (0..self.symbols.len())
+            .filter_map(|slot| {
+                let v = self.get(slot).ok()?;
+                let data_type = self.symbols.data_type(slot)?;
+                Some((format!("{}{}", self.symbols.name(slot), data_type.suffix()), v))
+            })
+            .collect()
+    }
+
+    /// Value of a variable given with its suffix, like `a$`.
+    pub fn variable(&self, name: &str) -> Result<&Value, InterpreterError> {
+        let (name, data_type) = DataType::of_name(name);
+        self.get_named(name, data_type)
+    }
+
+    /// Assigns a variable given with its suffix, converting `value` to its
+    /// type.
+    pub fn set_variable(&mut self, name: &str, value: Value) -> Result<(), InterpreterError> {
+        let (name, data_type) = DataType::of_name(name);
+        let value = value.convert(data_type, self.integers)?;
+        self.set_named(name, data_type, value);
+
+        Ok(())
+    }
+
+    pub(crate) fn set_named(&mut self, name: &str, data_type: DataType, value: Value) {
+        let slot = self.symbols.resolve(name, data_type);
+        self.set(slot, value);
+    }
+
+    fn get_named(&self, name: &str, data_type: DataType) -> Result<&Value, InterpreterError> {
+        match self.symbols.find(name, data_type) {
+            None => match resident::index(name, data_type) {
+                Some(index) => Ok(self.resident.get(index)),
+                None => Err(InterpreterError::UnknownVariable(name.to_string()))
+            },
+            Some(slot) => self.get(slot)
+        }
+    }
+
+    pub fn set_float(&mut self, name: &str, value: Float) {
+        self.set_named(name, DataType::Float, Value::Float(value));
+    }
 
-        if self.strings.contains_key(name) {
-            return Ok(DataType::String)
+    pub fn set_int(&mut self, name: &str, value: Integer) {
+        self.set_named(name, DataType::Integer, Value::Integer(value));
+    }
+
+    pub fn set_byte(&mut self, name: &str, value: Byte) {
+        self.set_named(name, DataType::Byte, Value::Byte(value));
+    }
+
+    pub fn set_string(&mut self, name: &str, value: String) {
+        self.set_named(name, DataType::String, Value::String(value));
+    }
+
+    pub fn get_float(&self, name: &str) -> Result<Float, InterpreterError> {
+        self.get_named(name, DataType::Float)?.to_float()
+    }
+
+    pub fn get_int(&self, name: &str) -> Result<Integer, InterpreterError> {
+        self.get_named(name, DataType::Integer)?.to_integer()
+    }
+
+    pub fn get_byte(&self, name: &str) -> Result<Byte, InterpreterError> {
+        self.get_named(name, DataType::Byte)?.to_byte()
+    }
+
+    pub fn get_string(&self, name: &str) -> Result<&String, InterpreterError> {
+        match self.get_named(name, DataType::String)? {
+            Value::String(s) => Ok(s),
+            _ => Err(InterpreterError::TypeMismatch)
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn is(&self, name: &str) -> Result<DataType, InterpreterError> {
+        for t in [DataType::Float, DataType::Integer, DataType::Byte, DataType::String] {
+            if self.get_named(name, t).is_ok() {
+                return Ok(t);
+            }
         }
 
-        Err(InterpreterError::UnknownVariable(name.clone()))
+        Err(InterpreterError::UnknownVariable(name.to_string()))
     }
 }
@@ -0,0 +1,82 @@
+//! Snapshots of programs started with
+//! [`Interpreter::start`](crate::Interpreter::start), to stop a long running
+//! program and continue it later, in another process. Only available with
+//! the `serde` feature.
+//!
+//! A snapshot keeps the source of the program, the interpreter settings it
+//! was compiled with, the values of all variables, the locals saved by
+//! running procedures, the memory reserved with `DIM`, the resident
+//! variables `@%` and `A%` to `Z%` and the state of the VM: the next
+//! instruction, its stack, running `FOR` loops and procedure calls. Restoring compiles the
+//! source again, which gives the same bytecode.
+//!
+//! Of the [`Host`](crate::Host) a snapshot keeps the state of the random
+//! number generator, if the host can tell it, so that `RND` continues the
+//! same sequence. `TIME` and the screen aren't part of it, a profile being
+//! recorded is dropped. Files can't be saved: taking a snapshot while the
+//! program has files open fails with
+//! [`InterpreterError::FilesOpen`](crate::InterpreterError::FilesOpen).
+//!
+//! Snapshots are written as JSON. Changes to the format that break readers
+//! increase the version, [`Snapshot::from_json`] only reads snapshots of
+//! [`VERSION`].
+
+use serde::de::Error;
+use serde::{Deserialize, Serialize};
+use crate::memory::Memory;
+use crate::resident::Resident;
+use crate::scope::{IntegerModel, Locals};
+use crate::trace::Trace;
+use crate::value::Value;
+use crate::vm::Vm;
+
+/// Version of the format written by [`Snapshot::to_json`].
+pub const VERSION: u32 = 1;
+
+/// State of a started program, taken with
+/// [`Interpreter::snapshot`](crate::Interpreter::snapshot) and continued
+/// with [`Interpreter::restore`](crate::Interpreter::restore).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub(crate) version: u32,
+    pub(crate) code: String,
+    pub(crate) integers: IntegerModel,
+    pub(crate) optimize: bool,
+    pub(crate) trace: Trace,
+    /// Names of the variables with their suffix, by slot.
+    pub(crate) variables: Vec<String>,
+    pub(crate) values: Vec<Option<Value>>,
+    pub(crate) locals: Vec<Locals>,
+    /// Memory reserved with `DIM`, missing in snapshots taken before it
+    /// existed.
+    #[serde(default)]
+    pub(crate) memory: Memory,
+    #[serde(default)]
+    pub(crate) resident: Resident,
+    /// See [`Host::random_state`](crate::Host::random_state).
+    #[serde(default)]
+    pub(crate) random: Option<u64>,
+    pub(crate) vm: Vm,
+}
+
+impl Snapshot {
+    /// Source of the program.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn to_json(&self) -> String {
+        // Snapshots are plain data, they can always be written.
+        serde_json::to_string(self).expect("Snapshot not serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Snapshot, serde_json::Error> {
+        let snapshot: Snapshot = serde_json::from_str(json)?;
+
+        if snapshot.version != VERSION {
+            return Err(serde_json::Error::custom(format!("Unsupported version {}", snapshot.version)));
+        }
+
+        Ok(snapshot)
+    }
+}
@@ -0,0 +1,531 @@
+//! Tokenised program files, in which keywords are replaced by tokens `0x80`
+//! to `0xFF`. Keywords are only tokenised as whole words, so variables like
+//! `TOTAL` keep their names.
+//!
+//! Programs saved by BBC BASIC on the BBC Micro and RISC OS store every line
+//! as `0x0D`, the line number high byte first, the length of the line and
+//! its text. The program ends with `0x0D 0xFF`. The tokens are those of
+//! BASIC V, which adds `WHILE`, `ENDIF` and the like to the ones of BASIC II.
+//! Text in strings and after `REM` and `DATA` is stored as Latin-1.
+//!
+//! BBC BASIC for Windows and BBCSDL save `.bbc` files, which store every line
+//! as its length, the line number low byte first, its text and `0x0D`. The
+//! program ends with `0x00 0xFF 0xFF`. Their tokens differ from BASIC V after
+//! `0xC5` and use `0x01` to `0x10` for their own statements, text is UTF-8.
+
+use std::ops::RangeInclusive;
+use crate::error::InterpreterError;
+use crate::listing::{Listing, REFERENCES};
+
+/// Highest line number of a tokenised program on a BBC Micro or RISC OS.
+pub const MAX_ACORN_LINE_NUMBER: u32 = 32767;
+
+/// Token of a line number after `GOTO` and the like, followed by the
+/// number in three bytes.
+const LINE_NUMBER: u8 = 0x8D;
+
+/// Tokens of keywords, which BASIC V extends with two-byte tokens after
+/// `0xC6`, `0xC7` and `0xC8`.
+const TOKENS: &[(u8, &str)] = &[
+    (0x7F, "OTHERWISE"),
+    (0x80, "AND"), (0x81, "DIV"), (0x82, "EOR"), (0x83, "MOD"), (0x84, "OR"), (0x85, "ERROR"), (0x86, "LINE"), (0x87, "OFF"),
+    (0x88, "STEP"), (0x89, "SPC"), (0x8A, "TAB("), (0x8B, "ELSE"), (0x8C, "THEN"), (0x8E, "OPENIN"), (0x8F, "PTR"),
+    (0x90, "PAGE"), (0x91, "TIME"), (0x92, "LOMEM"), (0x93, "HIMEM"), (0x94, "ABS"), (0x95, "ACS"), (0x96, "ADVAL"), (0x97, "ASC"),
+    (0x98, "ASN"), (0x99, "ATN"), (0x9A, "BGET"), (0x9B, "COS"), (0x9C, "COUNT"), (0x9D, "DEG"), (0x9E, "ERL"), (0x9F, "ERR"),
+    (0xA0, "EVAL"), (0xA1, "EXP"), (0xA2, "EXT"), (0xA3, "FALSE"), (0xA4, "FN"), (0xA5, "GET"), (0xA6, "INKEY"), (0xA7, "INSTR("),
+    (0xA8, "INT"), (0xA9, "LEN"), (0xAA, "LN"), (0xAB, "LOG"), (0xAC, "NOT"), (0xAD, "OPENUP"), (0xAE, "OPENOUT"), (0xAF, "PI"),
+    (0xB0, "POINT("), (0xB1, "POS"), (0xB2, "RAD"), (0xB3, "RND"), (0xB4, "SGN"), (0xB5, "SIN"), (0xB6, "SQR"), (0xB7, "TAN"),
+    (0xB8, "TO"), (0xB9, "TRUE"), (0xBA, "USR"), (0xBB, "VAL"), (0xBC, "VPOS"), (0xBD, "CHR$"), (0xBE, "GET$"), (0xBF, "INKEY$"),
+    (0xC0, "LEFT$("), (0xC1, "MID$("), (0xC2, "RIGHT$("), (0xC3, "STR$"), (0xC4, "STRING$("), (0xC5, "EOF"),
+    (0xC9, "WHEN"), (0xCA, "OF"), (0xCB, "ENDCASE"), (0xCC, "ELSE"), (0xCD, "ENDIF"), (0xCE, "ENDWHILE"), (0xCF, "PTR"),
+    (0xD0, "PAGE"), (0xD1, "TIME"), (0xD2, "LOMEM"), (0xD3, "HIMEM"), (0xD4, "SOUND"), (0xD5, "BPUT"), (0xD6, "CALL"), (0xD7, "CHAIN"),
+    (0xD8, "CLEAR"), (0xD9, "CLOSE"), (0xDA, "CLG"), (0xDB, "CLS"), (0xDC, "DATA"), (0xDD, "DEF"), (0xDE, "DIM"), (0xDF, "DRAW"),
+    (0xE0, "END"), (0xE1, "ENDPROC"), (0xE2, "ENVELOPE"), (0xE3, "FOR"), (0xE4, "GOSUB"), (0xE5, "GOTO"), (0xE6, "GCOL"), (0xE7, "IF"),
+    (0xE8, "INPUT"), (0xE9, "LET"), (0xEA, "LOCAL"), (0xEB, "MODE"), (0xEC, "MOVE"), (0xED, "NEXT"), (0xEE, "ON"), (0xEF, "VDU"),
+    (0xF0, "PLOT"), (0xF1, "PRINT"), (0xF2, "PROC"), (0xF3, "READ"), (0xF4, "REM"), (0xF5, "REPEAT"), (0xF6, "REPORT"), (0xF7, "RESTORE"),
+    (0xF8, "RETURN"), (0xF9, "RUN"), (0xFA, "STOP"), (0xFB, "COLOUR"), (0xFC, "TRACE"), (0xFD, "UNTIL"), (0xFE, "WIDTH"), (0xFF, "OSCLI"),
+];
+
+/// Two-byte tokens: functions after `0xC6`, commands after `0xC7` and
+/// statements after `0xC8`.
+const EXTENDED_TOKENS: &[(u8, u8, &str)] = &[
+    (0xC6, 0x8E, "SUM"), (0xC6, 0x8F, "BEAT"),
+    (0xC7, 0x8E, "APPEND"), (0xC7, 0x8F, "AUTO"), (0xC7, 0x90, "CRUNCH"), (0xC7, 0x91, "DELETE"), (0xC7, 0x92, "EDIT"), (0xC7, 0x93, "HELP"),
+    (0xC7, 0x94, "LIST"), (0xC7, 0x95, "LOAD"), (0xC7, 0x96, "LVAR"), (0xC7, 0x97, "NEW"), (0xC7, 0x98, "OLD"), (0xC7, 0x99, "RENUMBER"),
+    (0xC7, 0x9A, "SAVE"), (0xC7, 0x9B, "TEXTLOAD"), (0xC7, 0x9C, "TEXTSAVE"), (0xC7, 0x9D, "TWIN"), (0xC7, 0x9E, "TWINO"), (0xC7, 0x9F, "INSTALL"),
+    (0xC8, 0x8E, "CASE"), (0xC8, 0x8F, "CIRCLE"), (0xC8, 0x90, "FILL"), (0xC8, 0x91, "ORIGIN"), (0xC8, 0x92, "POINT"), (0xC8, 0x93, "RECTANGLE"),
+    (0xC8, 0x94, "SWAP"), (0xC8, 0x95, "WHILE"), (0xC8, 0x96, "WAIT"), (0xC8, 0x97, "MOUSE"), (0xC8, 0x98, "QUIT"), (0xC8, 0x99, "SYS"),
+    (0xC8, 0x9A, "INSTALL"), (0xC8, 0x9B, "LIBRARY"), (0xC8, 0x9C, "TINT"), (0xC8, 0x9D, "ELLIPSE"), (0xC8, 0x9E, "BEATS"), (0xC8, 0x9F, "TEMPO"),
+    (0xC8, 0xA0, "VOICES"), (0xC8, 0xA1, "VOICE"), (0xC8, 0xA2, "STEREO"), (0xC8, 0xA3, "OVERLAY"), (0xC8, 0xA4, "MANDEL"), (0xC8, 0xA5, "PRIVATE"),
+];
+
+/// Tokens of BBC BASIC for Windows and BBCSDL, where they differ from
+/// [`TOKENS`].
+const BBC_TOKENS: &[(u8, &str)] = &[
+    (0x01, "CIRCLE"), (0x02, "ELLIPSE"), (0x03, "FILL"), (0x04, "MOUSE"), (0x05, "ORIGIN"), (0x06, "QUIT"), (0x07, "RECTANGLE"), (0x08, "SWAP"),
+    (0x09, "SYS"), (0x0A, "TINT"), (0x0B, "WAIT"), (0x0C, "INSTALL"), (0x0E, "PRIVATE"), (0x0F, "BY"), (0x10, "EXIT"),
+    (0xC6, "SUM"), (0xC7, "WHILE"), (0xC8, "CASE"), (0xC9, "WHEN"), (0xCA, "OF"), (0xCB, "ENDCASE"), (0xCD, "ENDIF"), (0xCE, "ENDWHILE"),
+];
+
+/// Pseudo-variables have a second token at the start of a statement, where
+/// they are assigned to.
+const STATEMENT_TOKENS: [(u8, u8); 5] = [(0x8F, 0xCF), (0x90, 0xD0), (0x91, 0xD1), (0x92, 0xD2), (0x93, 0xD3)];
+
+/// Keywords and character set of one of the tokenised formats.
+struct Dialect {
+    /// Tokens of the dialect's own, looked up before [`TOKENS`].
+    tokens: &'static [(u8, &'static str)],
+    /// Tokens of [`TOKENS`] the dialect uses for other keywords.
+    replaced: Option<RangeInclusive<u8>>,
+    extended: &'static [(u8, u8, &'static str)],
+    /// Token of `ELSE` at the start of a line, which belongs to a multi-line
+    /// `IF`.
+    line_else: Option<u8>,
+    /// Whether text is stored as UTF-8 rather than Latin-1.
+    utf8: bool,
+}
+
+const ACORN: Dialect = Dialect { tokens: &[], replaced: None, extended: EXTENDED_TOKENS, line_else: Some(0xCC), utf8: false };
+const BBC: Dialect = Dialect { tokens: BBC_TOKENS, replaced: Some(0xC6..=0xCE), extended: &[], line_else: None, utf8: true };
+
+impl Dialect {
+    fn tokens(&self) -> impl Iterator<Item = (u8, &'static str)> + '_ {
+        let shared = TOKENS.iter().filter(|(t, _)| !self.replaced.as_ref().is_some_and(|r| r.contains(t)));
+        self.tokens.iter().chain(shared).copied()
+    }
+
+    fn keyword(&self, token: u8) -> Option<&'static str> {
+        self.tokens().find(|(t, _)| *t == token).map(|(_, k)| k)
+    }
+
+    fn is_token(&self, byte: u8) -> bool {
+        byte >= 0x7F || self.tokens.iter().any(|(t, _)| *t == byte)
+    }
+
+    /// Appends the bytes of `text` as stored in a tokenised line.
+    fn encode(&self, text: &str, bytes: &mut Vec<u8>) -> Result<(), InterpreterError> {
+        if !self.utf8 {
+            return latin1(text, bytes);
+        }
+
+        match text.find(['\r', '\n']) {
+            Some(i) => Err(InterpreterError::Unrepresentable(text[i..].chars().next().unwrap())),
+            None => {
+                bytes.extend(text.bytes());
+                Ok(())
+            }
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> String {
+        if self.utf8 {
+            String::from_utf8_lossy(bytes).into_owned()
+        } else {
+            bytes.iter().map(|b| *b as char).collect()
+        }
+    }
+}
+
+/// Whether `bytes` look like a tokenised program from a BBC Micro or
+/// RISC OS.
+pub fn is_acorn(bytes: &[u8]) -> bool {
+    bytes.first() == Some(&0x0D)
+}
+
+/// Whether `bytes` look like a `.bbc` file of BBC BASIC for Windows or
+/// BBCSDL: a chain of lines ending in `0x0D`, followed by the end of the
+/// program.
+pub fn is_bbc(bytes: &[u8]) -> bool {
+    let mut rest = bytes;
+
+    loop {
+        match rest {
+            [0x00, 0xFF, 0xFF, ..] => return true,
+            [length, ..] if *length >= 4 && rest.len() >= *length as usize && rest[*length as usize - 1] == 0x0D => {
+                rest = &rest[*length as usize..];
+            }
+            _ => return false
+        }
+    }
+}
+
+/// Source of a program file: plain source as is, a classic listing or a
+/// tokenised program without its line numbers.
+pub fn load_source(bytes: &[u8]) -> Result<String, InterpreterError> {
+    if is_bbc(bytes) || is_acorn(bytes) {
+        return Ok(Listing::load(bytes)?.source());
+    }
+
+    let text = String::from_utf8_lossy(bytes);
+    match Listing::from_numbered(&text) {
+        Some(listing) => Ok(listing.source()),
+        None => Ok(text.into_owned())
+    }
+}
+
+impl Listing {
+    /// Reads a program file in any supported format, see [`load_source`].
+    pub fn load(bytes: &[u8]) -> Result<Listing, InterpreterError> {
+        // A `.bbc` file starts with `0x0D` as well if its first line is 13
+        // bytes long.
+        if is_bbc(bytes) {
+            Listing::from_bbc(bytes)
+        } else if is_acorn(bytes) {
+            Listing::from_acorn(bytes)
+        } else {
+            Ok(Listing::from_source(&String::from_utf8_lossy(bytes)))
+        }
+    }
+
+    /// Reads a tokenised program of a BBC Micro or RISC OS.
+    pub fn from_acorn(bytes: &[u8]) -> Result<Listing, InterpreterError> {
+        let mut listing = Listing::new();
+        let mut rest = bytes;
+
+        loop {
+            match rest {
+                [0x0D, 0xFF, ..] => return Ok(listing),
+                [0x0D, high, low, length, ..] if *length >= 4 && rest.len() >= *length as usize => {
+                    let number = (*high as u32) << 8 | *low as u32;
+                    listing.set_line(number, &detokenise(&rest[4..*length as usize], &ACORN)?);
+                    rest = &rest[*length as usize..];
+                }
+                _ => return Err(InterpreterError::BadProgram)
+            }
+        }
+    }
+
+    /// Tokenises the program for a BBC Micro or RISC OS. Lines may not be
+    /// longer than 251 bytes once tokenised, and line numbers not higher
+    /// than [`MAX_ACORN_LINE_NUMBER`].
+    pub fn to_acorn(&self) -> Result<Vec<u8>, InterpreterError> {
+        let mut bytes = Vec::new();
+
+        for (number, text) in self.lines() {
+            if number > MAX_ACORN_LINE_NUMBER {
+                return Err(InterpreterError::Silly);
+            }
+
+            let line = tokenise(text, &ACORN)?;
+            if line.len() > 251 {
+                return Err(InterpreterError::LineTooLong);
+            }
+
+            bytes.extend([0x0D, (number >> 8) as u8, number as u8, line.len() as u8 + 4]);
+            bytes.extend(line);
+        }
+
+        bytes.extend([0x0D, 0xFF]);
+
+        Ok(bytes)
+    }
+
+    /// Reads a `.bbc` file of BBC BASIC for Windows or BBCSDL. Programs
+    /// written without line numbers store them as 0, their lines are
+    /// numbered 10, 20, 30 and so on.
+    pub fn from_bbc(bytes: &[u8]) -> Result<Listing, InterpreterError> {
+        let mut lines = Vec::new();
+        let mut rest = bytes;
+
+        loop {
+            match rest {
+                [0x00, 0xFF, 0xFF, ..] => break,
+                [length, low, high, ..] if *length >= 4 && rest.len() >= *length as usize && rest[*length as usize - 1] == 0x0D => {
+                    let number = (*high as u32) << 8 | *low as u32;
+                    lines.push((number, detokenise(&rest[3..*length as usize - 1], &BBC)?));
+                    rest = &rest[*length as usize..];
+                }
+                _ => return Err(InterpreterError::BadProgram)
+            }
+        }
+
+        let numbered = lines.first().map(|(n, _)| *n > 0).unwrap_or(true)
+            && lines.windows(2).all(|w| w[0].0 < w[1].0);
+
+        if numbered {
+            let mut listing = Listing::new();
+            for (number, text) in lines {
+                listing.set_line(number, &text);
+            }
+            Ok(listing)
+        } else {
+            let text: Vec<String> = lines.into_iter().map(|(_, t)| t).collect();
+            Ok(Listing::from_source(&text.join("\n")))
+        }
+    }
+
+    /// Tokenises the program as a `.bbc` file for BBC BASIC for Windows or
+    /// BBCSDL. Lines may not be longer than 251 bytes once tokenised.
+    pub fn to_bbc(&self) -> Result<Vec<u8>, InterpreterError> {
+        let mut bytes = Vec::new();
+
+        for (number, text) in self.lines() {
+            let line = tokenise(text, &BBC)?;
+            if line.len() > 251 {
+                return Err(InterpreterError::LineTooLong);
+            }
+
+            bytes.extend([line.len() as u8 + 4, number as u8, (number >> 8) as u8]);
+            bytes.extend(line);
+            bytes.push(0x0D);
+        }
+
+        bytes.extend([0x00, 0xFF, 0xFF]);
+
+        Ok(bytes)
+    }
+}
+
+/// Text of a tokenised line.
+fn detokenise(line: &[u8], dialect: &Dialect) -> Result<String, InterpreterError> {
+    let mut text = Vec::new();
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < line.len() {
+        let byte = line[i];
+        i += 1;
+
+        if byte == b'"' {
+            in_string = !in_string;
+        }
+
+        if in_string || !dialect.is_token(byte) {
+            text.push(byte);
+            continue;
+        }
+
+        if byte == LINE_NUMBER {
+            let encoded = line.get(i..i + 3).ok_or(InterpreterError::BadProgram)?;
+            text.extend(decode_line_number(encoded).to_string().bytes());
+            i += 3;
+        } else if dialect.extended.iter().any(|(f, _, _)| *f == byte) {
+            let second = *line.get(i).ok_or(InterpreterError::BadProgram)?;
+            let (_, _, keyword) = dialect.extended.iter().find(|(f, s, _)| *f == byte && *s == second).ok_or(InterpreterError::BadProgram)?;
+            text.extend(keyword.bytes());
+            i += 1;
+        } else {
+            let keyword = dialect.keyword(byte).ok_or(InterpreterError::BadProgram)?;
+            text.extend(keyword.bytes());
+
+            // The rest of the line is text.
+            if byte == 0xF4 || byte == 0xDC {
+                text.extend(&line[i..]);
+                break;
+            }
+        }
+    }
+
+    Ok(dialect.decode(&text))
+}
+
+/// Tokenised bytes of a line of text.
+fn tokenise(text: &str, dialect: &Dialect) -> Result<Vec<u8>, InterpreterError> {
+    let mut bytes = Vec::new();
+    let mut statement_start = true;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            let end = rest[1..].find('"').map(|i| i + 2).unwrap_or(rest.len());
+            dialect.encode(&rest[..end], &mut bytes)?;
+            rest = &rest[end..];
+            statement_start = false;
+            continue;
+        }
+
+        let token = match keyword(rest, bytes.last(), dialect) {
+            None => {
+                if !c.is_ascii() || dialect.is_token(c as u8) || c == '\r' {
+                    return Err(InterpreterError::Unrepresentable(c));
+                }
+
+                bytes.push(c as u8);
+                rest = &rest[1..];
+
+                if c == ':' {
+                    statement_start = true;
+                } else if c != ' ' {
+                    statement_start = false;
+                }
+                continue;
+            }
+            Some(t) => t
+        };
+
+        let (keyword, token) = token;
+        rest = &rest[keyword.len()..];
+
+        match token {
+            Token::Single(t) => {
+                let t = match STATEMENT_TOKENS.iter().find(|(f, _)| *f == t) {
+                    Some((_, s)) if statement_start => *s,
+                    _ => t
+                };
+                let t = match dialect.line_else {
+                    Some(e) if t == 0x8B && bytes.iter().all(|b| *b == b' ') => e,
+                    _ => t
+                };
+                bytes.push(t);
+            }
+            Token::Extended(first, second) => bytes.extend([first, second]),
+        }
+
+        if keyword == "REM" || keyword == "DATA" {
+            dialect.encode(rest, &mut bytes)?;
+            break;
+        }
+
+        statement_start = keyword == "THEN" || keyword == "ELSE";
+
+        // Names of procedures and functions are never tokenised.
+        if keyword == "PROC" || keyword == "FN" {
+            let name = rest.find(|c| !is_name(c)).unwrap_or(rest.len());
+            bytes.extend(rest[..name].bytes());
+            rest = &rest[name..];
+        }
+
+        if let Some((_, list)) = REFERENCES.iter().find(|(k, _)| *k == keyword) {
+            rest = line_numbers(rest, *list, &mut bytes);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    Single(u8),
+    Extended(u8, u8),
+}
+
+/// The longest keyword `text` starts with and its token. Keywords are only
+/// tokenised as whole words, except those ending in `(` or `$` and `FN` and
+/// `PROC`, which are followed by names.
+fn keyword(text: &str, before: Option<&u8>, dialect: &Dialect) -> Option<(&'static str, Token)> {
+    if before.map(|b| is_name(*b as char)).unwrap_or(false) {
+        return None;
+    }
+
+    let single = dialect.tokens().map(|(t, k)| (k, Token::Single(t)));
+    let extended = dialect.extended.iter().map(|(f, s, k)| (*k, Token::Extended(*f, *s)));
+
+    let (keyword, token) = single.chain(extended)
+        .filter(|(k, _)| text.starts_with(k))
+        .max_by_key(|(k, _)| k.len())?;
+
+    let whole = match text[keyword.len()..].chars().next() {
+        None => true,
+        Some(c) => !is_name(c)
+    };
+
+    if whole || keyword.ends_with(['(', '$']) || keyword == "FN" || keyword == "PROC" {
+        Some((keyword, token))
+    } else {
+        None
+    }
+}
+
+fn is_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '`'
+}
+
+/// Tokenises the line numbers at the start of `text`, returning what
+/// follows them.
+fn line_numbers<'a>(mut text: &'a str, list: bool, bytes: &mut Vec<u8>) -> &'a str {
+    loop {
+        let number = text.trim_start_matches(' ');
+        let digits = number.find(|c: char| !c.is_ascii_digit()).unwrap_or(number.len());
+
+        let n: u32 = match number[..digits].parse() {
+            Ok(n) if n <= 0xFFFF => n,
+            _ => return text
+        };
+
+        bytes.extend(std::iter::repeat_n(b' ', text.len() - number.len()));
+        bytes.push(LINE_NUMBER);
+        bytes.extend(encode_line_number(n));
+        text = &number[digits..];
+
+        let after = text.trim_start_matches(' ');
+        match after.strip_prefix(',') {
+            Some(next) if list => {
+                bytes.extend(std::iter::repeat_n(b' ', text.len() - after.len()));
+                bytes.push(b',');
+                text = next;
+            }
+            _ => return text
+        }
+    }
+}
+
+/// Three bytes that can't be mistaken for tokens or the end of a line.
+fn encode_line_number(n: u32) -> [u8; 3] {
+    let (low, high) = ((n & 0xFF) as u8, (n >> 8) as u8);
+
+    [((low & 0xC0) >> 2 | (high & 0xC0) >> 4) ^ 0x54, (low & 0x3F) | 0x40, (high & 0x3F) | 0x40]
+}
+
+fn decode_line_number(encoded: &[u8]) -> u32 {
+    let first = encoded[0] ^ 0x54;
+    let low = (first & 0x30) << 2 | (encoded[1] & 0x3F);
+    let high = (first & 0x0C) << 4 | (encoded[2] & 0x3F);
+
+    (high as u32) << 8 | low as u32
+}
+
+fn latin1(text: &str, bytes: &mut Vec<u8>) -> Result<(), InterpreterError> {
+    for c in text.chars() {
+        match u8::try_from(c) {
+            Ok(b) if b != 0x0D => bytes.push(b),
+            _ => return Err(InterpreterError::Unrepresentable(c))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(text: &str) -> Listing {
+        Listing::from_numbered(text).unwrap()
+    }
+
+    #[test]
+    fn line_numbers_are_encoded() {
+        for n in [0, 10, 255, 256, 1000, 32767, 65279] {
+            let encoded = encode_line_number(n);
+            assert!(encoded.iter().all(|b| (0x40..0x80).contains(b)));
+            assert_eq!(decode_line_number(&encoded), n);
+        }
+
+        // From a BBC Micro: GOTO 10.
+        assert_eq!(encode_line_number(10), [0x54, 0x4A, 0x40]);
+    }
+
+    #[test]
+    fn lines_are_tokenised() {
+        let listing = listing("10 PRINT \"AND\"; TOTAL: PROCEND\n20 IF x THEN 10 ELSE PRINT\n30 TIME = TIME + 1\n40 REM PRINT");
+
+        let bytes = listing.to_acorn().unwrap();
+        assert_eq!(&bytes[..9], &[0x0D, 0x00, 0x0A, 0x18, 0xF1, b' ', b'"', b'A', b'N']);
+        assert_eq!(Listing::from_acorn(&bytes).unwrap(), listing);
+    }
+
+    #[test]
+    fn dialects_have_their_own_tokens() {
+        let listing = listing("10 WHILE x\n20 EXIT WHILE\n30 ENDWHILE\n40 PRINT \"·\"");
+
+        let bytes = listing.to_bbc().unwrap();
+        assert_eq!(&bytes[..6], &[0x07, 0x0A, 0x00, 0xC7, b' ', b'x']);
+        assert_eq!(&bytes[7..11], &[0x07, 0x14, 0x00, 0x10]);
+        assert!(bytes.windows(2).any(|w| w == "·".as_bytes()));
+        assert_eq!(Listing::from_bbc(&bytes).unwrap(), listing);
+
+        let bytes = listing.to_acorn().unwrap();
+        assert_eq!(&bytes[4..6], &[0xC8, 0x95]);
+        assert_eq!(Listing::from_acorn(&bytes).unwrap(), listing);
+    }
+}
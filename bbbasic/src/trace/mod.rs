@@ -0,0 +1,64 @@
+use crate::host::Host;
+
+/// What `TRACE` prints while a program runs: the number of every line
+/// executed, like `[12] `, and the procedures entered and left.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trace {
+    /// Lines below this one are printed.
+    below: usize,
+    procedures: bool,
+}
+
+impl Trace {
+    /// `TRACE OFF`
+    pub fn off() -> Trace {
+        Trace::default()
+    }
+
+    /// `TRACE ON`, prints every line.
+    pub fn lines(self) -> Trace {
+        self.lines_below(usize::MAX)
+    }
+
+    /// `TRACE n`, prints the lines below `line`.
+    pub fn lines_below(mut self, line: usize) -> Trace {
+        self.below = line;
+        self
+    }
+
+    /// `TRACE PROC`, prints `[PROCname] ` when a procedure is called and
+    /// `[ENDPROC] ` when it returns.
+    pub fn procedures(mut self) -> Trace {
+        self.procedures = true;
+        self
+    }
+
+    pub fn is_off(&self) -> bool {
+        *self == Trace::off()
+    }
+
+    pub(crate) fn line(&self, line: usize, host: &mut dyn Host) -> std::io::Result<()> {
+        if line < self.below {
+            host.trace(&format!("[{}] ", line))?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn enter(&self, procedure: &str, host: &mut dyn Host) -> std::io::Result<()> {
+        if self.procedures {
+            host.trace(&format!("[PROC{}] ", procedure))?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn leave(&self, host: &mut dyn Host) -> std::io::Result<()> {
+        if self.procedures {
+            host.trace("[ENDPROC] ")?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use crate::error::InterpreterError;
 use crate::error::InterpreterError::TypeMismatch;
-use crate::scope::{Byte, Float, Integer};
+use crate::scope::{Byte, DataType, Float, Integer, IntegerModel};
 
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     String(String),
     Integer(Integer),
@@ -11,12 +14,61 @@ pub enum Value {
     Boolean(bool)
 }
 
+/// Formats the value like `PRINT` does.
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", s),
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Byte(b) => write!(f, "{}", b),
+            Value::Boolean(b) => write!(f, "{}", b)
+        }
+    }
+}
+
+impl From<Integer> for Value {
+    fn from(i: Integer) -> Value {
+        Value::Integer(i)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(i: i32) -> Value {
+        Value::Integer(i as Integer)
+    }
+}
+
+impl From<Float> for Value {
+    fn from(f: Float) -> Value {
+        Value::Float(f)
+    }
+}
+
+impl From<Byte> for Value {
+    fn from(b: Byte) -> Value {
+        Value::Byte(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+}
+
 impl Value {
 
     #[allow(dead_code)]
     pub fn as_int(&self) -> Option<Integer> {
         match self {
-            Value::String(s) => Some(s.parse::<Integer>().unwrap()),
+            Value::String(s) => s.parse::<Integer>().ok(),
             Value::Integer(i) => Some(*i),
             Value::Float(f) => Some(*f as Integer),
             Value::Boolean(_) => None,
@@ -32,226 +84,210 @@ impl Value {
         }
     }
 
-    pub fn eq(&self, rhs:&Value) -> Result<bool, InterpreterError> {
-        match &self {
-            Value::Integer(i) => match rhs {
-                Value::Integer(rhs) => Ok(*i == *rhs),
-                Value::Float(rhs) => Ok(*i as Float == *rhs),
-                Value::Byte(rhs) => Ok(*i == *rhs as Integer),
-                _ => Err(TypeMismatch)
-            }
-            Value::Float(f) => match rhs {
-                Value::Integer(rhs) => Ok(*f == *rhs as Float),
-                Value::Float(rhs) => Ok(*f == *rhs),
-                Value::Byte(rhs) => Ok(*f == *rhs as Float),
-                Value::String(_) => Err(TypeMismatch),
-                Value::Boolean(_) => Err(TypeMismatch),
-            }
-            Value::String(lhs) => match rhs {
-                Value::String(rhs) => Ok(lhs == rhs),
-                _ => Err(TypeMismatch)
-            },
-            Value::Byte(lhs) => match rhs {
-                Value::Integer(rhs) => Ok(*lhs == *rhs as Byte),
-                Value::Float(rhs) => Ok(*lhs == *rhs as Byte),
-                Value::Byte(rhs) => Ok(*lhs == *rhs),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch),
-            },
-            Value::Boolean(_) => Err(TypeMismatch),
+    #[inline]
+    pub fn to_float(&self) -> Result<Float, InterpreterError> {
+        match self {
+            Value::Integer(i) => Ok(*i as Float),
+            Value::Float(f) => Ok(*f),
+            Value::Byte(b) => Ok(*b as Float),
+            _ => Err(TypeMismatch)
         }
     }
 
-    pub fn gt(&self, rhs:&Value) -> Result<bool, InterpreterError> {
-        match &self {
-            Value::Integer(i) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*i > *rhs),
-                Value::Float(rhs) => Ok(*i as Float > *rhs),
-                Value::Byte(rhs) => Ok(*i > *rhs as Integer),
-
-                Value::Boolean(_) => Err(TypeMismatch)
-            }
-            Value::Float(f) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*f > *rhs as Float),
-                Value::Float(rhs) => Ok(*f > *rhs),
-                Value::Byte(rhs) => Ok(*f > *rhs as Float),
-                Value::Boolean(_) => Err(TypeMismatch)
+    /// Floats are truncated, those out of the integer range are too big.
+    #[inline]
+    pub fn to_integer(&self) -> Result<Integer, InterpreterError> {
+        match self {
+            Value::Integer(i) => Ok(*i),
+            Value::Float(f) => {
+                if *f >= -(Integer::MIN as Float) || *f < Integer::MIN as Float || f.is_nan() {
+                    return Err(InterpreterError::NumberTooBig);
+                }
+
+                Ok(*f as Integer)
             }
-            Value::Byte(lhs) => match rhs {
-                Value::Integer(rhs) => Ok(*lhs > *rhs as Byte),
-                Value::Float(rhs) => Ok(*lhs > *rhs as Byte),
-                Value::Byte(rhs) => Ok(*lhs > *rhs),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch),
-            },
-
-            Value::String(_) => Err(TypeMismatch),
-            Value::Boolean(_) => Err(TypeMismatch)
+            Value::Byte(b) => Ok(*b as Integer),
+            _ => Err(TypeMismatch)
         }
     }
 
-    pub fn ge(&self, rhs:&Value) -> Result<bool, InterpreterError> {
-        match &self {
-            Value::Integer(i) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*i >= *rhs),
-                Value::Byte(rhs) => Ok(*i >= *rhs as Integer),
-                Value::Float(rhs) => Ok(*i as Float >= *rhs),
-                Value::Boolean(_) => Err(TypeMismatch)
-            }
-            Value::Float(f) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*f >= *rhs as Float),
-                Value::Byte(rhs) => Ok(*f >= *rhs as Float),
-                Value::Float(rhs) => Ok(*f >= *rhs),
-                Value::Boolean(_) => Err(TypeMismatch)
-            }
-            Value::Byte(lhs) => match rhs {
-                Value::Integer(rhs) => Ok(*lhs >= *rhs as Byte),
-                Value::Float(rhs) => Ok(*lhs >= *rhs as Byte),
-                Value::Byte(rhs) => Ok(*lhs >= *rhs),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch),
-            },
-
-            Value::String(_) => Err(TypeMismatch),
-            Value::Boolean(_) => Err(TypeMismatch)
+    pub fn to_byte(&self) -> Result<Byte, InterpreterError> {
+        match self {
+            Value::Integer(i) => Ok(*i as Byte),
+            Value::Float(f) => Ok(*f as Integer as Byte),
+            Value::Byte(b) => Ok(*b),
+            _ => Err(TypeMismatch)
         }
     }
 
-    pub fn lt(&self, rhs:&Value) -> Result<bool, InterpreterError> {
-        match &self {
-            Value::Integer(i) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*i < *rhs),
-                Value::Byte(rhs) => Ok(*i < *rhs as Integer),
-                Value::Float(rhs) => Ok(*i < *rhs as Integer),
-                Value::Boolean(_) => Err(TypeMismatch)
-            }
-            Value::Float(f) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*f < *rhs as Float),
-                Value::Byte(rhs) => Ok(*f < *rhs as Float),
-                Value::Float(rhs) => Ok(*f < *rhs),
-                Value::Boolean(_) => Err(TypeMismatch)
+    /// Converts the value for a variable of the given type. Integers have to
+    /// fit the integer model, bytes wrap around.
+    pub fn convert(&self, data_type: DataType, model: IntegerModel) -> Result<Value, InterpreterError> {
+        match data_type {
+            DataType::Float => Ok(Value::Float(self.to_float()?)),
+            DataType::Integer => Ok(Value::Integer(model.check(self.to_integer()?)?)),
+            DataType::Byte => Ok(Value::Byte(self.to_byte()?)),
+            DataType::String => match self {
+                Value::String(_) => Ok(self.clone()),
+                _ => Err(TypeMismatch)
             }
-            Value::Byte(lhs) => match rhs {
-                Value::Integer(rhs) => Ok(*lhs < *rhs as Byte),
-                Value::Float(rhs) => Ok(*lhs < *rhs as Byte),
-                Value::Byte(rhs) => Ok(*lhs < *rhs),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch),
-            },
-
-            Value::String(_) => Err(TypeMismatch),
-            Value::Boolean(_) => Err(TypeMismatch)
         }
     }
 
-    pub fn le(&self, rhs:&Value) -> Result<bool, InterpreterError> {
-        match &self {
-            Value::Integer(i) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*i <= *rhs),
-                Value::Byte(rhs) => Ok(*i <= *rhs as Integer),
-                Value::Float(rhs) => Ok(*i <= *rhs as Integer),
-                Value::Boolean(_) => Err(TypeMismatch)
+    /// Orders two values. Numbers are compared after promotion, strings
+    /// only with strings.
+    fn compare(&self, rhs: &Value) -> Result<Option<Ordering>, InterpreterError> {
+        match (self, rhs) {
+            (Value::String(l), Value::String(r)) => Ok(l.partial_cmp(r)),
+            (Value::String(_), _) | (_, Value::String(_)) => Err(TypeMismatch),
+            _ => match promote(self, rhs)? {
+                Operands::Integers(l, r) => Ok(l.partial_cmp(&r)),
+                Operands::Floats(l, r) => Ok(l.partial_cmp(&r))
             }
-            Value::Float(f) => match rhs {
-                Value::String(_) => Err(TypeMismatch),
-                Value::Integer(rhs) => Ok(*f <= *rhs as Float),
-                Value::Byte(rhs) => Ok(*f <= *rhs as Float),
-                Value::Float(rhs) => Ok(*f <= *rhs),
-                Value::Boolean(_) => Err(TypeMismatch)
-            }
-            Value::Byte(lhs) => match rhs {
-                Value::Integer(rhs) => Ok(*lhs <= *rhs as Byte),
-                Value::Float(rhs) => Ok(*lhs <= *rhs as Byte),
-                Value::Byte(rhs) => Ok(*lhs <= *rhs),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch),
-            },
-
-            Value::String(_) => Err(TypeMismatch),
-            Value::Boolean(_) => Err(TypeMismatch)
         }
     }
 
-  /*  pub fn add(&self, rhs: &Value) -> Result<Value, InterpreterError> {
-        match &self {
-            Value::Integer(lhs) => match  rhs{
-                Value::Integer(r) => Ok(Value::Integer(*lhs + *r)),
-                Value::Float(f) => Ok(Value::Float(*lhs as Float + *f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Float(lhs) => match rhs {
-                Value::Integer(i) => Ok(Value::Float(*lhs + *i as Float)),
-                Value::Float(f) => Ok(Value::Float(*lhs + *f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Boolean(_) => Err(InterpreterError::OperationUnsupported),
-            Value::String(_) => Err(InterpreterError::OperationUnsupported)
+    pub fn eq(&self, rhs:&Value) -> Result<bool, InterpreterError> {
+        Ok(self.compare(rhs)? == Some(Ordering::Equal))
+    }
+
+    pub fn ne(&self, rhs:&Value) -> Result<bool, InterpreterError> {
+        Ok(!self.eq(rhs)?)
+    }
+
+    pub fn gt(&self, rhs:&Value) -> Result<bool, InterpreterError> {
+        Ok(self.compare(rhs)? == Some(Ordering::Greater))
+    }
+
+    pub fn ge(&self, rhs:&Value) -> Result<bool, InterpreterError> {
+        Ok(matches!(self.compare(rhs)?, Some(Ordering::Greater | Ordering::Equal)))
+    }
+
+    pub fn lt(&self, rhs:&Value) -> Result<bool, InterpreterError> {
+        Ok(self.compare(rhs)? == Some(Ordering::Less))
+    }
+
+    pub fn le(&self, rhs:&Value) -> Result<bool, InterpreterError> {
+        Ok(matches!(self.compare(rhs)?, Some(Ordering::Less | Ordering::Equal)))
+    }
+
+    pub fn add(&self, rhs: &Value, model: IntegerModel) -> Result<Value, InterpreterError> {
+        match promote(self, rhs)? {
+            Operands::Integers(l, r) => Ok(integer_result(model, l, r, Integer::checked_add, i32::wrapping_add, |l, r| l + r)),
+            Operands::Floats(l, r) => Ok(Value::Float(l + r))
         }
     }
 
-    pub fn sub(&self, rhs: &Value) -> Result<Value, InterpreterError> {
-        match &self {
-            Value::Integer(lhs) => match  rhs{
-                Value::Integer(r) => Ok(Value::Integer(*lhs - *r)),
-                Value::Float(f) => Ok(Value::Float(*lhs as Float - f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Float(lhs) => match rhs {
-                Value::Integer(i) => Ok(Value::Float(lhs - *i as Float)),
-                Value::Float(f) => Ok(Value::Float(lhs - f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Boolean(_) => Err(InterpreterError::OperationUnsupported),
-            Value::String(_) => Err(InterpreterError::OperationUnsupported)
+    pub fn sub(&self, rhs: &Value, model: IntegerModel) -> Result<Value, InterpreterError> {
+        match promote(self, rhs)? {
+            Operands::Integers(l, r) => Ok(integer_result(model, l, r, Integer::checked_sub, i32::wrapping_sub, |l, r| l - r)),
+            Operands::Floats(l, r) => Ok(Value::Float(l - r))
         }
     }
 
-    pub fn mul(&self, rhs: &Value) -> Result<Value, InterpreterError> {
-        match &self {
-            Value::Integer(lhs) => match  rhs{
-                Value::Integer(r) => Ok(Value::Integer(lhs * r)),
-                Value::Float(f) => Ok(Value::Float(*lhs as Float* f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Float(lhs) => match rhs {
-                Value::Integer(i) => Ok(Value::Float(*lhs as Float * *i as Float)),
-                Value::Float(f) => Ok(Value::Float(lhs * f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
+    pub fn mul(&self, rhs: &Value, model: IntegerModel) -> Result<Value, InterpreterError> {
+        match promote(self, rhs)? {
+            Operands::Integers(l, r) => {
+                match l.checked_mul(r) {
+                    Some(i) if model.contains(i) => Ok(Value::Integer(i)),
+                    _ => Ok(Value::Float(l as Float * r as Float))
+                }
             }
-            Value::Boolean(_) => Err(InterpreterError::OperationUnsupported),
-            Value::String(_) => Err(InterpreterError::OperationUnsupported)
+            Operands::Floats(l, r) => Ok(Value::Float(l * r))
         }
     }
 
+    /// `/` always divides as floats, like in BBC BASIC.
     pub fn div(&self, rhs: &Value) -> Result<Value, InterpreterError> {
-        match &self {
-            Value::Integer(lhs) => match  rhs{
-                Value::Integer(r) => Ok(Value::Integer(lhs / r)),
-                Value::Float(f) => Ok(Value::Float(*lhs as Float / f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Float(lhs) => match rhs {
-                Value::Integer(i) => Ok(Value::Float(lhs / *i as Float)),
-                Value::Float(f) => Ok(Value::Float(lhs / f)),
-                Value::Boolean(_) => Err(TypeMismatch),
-                Value::String(_) => Err(TypeMismatch)
-            }
-            Value::Boolean(_) => Err(InterpreterError::OperationUnsupported),
-            Value::String(_) => Err(InterpreterError::OperationUnsupported)
+        let l = self.to_float()?;
+        let r = rhs.to_float()?;
+
+        if r == 0.0 {
+            return Err(InterpreterError::DivisionByZero);
+        }
+
+        Ok(Value::Float(l / r))
+    }
+}
+
+/// Operands of an arithmetic operation or a comparison, promoted to a common
+/// type.
+enum Operands {
+    Integers(Integer, Integer),
+    Floats(Float, Float),
+}
+
+/// Result of adding or subtracting two integers. Acorn BASIC wraps around
+/// at 32 bits, BBC BASIC for SDL continues with floats. Operands outside the
+/// integer range, like large literals, are floats to begin with.
+fn integer_result(model: IntegerModel, l: Integer, r: Integer,
+                  checked: fn(Integer, Integer) -> Option<Integer>,
+                  wrapping: fn(i32, i32) -> i32,
+                  float: fn(Float, Float) -> Float) -> Value {
+    if !model.contains(l) || !model.contains(r) {
+        return Value::Float(float(l as Float, r as Float));
+    }
+
+    match model {
+        IntegerModel::Acorn => Value::Integer(wrapping(l as i32, r as i32) as Integer),
+        IntegerModel::BbcSdl => match checked(l, r) {
+            Some(i) => Value::Integer(i),
+            None => Value::Float(float(l as Float, r as Float))
         }
-    }*/
-}
\ No newline at end of file
+    }
+}
+
+/// Bytes are promoted to integers, integers to floats if the other side is
+/// a float.
+#[inline]
+fn promote(l: &Value, r: &Value) -> Result<Operands, InterpreterError> {
+    match (l, r) {
+        (Value::Float(l), r) => Ok(Operands::Floats(*l, r.to_float()?)),
+        (l, Value::Float(r)) => Ok(Operands::Floats(l.to_float()?, *r)),
+        (l, r) => Ok(Operands::Integers(l.to_integer()?, r.to_integer()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_promotes_its_operands() {
+        let two = Value::Integer(2);
+        let sdl = IntegerModel::BbcSdl;
+
+        assert_eq!(Value::Byte(200).add(&Value::Byte(100), sdl).unwrap(), Value::Integer(300));
+        assert_eq!(two.mul(&Value::Float(1.5), sdl).unwrap(), Value::Float(3.0));
+        assert_eq!(Value::Integer(7).div(&two).unwrap(), Value::Float(3.5));
+        assert_eq!(Value::Integer(Integer::MAX).add(&two, sdl).unwrap(), Value::Float(Integer::MAX as Float + 2.0));
+        assert!(matches!(two.div(&Value::Byte(0)), Err(InterpreterError::DivisionByZero)));
+        assert!(matches!(Value::Float(1e19).to_integer(), Err(InterpreterError::NumberTooBig)));
+        assert!(matches!(two.add(&Value::String("a".to_string()), sdl), Err(TypeMismatch)));
+    }
+
+    #[test]
+    fn acorn_integers_are_32_bit() {
+        let acorn = IntegerModel::Acorn;
+        let max = Value::Integer(i32::MAX as Integer);
+
+        assert_eq!(max.add(&Value::Integer(1), acorn).unwrap(), Value::Integer(i32::MIN as Integer));
+        assert_eq!(Value::Integer(i32::MIN as Integer).sub(&Value::Byte(1), acorn).unwrap(), max);
+        assert_eq!(max.mul(&Value::Integer(2), acorn).unwrap(), Value::Float(i32::MAX as Float * 2.0));
+        assert_eq!(Value::Integer(3_000_000_000).add(&Value::Integer(1), acorn).unwrap(), Value::Float(3_000_000_001.0));
+
+        assert!(matches!(Value::Float(3e9).convert(DataType::Integer, acorn), Err(InterpreterError::NumberTooBig)));
+        assert_eq!(Value::Float(3e9).convert(DataType::Integer, IntegerModel::BbcSdl).unwrap(), Value::Integer(3_000_000_000));
+        assert_eq!(Value::Integer(300).convert(DataType::Byte, acorn).unwrap(), Value::Byte(44));
+        assert_eq!(Value::Integer(-1).convert(DataType::Byte, acorn).unwrap(), Value::Byte(255));
+    }
+
+    #[test]
+    fn comparisons_promote_their_operands() {
+        assert!(Value::Integer(2).lt(&Value::Float(2.5)).unwrap());
+        assert!(!Value::Byte(255).gt(&Value::Integer(256)).unwrap());
+        assert!(Value::Float(3.0).eq(&Value::Byte(3)).unwrap());
+        assert!(Value::String("a".to_string()).lt(&Value::String("b".to_string())).unwrap());
+        assert!(matches!(Value::String("1".to_string()).eq(&Value::Integer(1)), Err(TypeMismatch)));
+    }
+}
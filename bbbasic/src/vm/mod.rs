@@ -0,0 +1,566 @@
+use std::io::ErrorKind;
+use crate::compiler::{Address, Chunk, Op, Source};
+use crate::error::InterpreterError;
+use crate::host::{Channel, Host};
+use crate::interpreter::ExecutionResult;
+use crate::native::Native;
+use crate::trace::Trace;
+use crate::scope::{Byte, DataType, Float, Integer, Scope, Slot};
+use crate::value::Value;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum ForBounds {
+    Integer { target: Integer, step: Integer },
+    Float { target: Float, step: Float },
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ForFrame {
+    slot: Slot,
+    bounds: ForBounds,
+    body: Address,
+}
+
+/// A running procedure.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallFrame {
+    /// Index of the procedure in [`Chunk::procedures`].
+    pub procedure: usize,
+    /// Where the program continues once the procedure returns.
+    pub return_address: Address,
+    /// FOR loops started before the call.
+    fors: usize,
+}
+
+/// Why [`Vm::resume`] returned.
+#[derive(Clone, Copy, Debug)]
+pub enum Status {
+    /// The program ended.
+    Done(ExecutionResult),
+    /// The step budget was used up.
+    Yielded,
+    /// The host has no input yet.
+    WaitingForInput,
+}
+
+/// Stack machine running a compiled [`Chunk`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vm {
+    pc: Address,
+    stack: Vec<Value>,
+    fors: Vec<ForFrame>,
+    calls: Vec<CallFrame>,
+    /// Channels the program opened and hasn't closed yet.
+    #[cfg_attr(feature = "serde", serde(default))]
+    channels: Vec<Channel>,
+}
+
+impl Vm {
+    pub fn new() -> Vm {
+        Vm {
+            pc: 0,
+            stack: Vec::with_capacity(16),
+            fors: Vec::new(),
+            calls: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    /// Address of the next instruction.
+    pub fn pc(&self) -> Address {
+        self.pc
+    }
+
+    /// Running procedures, the innermost last.
+    pub fn calls(&self) -> &[CallFrame] {
+        &self.calls
+    }
+
+    /// Files opened by the program and not closed yet.
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Whether every address the VM continues at lies in `chunk`, which a
+    /// restored snapshot has to be checked for.
+    #[cfg(feature = "serde")]
+    pub(crate) fn fits(&self, chunk: &Chunk) -> bool {
+        let fits = |address: Address| address < chunk.code.len();
+
+        fits(self.pc)
+            && self.fors.iter().all(|f| fits(f.body))
+            && self.calls.iter().all(|c| fits(c.return_address) && c.procedure < chunk.procedures.len() && c.fors <= self.fors.len())
+    }
+
+    fn pop(&mut self) -> Result<Value, InterpreterError> {
+        self.stack.pop().ok_or(InterpreterError::Unreachable)
+    }
+
+    fn pop_float(&mut self) -> Result<Float, InterpreterError> {
+        self.pop()?.to_float()
+    }
+
+    fn pop_integer(&mut self) -> Result<Integer, InterpreterError> {
+        self.pop()?.to_integer()
+    }
+
+    fn pop_bool(&mut self) -> Result<bool, InterpreterError> {
+        self.pop()?.as_bool()
+    }
+
+    fn pop_string(&mut self) -> Result<String, InterpreterError> {
+        match self.pop()? {
+            Value::String(s) => Ok(s),
+            _ => Err(InterpreterError::TypeMismatch)
+        }
+    }
+
+    fn pop_integers<const N: usize>(&mut self) -> Result<[Integer; N], InterpreterError> {
+        let mut result = [0; N];
+
+        for r in result.iter_mut().rev() {
+            *r = self.pop_integer()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Calls a native with the topmost `arguments` values of the stack.
+    fn call_native(&mut self, native: &Native, arguments: usize, scope: &Scope) -> Result<Value, InterpreterError> {
+        let first = self.stack.len().checked_sub(arguments).ok_or(InterpreterError::Unreachable)?;
+        let values = self.stack.split_off(first);
+
+        native.call(values, scope.integer_model())
+    }
+
+    /// Applies `operation` to the value on top of the stack and the one at
+    /// `source`, replacing the former.
+    #[inline(always)]
+    fn binary(&mut self, source: Source, chunk: &Chunk, scope: &Scope, operation: impl Fn(&Value, &Value) -> Result<Value, InterpreterError>) -> Result<(), InterpreterError> {
+        let popped;
+        let r = match source {
+            Source::Stack => {
+                popped = self.pop()?;
+                &popped
+            }
+            Source::Slot(slot) => scope.get(slot)?,
+            Source::Const(c) => &chunk.constants[c]
+        };
+
+        let l = self.stack.last_mut().ok_or(InterpreterError::Unreachable)?;
+        *l = operation(l, r)?;
+
+        Ok(())
+    }
+
+    /// Steps the innermost FOR loop, see [`Op::ForNext`].
+    #[inline(always)]
+    fn for_next(&mut self, pc: &mut Address, scope: &mut Scope) -> Result<(), InterpreterError> {
+        let frame = *self.fors.last().ok_or(InterpreterError::Unreachable)?;
+        let current = scope.get(frame.slot)?;
+
+        // Like the tree-walker, the variable keeps its last value once the
+        // loop is completed.
+        let next = match frame.bounds {
+            ForBounds::Integer { target, step } => {
+                let next = current.to_integer()?.checked_add(step).ok_or(InterpreterError::NumberTooBig)?;
+                let next = scope.integer_model().check(next)?;
+                if next > target { None } else { Some(Value::Integer(next)) }
+            }
+            ForBounds::Float { target, step } => {
+                let next = current.to_float()? + step;
+                if next > target { None } else { Some(Value::Float(next)) }
+            }
+        };
+
+        match next {
+            None => {
+                self.fors.pop();
+            }
+            Some(v) => {
+                scope.set(frame.slot, v);
+                *pc = frame.body;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the chunk until it ends or fails. Variables live in `scope`.
+    /// A host that can't wait for input fails with
+    /// [`WouldBlock`](std::io::ErrorKind::WouldBlock).
+    pub fn run(&mut self, chunk: &Chunk, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+        match self.resume(chunk, scope, host, None)? {
+            Status::Done(result) => Ok(result),
+            _ => Err(InterpreterError::Io(ErrorKind::WouldBlock.into()))
+        }
+    }
+
+    /// Continues running the chunk where it stopped, for at most `budget`
+    /// steps. When the host has no input yet the VM stops in front of the
+    /// `INPUT`, `GET` or `INKEY`, which is executed again on the next call.
+    /// Other instructions have already taken their operands, their
+    /// `WouldBlock` is an error like any other.
+    pub fn resume(&mut self, chunk: &Chunk, scope: &mut Scope, host: &mut dyn Host, budget: Option<u64>) -> Result<Status, InterpreterError> {
+        match self.execute(chunk, scope, host, budget) {
+            Err(InterpreterError::Io(e)) if e.kind() == ErrorKind::WouldBlock && self.reads_input(chunk) => {
+                self.pc -= 1;
+                Ok(Status::WaitingForInput)
+            }
+            result => result
+        }
+    }
+
+    /// Whether the instruction that just ran waits for input, and can be
+    /// retried.
+    fn reads_input(&self, chunk: &Chunk) -> bool {
+        matches!(self.pc.checked_sub(1).and_then(|pc| chunk.code.get(pc)), Some(Op::Input(..) | Op::Get | Op::Inkey))
+    }
+
+    fn execute(&mut self, chunk: &Chunk, scope: &mut Scope, host: &mut dyn Host, budget: Option<u64>) -> Result<Status, InterpreterError> {
+        // The address is kept apart from the VM while running, where it
+        // can stay in a register.
+        let mut pc = self.pc;
+        let status = self.dispatch(&mut pc, chunk, scope, host, budget);
+        self.pc = pc;
+
+        status
+    }
+
+    #[inline(always)]
+    fn dispatch(&mut self, pc: &mut Address, chunk: &Chunk, scope: &mut Scope, host: &mut dyn Host, mut budget: Option<u64>) -> Result<Status, InterpreterError> {
+        // Only the TRACE statements change this while running.
+        let mut instrumented = scope.instrumented();
+
+        loop {
+            let op = chunk.code[*pc];
+            *pc += 1;
+
+            match op {
+                Op::Const(c) => self.stack.push(chunk.constants[c].clone()),
+
+                Op::Load(slot) => self.stack.push(scope.get(slot)?.clone()),
+
+                Op::Store(slot) => scope.set(slot, self.pop()?),
+
+                Op::Assign(slot, data_type) => {
+                    let model = scope.integer_model();
+
+                    // Most values already have the type of their variable.
+                    let v = match (data_type, self.pop()?) {
+                        (DataType::Float, v @ Value::Float(_)) => v,
+                        (DataType::Integer, v @ Value::Integer(i)) if model.contains(i) => v,
+                        (data_type, v) => v.convert(data_type, model)?
+                    };
+                    scope.set(slot, v);
+                }
+
+                Op::Add(r) => self.binary(r, chunk, scope, |l, r| l.add(r, scope.integer_model()))?,
+                Op::Sub(r) => self.binary(r, chunk, scope, |l, r| l.sub(r, scope.integer_model()))?,
+                Op::Mul(r) => self.binary(r, chunk, scope, |l, r| l.mul(r, scope.integer_model()))?,
+                Op::Div(r) => self.binary(r, chunk, scope, Value::div)?,
+
+                Op::Eq(r) => self.binary(r, chunk, scope, |l, r| l.eq(r).map(Value::Boolean))?,
+                Op::Ne(r) => self.binary(r, chunk, scope, |l, r| l.ne(r).map(Value::Boolean))?,
+                Op::Lt(r) => self.binary(r, chunk, scope, |l, r| l.lt(r).map(Value::Boolean))?,
+                Op::Le(r) => self.binary(r, chunk, scope, |l, r| l.le(r).map(Value::Boolean))?,
+                Op::Gt(r) => self.binary(r, chunk, scope, |l, r| l.gt(r).map(Value::Boolean))?,
+                Op::Ge(r) => self.binary(r, chunk, scope, |l, r| l.ge(r).map(Value::Boolean))?,
+
+                Op::Jump(to) => *pc = to,
+                Op::JumpIfFalse(to) => {
+                    if !self.pop_bool()? {
+                        *pc = to;
+                    }
+                }
+                Op::JumpIfTrue(to) => {
+                    if self.pop_bool()? {
+                        *pc = to;
+                    }
+                }
+
+                Op::ForInit(slot, kind) => {
+                    let bounds = match kind {
+                        DataType::Integer => {
+                            let step = self.pop_integer()?;
+                            let target = self.pop_integer()?;
+                            ForBounds::Integer { target, step }
+                        }
+                        DataType::Float => {
+                            let step = self.pop_float()?;
+                            let target = self.pop_float()?;
+                            ForBounds::Float { target, step }
+                        }
+                        _ => return Err(InterpreterError::NotImplemented("Loops for other than integers and floats".to_string()))
+                    };
+
+                    self.fors.push(ForFrame { slot, bounds, body: *pc });
+                }
+
+                Op::ForPop => {
+                    self.fors.pop();
+                }
+
+                Op::Step | Op::Statement(_) | Op::ForNext => {
+                    if let Some(steps) = &mut budget {
+                        if *steps == 0 {
+                            *pc -= 1;
+                            return Ok(Status::Yielded);
+                        }
+                        *steps -= 1;
+                    }
+
+                    scope.step()?;
+
+                    match op {
+                        Op::Statement(line) if instrumented => {
+                            scope.trace().line(line, host)?;
+
+                            if let Some(p) = scope.profile_mut() {
+                                p.statement(line);
+                            }
+                        }
+                        Op::ForNext => self.for_next(pc, scope)?,
+                        _ => {}
+                    }
+                }
+
+                Op::Call(index, arguments) => {
+                    let procedure = &chunk.procedures[index];
+
+                    let address = match (procedure.address, scope.native_procedure(&procedure.name)) {
+                        (Some(address), _) => address,
+                        (None, Some(native)) => {
+                            scope.trace().enter(&procedure.name, host)?;
+                            self.call_native(&native, arguments, scope)?;
+                            scope.trace().leave(host)?;
+                            continue;
+                        }
+                        (None, None) => return Err(InterpreterError::NoSuchProcedure(procedure.name.clone()))
+                    };
+                    if arguments != procedure.parameters {
+                        return Err(InterpreterError::Arguments);
+                    }
+
+                    scope.enter();
+                    scope.trace().enter(&procedure.name, host)?;
+                    if let Some(p) = scope.profile_mut() {
+                        p.enter(&procedure.name);
+                    }
+                    self.calls.push(CallFrame { procedure: index, return_address: *pc, fors: self.fors.len() });
+                    *pc = address;
+                }
+
+                Op::Function(index, arguments) => {
+                    let function = scope.native_function(&chunk.functions[index])?;
+                    let v = self.call_native(&function, arguments, scope)?;
+                    self.stack.push(v);
+                }
+
+                Op::Local(slot) => scope.make_local(slot)?,
+
+                Op::Return => {
+                    let frame = self.calls.pop().ok_or(InterpreterError::NotInProcedure)?;
+
+                    // ENDPROC inside a loop drops the loop.
+                    self.fors.truncate(frame.fors);
+                    scope.leave()?;
+                    scope.trace().leave(host)?;
+                    if let Some(p) = scope.profile_mut() {
+                        p.leave();
+                    }
+                    *pc = frame.return_address;
+                }
+
+                Op::TraceOn => {
+                    scope.set_trace(scope.trace().lines());
+                    instrumented = scope.instrumented();
+                }
+                Op::TraceOff => {
+                    scope.set_trace(Trace::off());
+                    instrumented = scope.instrumented();
+                }
+                Op::TraceProc => {
+                    scope.set_trace(scope.trace().procedures());
+                    instrumented = scope.instrumented();
+                }
+                Op::TraceBelow => {
+                    let line = self.pop_integer()?.max(0) as usize;
+                    scope.set_trace(scope.trace().lines_below(line));
+                    instrumented = scope.instrumented();
+                }
+
+                Op::End => return Ok(Status::Done(ExecutionResult::End)),
+                Op::Halt => return Ok(Status::Done(ExecutionResult::Ok)),
+
+                op => self.host_operation(op, scope, host)?
+            }
+        }
+    }
+
+    /// Runs the instructions that talk to the host or the memory. They are
+    /// kept out of the dispatch loop, which stays small enough for the
+    /// compiler to keep its state in registers.
+    #[inline(never)]
+    fn host_operation(&mut self, op: Op, scope: &mut Scope, host: &mut dyn Host) -> Result<(), InterpreterError> {
+        match op {
+            Op::Print => match self.pop()? {
+                Value::String(s) => host.write_text(&s)?,
+                v => host.write_text(&scope.format(&v))?
+            },
+
+            Op::PrintNewline => host.write_text("\n")?,
+
+            Op::Input(slot, kind) => {
+                host.flush()?;
+
+                let line = match host.read_line()? {
+                    None => return Err(InterpreterError::EndOfInput),
+                    Some(l) => l
+                };
+
+                let value = match kind {
+                    DataType::String => Value::String(line),
+                    _ => {
+                        let number = line.trim().parse::<Float>().unwrap_or(0.0);
+                        Value::Float(number).convert(kind, scope.integer_model())?
+                    }
+                };
+
+                scope.set(slot, value);
+            }
+
+            Op::Time => self.stack.push(Value::Integer(host.time())),
+
+            Op::SetTime => {
+                let t = self.pop_integer()?;
+                host.set_time(t);
+            }
+
+            Op::Rnd => self.stack.push(Value::Integer((host.random() * u32::MAX as Float) as Integer + i32::MIN as Integer)),
+
+            Op::RndArg => {
+                let v = match self.pop_integer()? {
+                    n if n < 0 => {
+                        host.seed_random(n);
+                        Value::Integer(n)
+                    }
+                    0 | 1 => Value::Float(host.random()),
+                    n => Value::Integer((host.random() * n as Float) as Integer + 1)
+                };
+                self.stack.push(v);
+            }
+
+            Op::Get => match host.read_key(None)? {
+                None => return Err(InterpreterError::EndOfInput),
+                Some(key) => self.stack.push(Value::Integer(key as Integer))
+            },
+
+            Op::Inkey => {
+                // The timeout stays on the stack until a key was read,
+                // so the instruction can be retried.
+                let timeout = self.stack.last().ok_or(InterpreterError::Unreachable)?.to_integer()?.max(0) as u32;
+
+                let v = match host.read_key(Some(timeout))? {
+                    None => -1,
+                    Some(key) => key as Integer
+                };
+                self.pop()?;
+                self.stack.push(Value::Integer(v));
+            }
+
+            Op::Open(mode) => {
+                let name = self.pop_string()?;
+
+                let channel = match host.open_file(&name, mode)? {
+                    None => 0,
+                    Some(channel) => {
+                        self.channels.push(channel);
+                        channel as Integer
+                    }
+                };
+                self.stack.push(Value::Integer(channel));
+            }
+
+            Op::Bget => {
+                let channel = self.pop_integer()?;
+
+                match host.read_byte(channel as Channel)? {
+                    None => return Err(InterpreterError::EndOfFile),
+                    Some(byte) => self.stack.push(Value::Integer(byte as Integer))
+                }
+            }
+
+            Op::Eof => {
+                let channel = self.pop_integer()?;
+                let eof = if host.at_eof(channel as Channel)? { -1 } else { 0 };
+                self.stack.push(Value::Integer(eof));
+            }
+
+            Op::Bput => {
+                let [channel, value] = self.pop_integers()?;
+                host.write_byte(channel as Channel, value as Byte)?;
+            }
+
+            Op::Close => {
+                let channel = self.pop_integer()?;
+                host.close_file(channel as Channel)?;
+                self.channels.retain(|c| *c as Integer != channel);
+            }
+
+            Op::Vdu(n) => {
+                let mut codes = vec![0; n];
+
+                for c in codes.iter_mut().rev() {
+                    *c = self.pop_integer()? as Byte;
+                }
+                host.vdu(&codes)?;
+            }
+
+            Op::Plot => {
+                let [mode, x, y] = self.pop_integers()?;
+                host.plot(mode as i32, x as i32, y as i32)?;
+            }
+
+            Op::Sound => {
+                let [channel, amplitude, pitch, duration] = self.pop_integers()?;
+                host.sound(channel as i32, amplitude as i32, pitch as i32, duration as i32)?;
+            }
+
+            Op::Dim => {
+                let n = self.pop_integer()?;
+                let address = scope.memory_mut().dim(n)?;
+                self.stack.push(Value::Integer(address));
+            }
+
+            Op::Peek(indirection) => {
+                let address = self.pop_integer()?;
+                self.stack.push(scope.memory().peek(indirection, address)?);
+            }
+
+            Op::Poke(indirection) => {
+                let value = self.pop()?;
+                let address = self.pop_integer()?;
+                scope.memory_mut().poke(indirection, address, &value)?;
+            }
+
+            _ => return Err(InterpreterError::Unreachable)
+        }
+
+        Ok(())
+    }
+}
+
+impl Chunk {
+    /// Runs the chunk on a fresh VM.
+    pub fn execute_on(&self, scope: &mut Scope, host: &mut dyn Host) -> Result<ExecutionResult, InterpreterError> {
+        let result = Vm::new().run(self, scope, host);
+        host.flush()?;
+
+        result
+    }
+}
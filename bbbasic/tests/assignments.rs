@@ -49,3 +49,21 @@ PRINT x$";
     assert_eq!(out.stringify(), exp.stringify());
 
 }
+
+#[test]
+fn suffixes_are_separate_variables() {
+    let (mut out, exp) = common::make_buffer("2.5 3 2.5 x\n");
+    let inp =
+"x = 1.5
+x% = 3
+x# = x# + 1
+x$ = \"x\"
+PRINT x; \" \"; x%; \" \"; x; \" \"; x$";
+
+    let r = parser::Program::parse(inp).expect("Parse failed");
+
+    r.execute(&mut out).expect("Execution failed");
+
+    assert_eq!(out.stringify(), exp.stringify());
+
+}
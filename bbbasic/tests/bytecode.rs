@@ -0,0 +1,128 @@
+use bbbasic::{BasicError, Engine, Interpreter, InterpreterError, MemoryHost, Outcome};
+
+mod common;
+
+fn run_on(engine: Engine, host: MemoryHost, code: &str) -> (Result<Outcome, BasicError>, MemoryHost) {
+    let mut interpreter = Interpreter::new(host).with_engine(engine);
+    let result = interpreter.run(code);
+
+    (result, interpreter.into_host())
+}
+
+/// Runs `code` on both engines and checks that they behave the same.
+fn assert_same(host: MemoryHost, code: &str) -> MemoryHost {
+    let (result, host) = common::run_on_both_engines(code, |e| Interpreter::new(host.clone()).with_engine(e));
+    result.expect("Program failed");
+
+    host
+}
+
+#[test]
+fn demos_give_the_same_output() {
+    let host = assert_same(MemoryHost::new(), include_str!("../../bbcli/demo/mandelbrot.bbb"));
+    assert!(!host.output.is_empty());
+
+    assert_same(MemoryHost::new(), include_str!("../../bbcli/demo/benchmark1.bbb"));
+}
+
+#[test]
+fn mixed_expressions_are_promoted() {
+    let inp =
+"a% = 7
+b = 2.5
+c& = 20
+PRINT a% / 2
+PRINT a% / 2.0
+PRINT b * a% - 1
+PRINT (3 + 4) * (2 - 5)
+c& = c& / 3
+PRINT c& + 0
+x% = b
+PRINT x%
+PRINT c& * 100
+PRINT 9223372036854775807 + 1
+";
+
+    let host = assert_same(MemoryHost::new(), inp);
+    assert_eq!(host.output, "3.5\n3.5\n16.5\n-21\n6\n2\n600\n9223372036854776000\n");
+}
+
+#[test]
+fn loops_and_exits() {
+    let inp =
+"FOR i% = 1 TO 3
+    FOR j = 0 TO 1 STEP 0.5
+        PRINT i%; \" \"; j
+        IF j >= 0.5 THEN
+            EXIT FOR
+        ENDIF
+    NEXT j
+NEXT i%
+PRINT i%
+n% = 0
+WHILE n% < 10
+    n% = n% + 1
+    FOR k% = 1 TO 10
+        IF n% = 3 THEN
+            EXIT WHILE
+        ENDIF
+    NEXT k%
+ENDWHILE
+PRINT n%; \" \"; k%
+";
+
+    let host = assert_same(MemoryHost::new(), inp);
+    assert_eq!(host.output, "1 0\n1 0.5\n2 0\n2 0.5\n3 0\n3 0.5\n3\n3 1\n");
+}
+
+#[test]
+fn conditions_and_strings() {
+    let inp =
+"a$ = \"abc\"
+x = 3
+IF a$ = \"abc\" AND x > 2 OR x = 0 THEN
+    PRINT \"yes\"
+ELSE
+    PRINT \"no\"
+ENDIF
+IF (x < 2 OR x <> 3) AND a$ <> \"abc\" THEN
+    PRINT \"yes\"
+ELSE
+    PRINT \"no\"
+ENDIF
+";
+
+    let host = assert_same(MemoryHost::new(), inp);
+    assert_eq!(host.output, "yes\nno\n");
+}
+
+#[test]
+fn end_and_host_statements() {
+    let inp =
+"INPUT \"Name\", n$
+INPUT x%
+TIME = 100
+PRINT n$; x%; TIME
+c% = OPENOUT \"out.bin\"
+BPUT #c%, 65
+CLOSE #c%
+VDU 1, 2, 3
+MOVE 10, 20
+SOUND 1, -15, 53, 20
+END
+PRINT \"unreachable\"
+";
+
+    let host = assert_same(MemoryHost::new().with_input(&["Joe", "42"]), inp);
+    assert_eq!(host.output, "Name??Joe42100\n");
+    assert_eq!(host.files["out.bin"], vec![65]);
+}
+
+#[test]
+fn bytecode_reports_runtime_errors() {
+    let (r, _) = run_on(Engine::Bytecode, MemoryHost::new(), "PRINT y%");
+    assert!(matches!(r, Err(BasicError::Runtime(InterpreterError::UnknownVariable(name))) if name == "y"));
+
+    let (r, _) = run_on(Engine::Bytecode, MemoryHost::new(), "x% = 0\nPRINT 1 / x%");
+    assert!(matches!(r, Err(BasicError::Runtime(InterpreterError::DivisionByZero))));
+}
@@ -0,0 +1,111 @@
+use bbbasic::checker::{check_source, Diagnostic, Problem};
+
+mod common;
+
+fn problems(code: &str) -> Vec<(usize, Problem)> {
+    check_source(code).expect("Parse failed").into_iter()
+        .map(|Diagnostic { position, problem }| (position.line, problem))
+        .collect()
+}
+
+#[test]
+fn uncalled_procedures_are_checked_too() {
+    let code = "total% = 0
+FOR i% = 1 TO 4
+    IF i% > 2 THEN
+        EXIT FOR
+    ELSE
+        PROCadd(i%)
+    ENDIF
+NEXT i%
+INPUT \"Name\", name$
+IF name$ = \"Bob\" THEN
+    PRINT total%
+ENDIF
+END
+DEF PROCadd(n%)
+total% = total% + n%
+ENDPROC
+DEF PROCunused
+PRINT total%; n%
+";
+
+    assert_eq!(problems(code), vec![(18, Problem::Unassigned("n%".to_string()))]);
+}
+
+#[test]
+fn variables_read_before_assignment_are_found() {
+    let code = "iteration% = 1
+PRINT iteraton%
+IF iteration% > 0 THEN
+    a = 1
+ELSE
+    b = 1
+ENDIF
+PRINT a + b + iteraton%
+";
+
+    assert_eq!(problems(code), vec![(2, Problem::Unassigned("iteraton%".to_string()))]);
+}
+
+#[test]
+fn procedures_see_the_variables_of_their_first_call() {
+    let code = "PROCshow
+x = 1
+PROCshow
+PROCset
+PRINT y; p
+END
+DEF PROCshow
+PRINT x
+ENDPROC
+DEF PROCset
+PROCparameter(1)
+y = 2
+ENDPROC
+DEF PROCparameter(p)
+ENDPROC
+";
+
+    assert_eq!(problems(code), vec![
+        (5, Problem::Unassigned("p".to_string())),
+        (8, Problem::Unassigned("x".to_string())),
+    ]);
+}
+
+#[test]
+fn loops_are_checked() {
+    let code = "FOR i% = 1 TO 2
+    FOR j% = 1 TO 2
+        EXIT WHILE
+    NEXT i%
+NEXT
+EXIT FOR
+";
+
+    assert_eq!(problems(code), vec![
+        (3, Problem::ExitWhileOutsideWhile),
+        (4, Problem::NextMismatch { expected: "j%".to_string(), found: "i%".to_string() }),
+        (6, Problem::ExitForOutsideFor),
+    ]);
+}
+
+#[test]
+fn other_problems_are_found() {
+    let code = "a$ = \"1\"
+WHILE a$ = 1 OR 2 < a$
+    PROCmissing(a$)
+ENDWHILE
+END
+PRINT a$
+PRINT a$
+";
+
+    let diagnostics = check_source(code).unwrap();
+    let diagnostics: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+    assert_eq!(diagnostics, [
+        "2:1: Comparing a string with a number",
+        "3:5: No such FN/PROC: missing",
+        "6:1: Unreachable code",
+    ]);
+}
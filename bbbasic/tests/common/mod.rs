@@ -1,8 +1,10 @@
 use std::io::Write;
+use bbbasic::{BasicError, Engine, Interpreter, MemoryHost, Outcome};
 
 pub type Buffer = std::io::Cursor<Vec<u8>>;
 
 
+#[allow(dead_code)]
 pub trait Stringify {
     fn stringify(&self) -> String;
 }
@@ -10,8 +12,8 @@ pub trait Stringify {
 impl Stringify for Buffer {
     fn stringify(&self) -> String {
         match std::str::from_utf8(self.get_ref()) {
-            Ok(s) => format!("{s}"),
-            Err(_) => format!("Not a UTF-8 string")
+            Ok(s) => s.to_string(),
+            Err(_) => "Not a UTF-8 string".to_string()
         }
     }
 }
@@ -39,4 +41,24 @@ pub fn print_buffer(buffer: &Buffer) {
         Ok(s) => println!("{s}"),
         Err(_) => println!("Not a UTF-8 string")
     }
-}
\ No newline at end of file
+}
+
+/// Runs `code` on an interpreter from `interpreter` for each engine,
+/// expecting the same result, output, trace, host events and files.
+#[allow(dead_code)]
+pub fn run_on_both_engines(code: &str, interpreter: impl Fn(Engine) -> Interpreter<MemoryHost>) -> (Result<Outcome, BasicError>, MemoryHost) {
+    let mut tree = interpreter(Engine::TreeWalker);
+    let tree_result = tree.run(code);
+
+    let mut bytecode = interpreter(Engine::Bytecode);
+    let bytecode_result = bytecode.run(code);
+
+    let (tree, bytecode) = (tree.into_host(), bytecode.into_host());
+    assert_eq!(format!("{:?}", tree_result), format!("{:?}", bytecode_result));
+    assert_eq!(tree.output, bytecode.output);
+    assert_eq!(tree.trace, bytecode.trace);
+    assert_eq!(tree.events, bytecode.events);
+    assert_eq!(tree.files, bytecode.files);
+
+    (bytecode_result, bytecode)
+}
@@ -0,0 +1,93 @@
+use bbbasic::{Debugger, Frame, Interpreter, MemoryHost, Outcome, Stop, Value};
+
+mod common;
+
+const PROGRAM: &str =
+"total% = 0
+FOR i% = 1 TO 3
+    PROCadd(i%)
+NEXT i%
+PRINT total%
+END
+
+DEF PROCadd(n%)
+total% = total% + n%
+PROCshow
+ENDPROC
+
+DEF PROCshow
+PRINT total%
+";
+
+fn debugger() -> Debugger<MemoryHost> {
+    Debugger::new(Interpreter::new(MemoryHost::new()), PROGRAM).expect("Program failed")
+}
+
+fn frame(procedure: Option<&str>, line: usize) -> Frame {
+    Frame { procedure: procedure.map(|p| p.to_string()), line }
+}
+
+#[test]
+fn breakpoints_stop_the_program() {
+    let mut d = debugger();
+    assert_eq!(d.line(), Some(1));
+
+    d.add_breakpoint(9);
+    assert!(matches!(d.resume(), Stop::Breakpoint(9)));
+    assert_eq!(d.variable("n%").unwrap(), Value::Integer(1));
+
+    assert!(matches!(d.resume(), Stop::Breakpoint(9)));
+    assert_eq!(d.variable("n%").unwrap(), Value::Integer(2));
+    assert_eq!(d.host().output, "1\n");
+
+    assert!(d.remove_breakpoint(9));
+    assert!(matches!(d.resume(), Stop::Finished(Outcome::Ended)));
+    assert_eq!(d.host().output, "1\n3\n6\n6\n");
+}
+
+#[test]
+fn stepping_into_over_and_out_of_procedures() {
+    let mut d = debugger();
+
+    assert!(matches!(d.step_into(), Stop::Step(2)));
+    assert!(matches!(d.step_into(), Stop::Step(3)));
+    assert!(matches!(d.step_into(), Stop::Step(9)));
+    assert!(matches!(d.step_over(), Stop::Step(10)));
+    assert!(matches!(d.step_into(), Stop::Step(14)));
+
+    assert_eq!(d.call_stack(), vec![frame(Some("show"), 14), frame(Some("add"), 10), frame(None, 3)]);
+
+    assert!(matches!(d.step_out(), Stop::Step(11)));
+    assert!(matches!(d.step_out(), Stop::Step(3)));
+    assert_eq!(d.call_stack(), vec![frame(None, 3)]);
+
+    assert!(matches!(d.step_over(), Stop::Step(3)));
+    assert_eq!(d.host().output, "1\n3\n");
+}
+
+#[test]
+fn variables_can_be_changed() {
+    let mut d = debugger();
+    d.add_breakpoint(5);
+
+    assert!(matches!(d.resume(), Stop::Breakpoint(5)));
+    assert!(d.variables().contains(&("total%".to_string(), Value::Integer(6))));
+
+    d.set_variable("total%", Value::Float(42.9)).unwrap();
+    assert!(d.set_variable("total%", Value::String("many".to_string())).is_err());
+
+    assert!(matches!(d.resume(), Stop::Finished(Outcome::Ended)));
+    assert!(d.host().output.ends_with("6\n42\n"));
+}
+
+#[test]
+fn stepping_waits_for_input() {
+    let mut d = Debugger::new(Interpreter::new(MemoryHost::new().non_blocking()), "INPUT a$\nPRINT a$\n").expect("Program failed");
+
+    assert!(matches!(d.step_into(), Stop::WaitingForInput));
+    d.host_mut().input.push_back("Hi".to_string());
+
+    assert!(matches!(d.step_into(), Stop::Step(2)));
+    assert!(matches!(d.step_into(), Stop::Finished(Outcome::Finished)));
+    assert_eq!(d.host().output, "?Hi\n");
+}
@@ -0,0 +1,139 @@
+use bbbasic::{Interpreter, MemoryHost};
+use bbbasic::host::HostEvent;
+
+mod common;
+
+fn run(host: MemoryHost, code: &str) -> MemoryHost {
+    let mut interpreter = Interpreter::new(host);
+    interpreter.run(code).expect("Execution failed");
+
+    interpreter.into_host()
+}
+
+#[test]
+fn output_goes_to_the_host() {
+    let host = run(MemoryHost::new(), "PRINT \"Hello\"");
+
+    assert_eq!(host.output, "Hello\n");
+}
+
+#[test]
+fn input_comes_from_the_host() {
+    let inp =
+"INPUT \"Who\", who$
+PRINT \"Hello, \";who$";
+
+    let host = run(MemoryHost::new().with_input(&["World"]), inp);
+
+    assert_eq!(host.output, "Who?Hello, World\n");
+}
+
+#[test]
+fn keys_come_from_the_host() {
+    let inp =
+"k% = GET
+PRINT k%
+k% = INKEY(100)
+PRINT k%
+PRINT TIME";
+
+    let host = run(MemoryHost::new().with_keys("A"), inp);
+
+    assert_eq!(host.output, "65\n-1\n100\n");
+}
+
+#[test]
+fn time_can_be_set() {
+    let inp =
+"TIME = 500
+PRINT TIME";
+
+    let host = run(MemoryHost::new(), inp);
+
+    assert_eq!(host.output, "500\n");
+}
+
+#[test]
+fn random_numbers_are_reproducible_when_seeded() {
+    let inp =
+"x = RND(-42)
+a% = RND(6)
+b = RND(1)
+PRINT a%;\" \";b";
+
+    let first = run(MemoryHost::new(), inp);
+    let second = run(MemoryHost::new(), inp);
+
+    assert_eq!(first.output, second.output);
+}
+
+#[test]
+fn random_integers_are_in_range() {
+    let inp =
+"FOR i% = 1 TO 100
+    r% = RND(6)
+    IF r% < 1 OR r% > 6 THEN
+        PRINT r%
+    ENDIF
+NEXT i%";
+
+    let host = run(MemoryHost::new(), inp);
+
+    assert_eq!(host.output, "");
+}
+
+#[test]
+fn files_are_read_from_the_host() {
+    let inp =
+"f% = OPENIN \"data\"
+WHILE EOF#f% = 0
+    PRINT BGET#f%;\" \";
+ENDWHILE
+CLOSE#f%";
+
+    let host = run(MemoryHost::new().with_file("data", b"AB"), inp);
+
+    assert_eq!(host.output, "65 66 ");
+}
+
+#[test]
+fn files_are_written_to_the_host() {
+    let inp =
+"f% = OPENOUT \"data\"
+BPUT#f%, 72
+BPUT#f%, 105
+CLOSE#f%";
+
+    let host = run(MemoryHost::new(), inp);
+
+    assert_eq!(host.files.get("data").unwrap(), b"Hi");
+}
+
+#[test]
+fn missing_files_open_as_zero() {
+    let host = run(MemoryHost::new(), "f% = OPENIN \"missing\"\nPRINT f%");
+
+    assert_eq!(host.output, "0\n");
+}
+
+#[test]
+fn graphics_and_sound_go_to_the_host() {
+    let inp =
+"CLS
+VDU 7, 65
+MOVE 0, 0
+DRAW 100, 200
+PLOT 69, 10, 20
+SOUND 1, -15, 53, 20";
+
+    let host = run(MemoryHost::new(), inp);
+
+    assert_eq!(host.events, vec![
+        HostEvent::Vdu(vec![12]),
+        HostEvent::Vdu(vec![7, 65]),
+        HostEvent::Plot { mode: 4, x: 0, y: 0 },
+        HostEvent::Plot { mode: 5, x: 100, y: 200 },
+        HostEvent::Plot { mode: 69, x: 10, y: 20 },
+        HostEvent::Sound { channel: 1, amplitude: -15, pitch: 53, duration: 20 },
+    ]);
+}
@@ -0,0 +1,67 @@
+use bbbasic::{Engine, Interpreter, Listing, MemoryHost};
+
+mod common;
+
+#[test]
+fn variables_persist_between_statements() {
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+
+        interpreter.execute("x% = 20\n").expect("Statement failed");
+        interpreter.execute("name$ = \"Beeb\"\n").expect("Statement failed");
+        interpreter.execute("PRINT name$; x% + 1\n").expect("Statement failed");
+
+        assert_eq!(interpreter.host().output, "Beeb21\n");
+    }
+}
+
+#[test]
+fn statements_see_the_last_program() {
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+
+        interpreter.run("total% = 5\nEND\nDEF PROCtwice(n%)\nPRINT n% * 2\n").expect("Program failed");
+        interpreter.execute("PROCtwice(total%)\n").expect("Statement failed");
+        assert_eq!(interpreter.host().output, "10\n");
+
+        interpreter.clear();
+        assert!(interpreter.execute("PRINT total%\n").is_err());
+        interpreter.execute("PROCtwice(1)\n").expect("Statement failed");
+        assert_eq!(interpreter.host().output, "10\n2\n");
+    }
+}
+
+#[test]
+fn running_a_program_forgets_the_variables() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+
+    interpreter.execute("x = 1\n").expect("Statement failed");
+    interpreter.run("y = 2\n").expect("Program failed");
+
+    assert!(interpreter.execute("PRINT x\n").is_err());
+}
+
+#[test]
+fn numbered_lines_edit_the_listing() {
+    let mut listing = Listing::new();
+
+    listing.set_line(20, "PRINT x");
+    listing.set_line(10, "x = 1");
+    listing.set_line(30, "PRINT y");
+    listing.set_line(30, "");
+    listing.set_line(15, "    x = x + 1");
+
+    assert_eq!(listing.to_string(), "   10 x = 1\n   15     x = x + 1\n   20 PRINT x\n");
+    assert_eq!(listing.source(), "x = 1\n    x = x + 1\nPRINT x\n");
+    assert_eq!(listing.line_number(2), Some(15));
+    assert_eq!(listing.line_number(4), None);
+}
+
+#[test]
+fn sources_are_numbered() {
+    let listing = Listing::from_source("x = 1\nPRINT x\n");
+    assert_eq!(listing.to_string(), "   10 x = 1\n   20 PRINT x\n");
+
+    let listing = Listing::from_source("100 x = 1\n105 PRINT x\n");
+    assert_eq!(listing.to_string(), "  100 x = 1\n  105 PRINT x\n");
+}
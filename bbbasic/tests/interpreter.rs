@@ -0,0 +1,302 @@
+use bbbasic::{BasicError, Engine, IntegerModel, Interpreter, InterpreterError, MemoryHost, Outcome, StdHost, StepResult};
+use bbbasic::host::{Channel, FileMode, Host};
+use crate::common::Stringify;
+
+mod common;
+
+#[test]
+fn run_writes_to_given_output() {
+    let (mut out, exp) = common::make_buffer("4\n");
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+
+    let outcome = interpreter.run("PRINT 2 + 2").expect("Execution failed");
+
+    assert_eq!(outcome, Outcome::Finished);
+    assert_eq!(out.stringify(), exp.stringify());
+}
+
+#[test]
+fn end_stops_the_program() {
+    let (mut out, exp) = common::make_buffer("1\n");
+    let inp =
+"PRINT 1
+END
+PRINT 2";
+
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+    let outcome = interpreter.run(inp).expect("Execution failed");
+
+    assert_eq!(outcome, Outcome::Ended);
+    assert_eq!(out.stringify(), exp.stringify());
+}
+
+#[test]
+fn end_inside_a_loop_stops_the_program() {
+    let (mut out, exp) = common::make_buffer("1\n");
+    let inp =
+"FOR i = 1 TO 3
+    PRINT i
+    END
+NEXT i";
+
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+    let outcome = interpreter.run(inp).expect("Execution failed");
+
+    assert_eq!(outcome, Outcome::Ended);
+    assert_eq!(out.stringify(), exp.stringify());
+}
+
+#[test]
+fn parse_errors_are_reported() {
+    let (mut out, _) = common::make_buffer("");
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+
+    match interpreter.run("PRINT 1\nPRINT (1 +") {
+        Err(BasicError::Parse { line, .. }) => assert_eq!(line, 2),
+        r => panic!("Expected a parse error, got {:?}", r)
+    }
+}
+
+#[test]
+fn runtime_errors_are_reported() {
+    let (mut out, _) = common::make_buffer("");
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+
+    match interpreter.run("PRINT x") {
+        Err(BasicError::Runtime(InterpreterError::UnknownVariable(name))) => assert_eq!(name, "x"),
+        r => panic!("Expected an unknown variable error, got {:?}", r)
+    }
+}
+
+#[test]
+fn division_by_zero_does_not_panic() {
+    let (mut out, _) = common::make_buffer("");
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+
+    match interpreter.run("x% = 0\nPRINT 1 / x%") {
+        Err(e) => assert_eq!(e.to_string(), "Division by zero"),
+        r => panic!("Expected a division by zero error, got {:?}", r)
+    }
+}
+
+#[test]
+fn integer_overflow_does_not_panic() {
+    let (mut out, _) = common::make_buffer("");
+    let mut interpreter = Interpreter::new(StdHost::new(std::io::empty(), &mut out));
+
+    let r = interpreter.run("x% = 9223372036854775807\nx% = x% + 1");
+
+    assert!(matches!(r, Err(BasicError::Runtime(InterpreterError::NumberTooBig))));
+}
+
+#[test]
+fn input_reads_from_given_input() {
+    let (mut out, exp) = common::make_buffer("Name?Age?Hello, Joe 42\n");
+    let inp =
+"INPUT \"Name\", n$
+INPUT \"Age\", a%
+PRINT \"Hello, \";n$;\" \";a%";
+
+    let mut interpreter = Interpreter::new(StdHost::new("Joe\n42\n".as_bytes(), &mut out));
+    interpreter.run(inp).expect("Execution failed");
+
+    assert_eq!(out.stringify(), exp.stringify());
+}
+
+#[test]
+fn input_without_prompt_asks() {
+    let (mut out, exp) = common::make_buffer("?3.5\n");
+    let inp =
+"INPUT x
+PRINT x";
+
+    let mut interpreter = Interpreter::new(StdHost::new("3.5\n".as_bytes(), &mut out));
+    interpreter.run(inp).expect("Execution failed");
+
+    assert_eq!(out.stringify(), exp.stringify());
+}
+
+#[test]
+fn input_past_the_end_is_an_error() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+
+    let r = interpreter.run("INPUT x");
+
+    assert!(matches!(r, Err(BasicError::Runtime(InterpreterError::EndOfInput))));
+}
+
+#[test]
+fn optimization_does_not_change_the_output() {
+    let inp =
+"W = 4
+H = 2.5
+FOR i% = 1 TO W * 2 / 3
+    PRINT i% * 1 + 0; \" \"; (3.0 * 2) / W + H; \" \"; 7 / 2 + 0.5; \" \"; i% / 2 * 1
+NEXT i%";
+
+    let mut outputs = Vec::new();
+
+    for enabled in [false, true] {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_optimization(enabled);
+        interpreter.run(inp).expect("Execution failed");
+        outputs.push(interpreter.into_host().output);
+    }
+
+    assert_eq!(outputs[0], "1 4 4 0.5\n2 4 4 1\n");
+    assert_eq!(outputs[0], outputs[1]);
+}
+
+#[test]
+fn integer_model_decides_how_integers_overflow() {
+    let inp =
+"a% = 2147483647
+PRINT a% + 1
+PRINT a% * 2
+b& = 250
+b& = b& + 10
+PRINT b&
+c% = 3000000000";
+
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new())
+            .with_engine(engine)
+            .with_integer_model(IntegerModel::Acorn);
+        let r = interpreter.run(inp);
+
+        assert!(matches!(r, Err(BasicError::Runtime(InterpreterError::NumberTooBig))));
+        assert_eq!(interpreter.host().output, "-2147483648\n4294967294\n4\n");
+
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+        interpreter.run(inp).expect("Execution failed");
+
+        assert_eq!(interpreter.host().output, "2147483648\n4294967294\n4\n");
+    }
+}
+
+#[test]
+fn endless_loops_hit_the_step_limit() {
+    let inp =
+"i% = 0
+WHILE 1 = 1
+    i% = i% + 1
+    PRINT i%
+ENDWHILE";
+
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new())
+            .with_engine(engine)
+            .with_step_limit(10);
+        let outcome = interpreter.run(inp).expect("Execution failed");
+
+        assert_eq!(outcome, Outcome::StepLimitExceeded);
+        assert_eq!(interpreter.host().output, "1\n2\n");
+
+        let outcome = interpreter.run("WHILE 1 = 1\nENDWHILE").expect("Execution failed");
+        assert_eq!(outcome, Outcome::StepLimitExceeded);
+
+        let outcome = interpreter.run("FOR i = 1 TO 3\nNEXT i").expect("Execution failed");
+        assert_eq!(outcome, Outcome::Finished);
+    }
+}
+
+#[test]
+fn cancelled_programs_escape() {
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+        let token = interpreter.cancel_token();
+
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            token.cancel();
+        });
+
+        let outcome = interpreter.run("WHILE 1 = 1\nENDWHILE").expect("Execution failed");
+        canceller.join().expect("Cancelling failed");
+
+        assert_eq!(outcome, Outcome::Escaped);
+        interpreter.cancel_token().reset();
+    }
+}
+
+#[test]
+fn programs_run_step_by_step() {
+    let inp =
+"INPUT \"Name\", n$
+FOR i% = 1 TO 3
+    PRINT n$; i%
+NEXT i%
+k% = GET
+PRINT k%";
+
+    let mut interpreter = Interpreter::new(MemoryHost::new().non_blocking());
+    interpreter.start(inp).expect("Parse failed");
+
+    assert!(matches!(interpreter.step(10), StepResult::WaitingForInput));
+    assert!(matches!(interpreter.step(10), StepResult::WaitingForInput));
+    assert_eq!(interpreter.host().output, "Name?");
+
+    interpreter.host_mut().input.push_back("Jo".to_string());
+    assert!(matches!(interpreter.step(2), StepResult::Running));
+    assert_eq!(interpreter.host().output, "Name?Jo1\n");
+
+    let mut steps = 0;
+    while let StepResult::Running = interpreter.step(1) {
+        steps += 1;
+    }
+    assert_eq!(steps, 5);
+    assert_eq!(interpreter.host().output, "Name?Jo1\nJo2\nJo3\n");
+    assert!(interpreter.is_running());
+
+    interpreter.host_mut().keys.push_back(b'A');
+    assert!(matches!(interpreter.step(100), StepResult::Finished(Outcome::Finished)));
+    assert_eq!(interpreter.host().output, "Name?Jo1\nJo2\nJo3\n65\n");
+
+    assert!(!interpreter.is_running());
+    assert!(matches!(interpreter.step(100), StepResult::Error(BasicError::Runtime(InterpreterError::NoProgram))));
+}
+
+#[test]
+fn stepped_programs_report_errors() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+
+    interpreter.start("x% = 1\nPRINT 1 / (x% - 1)").expect("Parse failed");
+    assert!(matches!(interpreter.step(100), StepResult::Error(BasicError::Runtime(InterpreterError::DivisionByZero))));
+
+    interpreter.start("END\n").expect("Parse failed");
+    assert!(matches!(interpreter.step(100), StepResult::Finished(Outcome::Ended)));
+
+    assert!(matches!(interpreter.start("PRINT ("), Err(BasicError::Parse { .. })));
+}
+
+/// Can't take output yet, which only `INPUT` and `GET` are meant to wait for.
+struct BusyHost(MemoryHost);
+
+impl Host for BusyHost {
+    fn write_text(&mut self, _: &str) -> std::io::Result<()> {
+        Err(std::io::ErrorKind::WouldBlock.into())
+    }
+
+    fn read_line(&mut self) -> std::io::Result<Option<String>> { self.0.read_line() }
+    fn read_key(&mut self, timeout: Option<u32>) -> std::io::Result<Option<u8>> { self.0.read_key(timeout) }
+    fn time(&mut self) -> i64 { self.0.time() }
+    fn set_time(&mut self, centiseconds: i64) { self.0.set_time(centiseconds) }
+    fn random(&mut self) -> f64 { self.0.random() }
+    fn seed_random(&mut self, seed: i64) { self.0.seed_random(seed) }
+    fn open_file(&mut self, name: &str, mode: FileMode) -> std::io::Result<Option<Channel>> { self.0.open_file(name, mode) }
+    fn close_file(&mut self, channel: Channel) -> std::io::Result<()> { self.0.close_file(channel) }
+    fn read_byte(&mut self, channel: Channel) -> std::io::Result<Option<u8>> { self.0.read_byte(channel) }
+    fn write_byte(&mut self, channel: Channel, byte: u8) -> std::io::Result<()> { self.0.write_byte(channel, byte) }
+    fn at_eof(&mut self, channel: Channel) -> std::io::Result<bool> { self.0.at_eof(channel) }
+    fn vdu(&mut self, codes: &[u8]) -> std::io::Result<()> { self.0.vdu(codes) }
+    fn plot(&mut self, mode: i32, x: i32, y: i32) -> std::io::Result<()> { self.0.plot(mode, x, y) }
+    fn sound(&mut self, channel: i32, amplitude: i32, pitch: i32, duration: i32) -> std::io::Result<()> { self.0.sound(channel, amplitude, pitch, duration) }
+}
+
+#[test]
+fn only_input_is_retried() {
+    let mut interpreter = Interpreter::new(BusyHost(MemoryHost::new()));
+    interpreter.start("PRINT 1 + 2").expect("Program failed");
+
+    assert!(matches!(interpreter.step(10), StepResult::Error(BasicError::Runtime(InterpreterError::Io(e))) if e.kind() == std::io::ErrorKind::WouldBlock));
+    assert!(!interpreter.is_running());
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "serde")]
+
+use bbbasic::json::{export_source, import};
+use bbbasic::pretty::{format_source, pretty_print};
+use bbbasic::{Interpreter, MemoryHost};
+
+mod common;
+
+const PROGRAM: &str = "total% = 0
+FOR i% = 1 TO 3
+    IF i% > 1 AND name$ <> \"x\" THEN
+        PROCadd(i%, \"big\")
+    ENDIF
+NEXT i%
+PRINT total%; RND(6) * 0.5
+END
+
+DEF PROCadd(n%, t$)
+total% = total% + n%
+PRINT t$
+";
+
+#[test]
+fn programs_survive_the_round_trip() {
+    let json = export_source(PROGRAM).unwrap();
+    let (program, symbols) = import(&json).unwrap();
+
+    let source = pretty_print(&program, &symbols);
+    assert_eq!(source, format_source(PROGRAM).unwrap());
+    assert_eq!(program.procedures[0].position.line, 10);
+}
+
+#[test]
+fn documents_are_stable() {
+    let json = export_source("x% = 1\nPRINT x%; \"!\"\n").unwrap();
+    let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(document["version"], 1);
+    assert_eq!(document["variables"], serde_json::json!(["x%"]));
+    assert_eq!(document["program"]["body"][0], serde_json::json!({
+        "position": { "line": 1, "column": 1 },
+        "statement": { "Assignment": [{ "slot": 0, "data_type": "Integer" }, { "Number": { "Integer": "1" } }] }
+    }));
+    assert_eq!(document["program"]["body"][1]["statement"]["Print"][1], serde_json::json!({
+        "value": { "String": { "Literal": "!" } },
+        "newline": true
+    }));
+}
+
+#[test]
+fn imported_programs_run() {
+    let json = export_source("a = 2\nPRINT a * 3\n").unwrap();
+    let (program, symbols) = import(&json).unwrap();
+
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.run(&pretty_print(&program, &symbols)).expect("Program failed");
+    assert_eq!(interpreter.host().output, "6\n");
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let json = export_source("PRINT 1\n").unwrap().replace("\"version\": 1", "\"version\": 2");
+
+    assert!(import(&json).unwrap_err().to_string().contains("Unsupported version 2"));
+    assert!(import("{}").is_err());
+}
@@ -0,0 +1,86 @@
+use bbbasic::{BasicError, Interpreter, InterpreterError, MemoryHost, Outcome};
+use bbbasic::pretty::format_source;
+
+mod common;
+
+/// Runs `code` on both engines, expecting the same result and output.
+fn run(code: &str) -> (Result<Outcome, BasicError>, String) {
+    let (result, host) = common::run_on_both_engines(code, |e| Interpreter::new(MemoryHost::new()).with_engine(e).with_memory_limit(256));
+
+    (result, host.output)
+}
+
+fn error(code: &str) -> InterpreterError {
+    match run(code).0 {
+        Err(BasicError::Runtime(e)) => e,
+        r => panic!("Unexpected {:?}", r)
+    }
+}
+
+#[test]
+fn memory_is_read_and_written() {
+    let code = "DIM buf% 31
+FOR i% = 0 TO 3
+    buf%?i% = 65 + i%
+NEXT
+PRINT ?buf%; \" \"; buf%?3; \" \"; !buf%
+!(buf% + 4) = -2
+PRINT buf%!4; \" \"; buf%?4; \" \"; buf%?7
+|(buf% + 8) = 2.5
+PRINT |(buf% + 8) * 2
+$(buf% + 16) = \"Hello\"
+name$ = \"World\"
+$(buf% + 22) = name$
+PRINT $(buf% + 16); \", \"; $(buf% + 22); \" \"; buf%?21
+";
+
+    let (result, output) = run(code);
+    assert_eq!(result.unwrap(), Outcome::Finished);
+    assert_eq!(output, "65 68 1145258561\n-2 254 255\n5\nHello, World 13\n");
+}
+
+#[test]
+fn blocks_are_reserved_one_after_the_other() {
+    let code = "DIM a% 3
+DIM b 9
+DIM c% -1
+PRINT b - a%; \" \"; c% - b; \" \"; ?(c% - 1)
+";
+
+    let (result, output) = run(code);
+    assert_eq!(result.unwrap(), Outcome::Finished);
+    assert_eq!(output, "4 10 0\n");
+}
+
+#[test]
+fn bad_accesses_are_reported() {
+    assert!(matches!(error("DIM a% 3\nPRINT a%?4"), InterpreterError::BadAddress));
+    assert!(matches!(error("DIM a% 3\na%!1 = 1"), InterpreterError::BadAddress));
+    assert!(matches!(error("PRINT ?0"), InterpreterError::BadAddress));
+    assert!(matches!(error("DIM a% 3\nPRINT $a%"), InterpreterError::BadAddress));
+    assert!(matches!(error("DIM a% 3\n$a% = \"four\""), InterpreterError::BadAddress));
+    assert!(matches!(error("DIM a% 3\n?a% = \"x\""), InterpreterError::TypeMismatch));
+    assert!(matches!(error("DIM a% -2"), InterpreterError::BadDim));
+    assert!(matches!(error("DIM a% 200\nDIM b% 100"), InterpreterError::NoRoom));
+    assert_eq!(error("PRINT |1").to_string(), "Bad address");
+}
+
+#[test]
+fn memory_is_released_by_clear() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+
+    interpreter.execute("DIM a% 3").unwrap();
+    interpreter.execute("?a% = 7").unwrap();
+    interpreter.execute("PRINT ?a%").unwrap();
+    interpreter.clear();
+    interpreter.execute("DIM b% 3").unwrap();
+    interpreter.execute("PRINT ?b%").unwrap();
+
+    assert_eq!(interpreter.host().output, "7\n0\n");
+}
+
+#[test]
+fn indirections_are_formatted() {
+    assert_eq!(format_source("DIM b% 9\nb%?1=?b%+b%!(2*2)\n$ b%=\"x\"\nPRINT |b%\n").unwrap(),
+               "DIM b% 9\nb%?1 = ?b% + b%!(2 * 2)\n$b% = \"x\"\nPRINT |b%\n");
+}
@@ -0,0 +1,105 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use bbbasic::checker::Problem;
+use bbbasic::{BasicError, DataType, Interpreter, InterpreterError, MemoryHost, Outcome, Value};
+
+mod common;
+
+/// Runs `code` on both engines with the natives of `register`, expecting the
+/// same result and output.
+fn run(code: &str, register: impl Fn(&mut Interpreter<MemoryHost>)) -> (Result<Outcome, BasicError>, String) {
+    let (result, host) = common::run_on_both_engines(code, |e| {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(e);
+        register(&mut interpreter);
+        interpreter
+    });
+
+    (result, host.output)
+}
+
+fn distance(interpreter: &mut Interpreter<MemoryHost>) {
+    interpreter.register_fn("FNdistance", &[DataType::Float, DataType::Float], |args| {
+        let (x, y) = (args[0].to_float()?, args[1].to_float()?);
+        Ok(Value::Float((x * x + y * y).sqrt()))
+    });
+    interpreter.register_fn("FNgreeting", &[DataType::String], |args| match &args[0] {
+        Value::String(name) if name.is_empty() => Err(InterpreterError::Native("No name".to_string())),
+        Value::String(name) => Ok(Value::String(format!("Hello, {}", name))),
+        _ => Err(InterpreterError::TypeMismatch)
+    });
+    interpreter.register_fn("half", &[DataType::Integer], |args| Ok(Value::Integer(args[0].to_integer()? / 2)));
+}
+
+#[test]
+fn native_functions_are_called() {
+    let code = "x = 3
+PRINT FNdistance(x, 2 + 2) * 2
+IF FNdistance(6, 8) = 10 THEN
+    PRINT FNgreeting(\"World\")
+ENDIF
+n$ = \"Bob\"
+PRINT FNgreeting(n$); \" \"; FNhalf(FNhalf(17))
+";
+
+    let (result, output) = run(code, distance);
+    assert_eq!(result.unwrap(), Outcome::Finished);
+    assert_eq!(output, "10\nHello, World\nHello, Bob 4\n");
+}
+
+#[test]
+fn native_calls_are_checked() {
+    let (result, _) = run("PRINT FNdistance(1)", distance);
+    assert!(matches!(result, Err(BasicError::Runtime(InterpreterError::Arguments))));
+
+    let (result, _) = run("PRINT FNdistance(\"a\", 1)", distance);
+    assert!(matches!(result, Err(BasicError::Runtime(InterpreterError::TypeMismatch))));
+
+    let (result, _) = run("PRINT FNgreeting(\"\")", distance);
+    assert_eq!(result.unwrap_err().to_string(), "No name");
+
+    let (result, _) = run("PRINT FNmissing", distance);
+    assert_eq!(result.unwrap_err().to_string(), "No such FN/PROC: missing");
+}
+
+#[test]
+fn native_procedures_are_called() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let register = |interpreter: &mut Interpreter<MemoryHost>| {
+        let log = log.clone();
+        interpreter.register_proc("PROClog", &[DataType::String, DataType::Integer], move |args| {
+            log.borrow_mut().push(format!("{} {}", args[0], args[1]));
+            Ok(())
+        });
+        interpreter.register_proc("PROCshadowed", &[], |_| Err(InterpreterError::Native("Native called".to_string())));
+    };
+
+    let code = "FOR i% = 1 TO 2
+    PROClog(\"step\", i% * 1.5)
+NEXT
+PROCshadowed
+END
+
+DEF PROCshadowed
+PRINT \"Own\"
+";
+
+    let (result, output) = run(code, register);
+    assert_eq!(result.unwrap(), Outcome::Ended);
+    assert_eq!(output, "Own\n");
+    assert_eq!(*log.borrow(), ["step 1", "step 3", "step 1", "step 3"]);
+
+    let (result, _) = run("PROClog(\"x\")", register);
+    assert!(matches!(result, Err(BasicError::Runtime(InterpreterError::Arguments))));
+}
+
+#[test]
+fn registered_natives_satisfy_the_checker() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    assert!(matches!(&interpreter.check("PROCgreet(\"Ada\")\nPRINT FNdistance(3, 4)").unwrap()[..],
+        [a, b] if a.problem == Problem::NoSuchProcedure("greet".to_string()) && b.problem == Problem::NoSuchProcedure("distance".to_string())));
+
+    distance(&mut interpreter);
+    interpreter.register_proc("greet", &[DataType::String], |_| Ok(()));
+    assert!(interpreter.check("PROCgreet(\"Ada\")\nPRINT FNdistance(3, 4)").unwrap().is_empty());
+}
@@ -0,0 +1,85 @@
+use bbbasic::{Interpreter, Listing, MemoryHost};
+use bbbasic::pretty::format_source;
+
+mod common;
+
+const PROGRAM: &str =
+"total%=0
+FOR i%=1 TO 4 STEP 1
+IF (i%*2)+1>5 OR i%=1 AND total%<>3 THEN
+PRINT \"big \"; i%
+ELSE
+     PROCadd(i%,\"small\")
+ENDIF
+
+
+NEXT
+n = 10
+WHILE (n > 1 OR n = 0) AND n <> 7
+n = n - (3 - 1)
+ENDWHILE
+PRINT total%; n
+END
+DEF PROCadd(x%, t$)
+total% = total% + x%
+PRINT t$
+";
+
+const FORMATTED: &str =
+"total% = 0
+FOR i% = 1 TO 4 STEP 1
+    IF i% * 2 + 1 > 5 OR i% = 1 AND total% <> 3 THEN
+        PRINT \"big \"; i%
+    ELSE
+        PROCadd(i%, \"small\")
+    ENDIF
+NEXT i%
+
+n = 10
+WHILE (n > 1 OR n = 0) AND n <> 7
+    n = n - (3 - 1)
+ENDWHILE
+PRINT total%; n
+END
+DEF PROCadd(x%, t$)
+total% = total% + x%
+PRINT t$
+";
+
+fn output(code: &str) -> String {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.run(code).expect("Program failed");
+
+    interpreter.into_host().output
+}
+
+#[test]
+fn programs_are_formatted() {
+    assert_eq!(format_source(PROGRAM).unwrap(), FORMATTED);
+}
+
+#[test]
+fn formatting_keeps_the_meaning() {
+    assert_eq!(output(FORMATTED), output(PROGRAM));
+    assert_eq!(format_source(FORMATTED).unwrap(), FORMATTED);
+}
+
+#[test]
+fn function_calls_are_formatted() {
+    assert_eq!(format_source("x=FNsize+FNarea( w*2,\"m\" )\n").unwrap(), "x = FNsize + FNarea(w * 2, \"m\")\n");
+}
+
+#[test]
+fn syntax_errors_are_reported() {
+    assert!(format_source("PRINT (1\n").is_err());
+}
+
+#[test]
+fn listings_are_formatted_line_by_line() {
+    let mut listing = Listing::new();
+    listing.set_line(10, "FOR i%=1 TO 2");
+    listing.set_line(20, "PRINT i%*(2+1)");
+    listing.set_line(30, "NEXT");
+
+    assert_eq!(listing.pretty().unwrap().to_string(), "   10 FOR i% = 1 TO 2\n   20     PRINT i% * (2 + 1)\n   30 NEXT i%\n");
+}
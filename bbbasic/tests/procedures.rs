@@ -0,0 +1,93 @@
+use bbbasic::{BasicError, Interpreter, InterpreterError, MemoryHost, Outcome};
+
+mod common;
+
+/// Runs `code` on both engines, expecting the same result and output.
+fn run(code: &str) -> (Result<Outcome, BasicError>, String) {
+    let (result, host) = common::run_on_both_engines(code, |e| Interpreter::new(MemoryHost::new()).with_engine(e));
+
+    (result, host.output)
+}
+
+#[test]
+fn procedures_are_called() {
+    let inp =
+"PRINT \"Start\"
+PROCgreet(\"World\", 2)
+PROCline
+END
+
+DEF PROCgreet(name$, n%)
+FOR i% = 1 TO n%
+    PRINT \"Hello, \"; name$
+NEXT i%
+ENDPROC
+
+DEF PROCline
+PRINT \"--\"
+";
+
+    let (result, output) = run(inp);
+    assert_eq!(result.unwrap(), Outcome::Ended);
+    assert_eq!(output, "Start\nHello, World\nHello, World\n--\n");
+}
+
+#[test]
+fn parameters_are_local() {
+    let inp =
+"x = 1
+n% = 10
+PROCcount(3)
+PRINT x; \" \"; n%
+END
+
+DEF PROCcount(n%)
+x = 2
+IF n% > 0 THEN
+    PRINT n%
+    PROCcount(n% - 1)
+ENDIF
+PRINT n%
+";
+
+    let (result, output) = run(inp);
+    assert_eq!(result.unwrap(), Outcome::Ended);
+    assert_eq!(output, "3\n2\n1\n0\n1\n2\n3\n2 10\n");
+}
+
+#[test]
+fn endproc_leaves_loops() {
+    let inp =
+"FOR i% = 1 TO 2
+    PROCfind(i%)
+NEXT i%
+END
+
+DEF PROCfind(n%)
+WHILE 1 = 1
+    FOR k% = 1 TO 10
+        IF k% = n% THEN
+            PRINT k%
+            ENDPROC
+        ENDIF
+    NEXT k%
+ENDWHILE
+";
+
+    let (result, output) = run(inp);
+    assert_eq!(result.unwrap(), Outcome::Ended);
+    assert_eq!(output, "1\n2\n");
+}
+
+#[test]
+fn calling_procedures_wrongly_fails() {
+    let (result, _) = run("PROCmissing\n");
+    assert!(matches!(result, Err(BasicError::Runtime(InterpreterError::NoSuchProcedure(n))) if n == "missing"));
+
+    let (result, _) = run("PROCp(1, 2)\nEND\nDEF PROCp(a)\nPRINT a\n");
+    assert!(matches!(result, Err(BasicError::Runtime(InterpreterError::Arguments))));
+
+    let (result, output) = run("PRINT 1\nENDPROC\n");
+    assert!(matches!(result, Err(BasicError::Runtime(InterpreterError::NotInProcedure))));
+    assert_eq!(output, "1\n");
+}
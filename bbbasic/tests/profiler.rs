@@ -0,0 +1,50 @@
+use bbbasic::{Engine, Interpreter, MemoryHost};
+
+mod common;
+
+const PROGRAM: &str =
+"s% = 0
+FOR i% = 1 TO 3
+    PROCadd(i%)
+NEXT i%
+PRINT s%
+END
+DEF PROCadd(n%)
+s% = s% + n%
+";
+
+/// Line and count of every line executed.
+fn counts(engine: Engine) -> Vec<(usize, u64)> {
+    let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine).with_profiling(true);
+    interpreter.run(PROGRAM).expect("Program failed");
+
+    let profile = interpreter.profile().expect("No profile");
+    assert_eq!(profile.hotspots(2).len(), 2);
+
+    profile.lines().iter().map(|l| (l.line, l.count)).collect()
+}
+
+#[test]
+fn statements_are_counted() {
+    let expected = vec![(1, 1), (2, 1), (3, 3), (5, 1), (6, 1), (8, 3)];
+
+    assert_eq!(counts(Engine::TreeWalker), expected);
+    assert_eq!(counts(Engine::Bytecode), expected);
+}
+
+#[test]
+fn profiling_is_off_by_default() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.run(PROGRAM).expect("Program failed");
+
+    assert!(interpreter.profile().is_none());
+}
+
+#[test]
+fn failed_programs_are_profiled() {
+    let mut interpreter = Interpreter::new(MemoryHost::new()).with_profiling(true);
+    assert!(interpreter.run("x = 1\ny = 1 / 0\n").is_err());
+
+    let lines: Vec<usize> = interpreter.profile().unwrap().lines().iter().map(|l| l.line).collect();
+    assert_eq!(lines, vec![1, 2]);
+}
@@ -0,0 +1,43 @@
+use bbbasic::{InterpreterError, Listing};
+use bbbasic::listing::Failed;
+
+mod common;
+
+const LISTING: &str =
+"5 REM START
+7 IF x THEN 5 ELSE 30
+30 ON x GOTO 5, 7,30 : GOSUB 1000
+31 PRINT \"GOTO 5\"; TOTAL
+40 RESTORE 31
+";
+
+#[test]
+fn references_are_renumbered() {
+    let mut listing = Listing::from_numbered(LISTING).unwrap();
+    let failed = listing.renumber(100, 10).unwrap();
+
+    assert_eq!(listing.numbered_source(),
+"100 REM START
+110 IF x THEN 100 ELSE 120
+120 ON x GOTO 100, 110,120 : GOSUB 1000
+130 PRINT \"GOTO 5\"; TOTAL
+140 RESTORE 130
+");
+    assert_eq!(failed, vec![Failed { line: 120, target: 1000 }]);
+}
+
+#[test]
+fn silly_numbers_are_rejected() {
+    let mut listing = Listing::from_numbered(LISTING).unwrap();
+
+    assert!(matches!(listing.renumber(10, 0), Err(InterpreterError::Silly)));
+    assert!(matches!(listing.renumber(65000, 100), Err(InterpreterError::Silly)));
+    assert_eq!(listing.line(5), Some("REM START"));
+}
+
+#[test]
+fn only_numbered_listings_are_read_as_such() {
+    assert!(Listing::from_numbered("x = 1\n").is_none());
+    assert!(Listing::from_numbered("10 x = 1\nPRINT x\n").is_none());
+    assert!(Listing::from_numbered("").is_none());
+}
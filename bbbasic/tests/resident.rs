@@ -0,0 +1,100 @@
+use bbbasic::checker::check_source;
+use bbbasic::pretty::format_source;
+use bbbasic::{Engine, Interpreter, MemoryHost, Value};
+
+mod common;
+
+fn interpreters() -> [Interpreter<MemoryHost>; 2] {
+    [Engine::TreeWalker, Engine::Bytecode].map(|engine| Interpreter::new(MemoryHost::new()).with_engine(engine))
+}
+
+#[test]
+fn resident_variables_start_at_zero() {
+    for mut interpreter in interpreters() {
+        interpreter.run("PRINT A%; \" \"; Z% + 1; \" \"; @%").expect("Program failed");
+        assert_eq!(interpreter.host().output, "0 1 0\n");
+        assert!(interpreter.run("PRINT a%").is_err());
+    }
+
+    assert!(check_source("PRINT A% + Z%\n").unwrap().is_empty());
+}
+
+#[test]
+fn resident_variables_survive_runs_and_clear() {
+    for mut interpreter in interpreters() {
+        interpreter.run("A% = 42\nB% = 2.9\nx% = 1").expect("Program failed");
+        interpreter.run("PRINT A% * B%").expect("Program failed");
+        assert!(interpreter.execute("PRINT x%").is_err());
+
+        interpreter.execute("C% = A% + 1").expect("Statement failed");
+        interpreter.clear();
+        interpreter.execute("PRINT A%; C%").expect("Statement failed");
+        assert_eq!(interpreter.host().output, "84\n4243\n");
+        assert_eq!(interpreter.variable("B%").unwrap(), Value::Integer(2));
+    }
+}
+
+#[test]
+fn procedures_restore_resident_parameters() {
+    for mut interpreter in interpreters() {
+        interpreter.run("A% = 1\nPROCshow(5)\nPRINT A%\nEND\nDEF PROCshow(A%)\nPRINT A%\n").expect("Program failed");
+        assert_eq!(interpreter.host().output, "5\n1\n");
+
+        interpreter.run("PROCshow(7)\nPRINT A%\nEND\nDEF PROCshow(A%)\nPRINT A%\n").expect("Program failed");
+        assert_eq!(interpreter.host().output, "5\n1\n7\n1\n");
+    }
+}
+
+#[test]
+fn hosts_pass_values_in_resident_variables() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    assert_eq!(interpreter.variable("Q%").unwrap(), Value::Integer(0));
+
+    interpreter.set_variable("A%", 20).unwrap();
+    interpreter.run("B% = A% + 1\nA% = 0").expect("Program failed");
+    interpreter.run("PRINT A%").expect("Program failed");
+
+    assert_eq!(interpreter.host().output, "0\n");
+    assert_eq!(interpreter.variable("B%").unwrap(), Value::Integer(21));
+}
+
+#[test]
+fn print_follows_the_format_variable() {
+    let code = "PRINT 1 / 3; 42
+@% = 2314
+PRINT 1 / 3; 42
+@% = 131594
+PRINT 1 / 3; 42
+@% = 66560
+PRINT 1500
+@% = 2304
+PRINT 0.01; \" \"; 2.5; \" \"; 1.0E10; \" \"; \"text\"
+@% = 0
+PRINT 2.5
+";
+
+    for mut interpreter in interpreters() {
+        interpreter.run(code).expect("Program failed");
+        assert_eq!(interpreter.host().output, "0.333333333333333342
+0.333333333        42
+      0.33     42.00
+1.500E3
+1E-2 2.5 1E10 text
+2.5
+");
+    }
+}
+
+#[test]
+fn format_variable_is_kept_between_runs() {
+    for mut interpreter in interpreters() {
+        interpreter.run("@% = 131075").expect("Program failed");
+        interpreter.run("PRINT 7").expect("Program failed");
+        assert_eq!(interpreter.host().output, "  7\n");
+    }
+}
+
+#[test]
+fn format_variable_is_pretty_printed() {
+    assert_eq!(format_source("@%=2314\nPRINT @%\n").unwrap(), "@% = 2314\nPRINT @%\n");
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "serde")]
+
+use bbbasic::snapshot::Snapshot;
+use bbbasic::{Interpreter, IntegerModel, InterpreterError, MemoryHost, Outcome, StepResult};
+
+mod common;
+
+const PROGRAM: &str = "total% = 0
+FOR i% = 1 TO 4
+    PROCadd(i%)
+NEXT i%
+INPUT name$
+PRINT name$; \" \"; total%
+END
+
+DEF PROCadd(n%)
+total% = total% + n%
+PRINT \"+\"; n%
+ENDPROC
+";
+
+/// Output of `PROGRAM` started on a new interpreter, interrupted after every
+/// `budget` steps and continued from a snapshot written as JSON.
+fn run_in_pieces(budget: u64) -> String {
+    let mut output = String::new();
+
+    let mut interpreter = Interpreter::new(MemoryHost::new().non_blocking());
+    interpreter.start(PROGRAM).expect("Program failed");
+
+    loop {
+        let result = interpreter.step(budget);
+        output.push_str(&interpreter.host().output);
+
+        match result {
+            StepResult::Running => {}
+            StepResult::WaitingForInput => {}
+            StepResult::Finished(outcome) => {
+                assert_eq!(outcome, Outcome::Ended);
+                return output;
+            }
+            StepResult::Error(e) => panic!("{}", e)
+        }
+
+        let json = interpreter.snapshot().expect("No program").to_json();
+
+        interpreter = Interpreter::new(MemoryHost::new().non_blocking());
+        interpreter.restore(&Snapshot::from_json(&json).unwrap()).expect("Restore failed");
+        if let StepResult::WaitingForInput = result {
+            interpreter.host_mut().input.push_back("Sum".to_string());
+        }
+    }
+}
+
+#[test]
+fn programs_continue_from_snapshots() {
+    let expected = "+1\n+2\n+3\n+4\n?Sum 10\n";
+
+    for budget in [1, 2, 3, 7, 1000] {
+        assert_eq!(run_in_pieces(budget), expected, "Budget {}", budget);
+    }
+}
+
+#[test]
+fn snapshots_keep_the_settings() {
+    let mut interpreter = Interpreter::new(MemoryHost::new())
+        .with_integer_model(IntegerModel::Acorn)
+        .with_optimization(false);
+    interpreter.start("x% = 2147483647\nx% = x% + 1\nPRINT x%").unwrap();
+    interpreter.step(1);
+    let snapshot = interpreter.snapshot().unwrap();
+
+    let mut restored = Interpreter::new(MemoryHost::new());
+    restored.restore(&snapshot).unwrap();
+    assert_eq!(restored.integer_model(), IntegerModel::Acorn);
+    assert!(matches!(restored.step(100), StepResult::Finished(Outcome::Finished)));
+    assert_eq!(restored.host().output, "-2147483648\n");
+}
+
+#[test]
+fn broken_snapshots_are_rejected() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    assert!(interpreter.snapshot().is_err());
+
+    interpreter.start("a% = 1\nPRINT a%").unwrap();
+    interpreter.step(1);
+    let json = interpreter.snapshot().unwrap().to_json();
+
+    let mut document: serde_json::Value = serde_json::from_str(&json).unwrap();
+    document["version"] = 2.into();
+    assert!(Snapshot::from_json(&document.to_string()).is_err());
+
+    let mut document: serde_json::Value = serde_json::from_str(&json).unwrap();
+    document["code"] = "b% = 1\nPRINT b%".into();
+    let snapshot = Snapshot::from_json(&document.to_string()).unwrap();
+    let error = interpreter.restore(&snapshot).unwrap_err();
+    assert_eq!(error.to_string(), "Bad snapshot");
+    assert!(!interpreter.is_running());
+}
+
+#[test]
+fn snapshots_keep_the_memory() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.start("DIM buf% 7\n!buf% = 1234567\nbuf%?4 = 9\nPRINT !buf%; \" \"; buf%?4").unwrap();
+    interpreter.step(3);
+    let json = interpreter.snapshot().unwrap().to_json();
+
+    let mut restored = Interpreter::new(MemoryHost::new());
+    restored.restore(&Snapshot::from_json(&json).unwrap()).unwrap();
+    assert!(matches!(restored.step(100), StepResult::Finished(Outcome::Finished)));
+    assert_eq!(restored.host().output, "1234567 9\n");
+}
+
+#[test]
+fn snapshots_keep_the_resident_variables() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.start("@% = 131590\nA% = 7\nPRINT A% / 2").unwrap();
+    interpreter.step(2);
+    let json = interpreter.snapshot().unwrap().to_json();
+
+    let mut restored = Interpreter::new(MemoryHost::new());
+    restored.restore(&Snapshot::from_json(&json).unwrap()).unwrap();
+    assert!(matches!(restored.step(100), StepResult::Finished(Outcome::Finished)));
+    assert_eq!(restored.host().output, "  3.50\n");
+}
+
+#[test]
+fn snapshots_continue_the_random_numbers() {
+    let code = "FOR i% = 1 TO 4\n    PRINT RND(1000)\nNEXT";
+
+    let mut whole = Interpreter::new(MemoryHost::new());
+    whole.run(code).unwrap();
+
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.start(code).unwrap();
+    interpreter.step(5);
+    let json = interpreter.snapshot().unwrap().to_json();
+    let before = interpreter.host().output.clone();
+    assert_eq!(before.lines().count(), 2);
+
+    let mut restored = Interpreter::new(MemoryHost::new());
+    restored.restore(&Snapshot::from_json(&json).unwrap()).unwrap();
+    assert!(matches!(restored.step(100), StepResult::Finished(Outcome::Finished)));
+    assert_eq!(before + &restored.host().output, whole.host().output);
+}
+
+#[test]
+fn snapshots_refuse_open_files() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.start("f% = OPENOUT \"log\"\nBPUT# f%, 65\nCLOSE# f%\nPRINT 1").unwrap();
+
+    interpreter.step(2);
+    assert!(matches!(interpreter.snapshot(), Err(InterpreterError::FilesOpen)));
+
+    interpreter.step(1);
+    assert!(interpreter.snapshot().is_ok());
+}
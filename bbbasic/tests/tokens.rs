@@ -0,0 +1,104 @@
+use bbbasic::{Interpreter, InterpreterError, Listing, MemoryHost};
+use bbbasic::tokens::{is_bbc, load_source};
+
+mod common;
+
+/// `10 PRINT "HELLO"` and `20 GOTO 10` as saved by a BBC Micro.
+const HELLO: [u8; 25] = [
+    0x0D, 0x00, 0x0A, 0x0D, 0xF1, b' ', b'"', b'H', b'E', b'L', b'L', b'O', b'"',
+    0x0D, 0x00, 0x14, 0x0A, 0xE5, b' ', 0x8D, 0x54, 0x4A, 0x40,
+    0x0D, 0xFF,
+];
+
+const PROGRAM: &str =
+"total% = 0
+FOR i% = 1 TO 3
+    IF i% > 1 THEN
+        PROCadd(i%)
+    ELSE
+        PRINT \"one · \";
+    ENDIF
+NEXT i%
+WHILE total% < 10
+    total% = total% + TIME - TIME + 1
+ENDWHILE
+PRINT total%
+END
+DEF PROCadd(n%)
+total% = total% + n%
+";
+
+#[test]
+fn tokenised_programs_are_read() {
+    let listing = Listing::from_acorn(&HELLO).unwrap();
+
+    assert_eq!(listing.numbered_source(), "10 PRINT \"HELLO\"\n20 GOTO 10\n");
+    assert_eq!(listing.to_acorn().unwrap(), HELLO);
+}
+
+#[test]
+fn tokenised_programs_run_like_their_source() {
+    let bytes = Listing::from_source(PROGRAM).to_acorn().unwrap();
+    assert!(!bytes.windows(5).any(|w| w == b"WHILE"));
+
+    let source = load_source(&bytes).unwrap();
+    assert_eq!(source, PROGRAM);
+
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.run(&source).expect("Program failed");
+    assert_eq!(interpreter.host().output, "one · 10\n");
+}
+
+#[test]
+fn broken_files_are_rejected() {
+    assert!(matches!(Listing::from_acorn(&HELLO[..20]), Err(InterpreterError::BadProgram)));
+    assert!(matches!(Listing::from_source("PRINT \"€\"").to_acorn(), Err(InterpreterError::Unrepresentable('€'))));
+    assert!(matches!(Listing::from_source(&format!("PRINT \"{}\"", "x".repeat(250))).to_acorn(), Err(InterpreterError::LineTooLong)));
+}
+
+#[test]
+fn plain_and_numbered_sources_are_loaded() {
+    assert_eq!(load_source(b"PRINT 1\n").unwrap(), "PRINT 1\n");
+    assert_eq!(load_source(b"10 PRINT 1\n20 PRINT 2\n").unwrap(), "PRINT 1\nPRINT 2\n");
+}
+
+/// `PRINT "HELLO"` and `END` as saved by BBC BASIC for Windows, without
+/// line numbers.
+const BBC_HELLO: [u8; 21] = [
+    0x0D, 0x00, 0x00, 0xF1, b' ', b'"', b'H', b'E', b'L', b'L', b'O', b'"', 0x0D,
+    0x05, 0x00, 0x00, 0xE0, 0x0D,
+    0x00, 0xFF, 0xFF,
+];
+
+#[test]
+fn bbc_programs_are_read() {
+    assert!(is_bbc(&BBC_HELLO));
+    assert!(!is_bbc(b"PRINT 1\r\n"));
+
+    let listing = Listing::load(&BBC_HELLO).unwrap();
+    assert_eq!(listing.numbered_source(), "10 PRINT \"HELLO\"\n20 END\n");
+    assert_eq!(load_source(&BBC_HELLO).unwrap(), "PRINT \"HELLO\"\nEND\n");
+}
+
+#[test]
+fn bbc_programs_run_like_their_source() {
+    let bytes = Listing::from_source(PROGRAM).to_bbc().unwrap();
+    assert!(is_bbc(&bytes));
+    assert_eq!(&bytes[bytes.len() - 3..], &[0x00, 0xFF, 0xFF]);
+
+    let source = load_source(&bytes).unwrap();
+    assert_eq!(source, PROGRAM);
+
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.run(&source).expect("Program failed");
+    assert_eq!(interpreter.host().output, "one · 10\n");
+}
+
+#[test]
+fn programs_convert_between_formats() {
+    let listing = Listing::from_source(PROGRAM);
+
+    let acorn = listing.to_acorn().unwrap();
+    let bbc = Listing::load(&acorn).unwrap().to_bbc().unwrap();
+    assert_eq!(Listing::load(&bbc).unwrap().to_acorn().unwrap(), acorn);
+}
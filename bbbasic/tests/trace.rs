@@ -0,0 +1,81 @@
+use bbbasic::{Engine, Interpreter, Listing, MemoryHost, Trace};
+
+mod common;
+
+/// Runs `code` on both engines, expecting the same output and trace.
+fn traced(interpreter: impl Fn(Engine) -> Interpreter<MemoryHost>, code: &str) -> MemoryHost {
+    let (result, host) = common::run_on_both_engines(code, interpreter);
+    result.expect("Program failed");
+
+    host
+}
+
+#[test]
+fn trace_on_prints_line_numbers() {
+    let inp =
+"PRINT \"A\"
+TRACE ON
+FOR i% = 1 TO 2
+    PRINT i%
+NEXT i%
+TRACE OFF
+PRINT \"B\"
+";
+
+    let host = traced(|e| Interpreter::new(MemoryHost::new()).with_engine(e), inp);
+    assert_eq!(host.output, "A\n[3] [4] 1\n[4] 2\n[6] B\n");
+}
+
+#[test]
+fn trace_n_prints_lines_below_n() {
+    let inp =
+"TRACE 4
+x = 1
+PROCp
+x = 2
+END
+DEF PROCp
+PRINT x
+";
+
+    let host = traced(|e| Interpreter::new(MemoryHost::new()).with_engine(e), inp);
+    assert_eq!(host.output, "[2] [3] 1\n");
+}
+
+#[test]
+fn trace_proc_prints_calls() {
+    let inp =
+"TRACE PROC
+PROCouter
+END
+DEF PROCouter
+PROCinner
+DEF PROCinner
+PRINT \"in\"
+";
+
+    let host = traced(|e| Interpreter::new(MemoryHost::new().with_separate_trace()).with_engine(e), inp);
+    assert_eq!(host.output, "in\n");
+    assert_eq!(host.trace.unwrap(), "[PROCouter] [PROCinner] [ENDPROC] [ENDPROC] ");
+}
+
+#[test]
+fn tracing_can_be_turned_on_from_outside() {
+    let host = traced(|e| Interpreter::new(MemoryHost::new().with_separate_trace()).with_engine(e).with_trace(Trace::off().lines()),
+                      "x = 1\nPRINT x\n");
+
+    assert_eq!(host.output, "1\n");
+    assert_eq!(host.trace.unwrap(), "[1] [2] ");
+}
+
+#[test]
+fn listings_trace_their_line_numbers() {
+    let listing = Listing::from_source("10 TRACE 25\n20 x = 1\n30 PRINT x\n");
+
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+        interpreter.run_listing(&listing).expect("Program failed");
+
+        assert_eq!(interpreter.host().output, "[20] 1\n");
+    }
+}
@@ -0,0 +1,63 @@
+use bbbasic::{Engine, Interpreter, InterpreterError, MemoryHost, Outcome, StepResult, Value};
+
+mod common;
+
+#[test]
+fn programs_start_with_preset_variables() {
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        let mut interpreter = Interpreter::new(MemoryHost::new()).with_engine(engine);
+        interpreter.set_variable("width", 80).unwrap();
+        interpreter.set_variable("width%", 2.7).unwrap();
+        interpreter.set_variable("width$", "wide").unwrap();
+        interpreter.set_variable("width&", "wide").unwrap_err();
+
+        interpreter.run("PRINT width; \" \"; width%; \" \"; width$\nwidth = width# / 2").unwrap();
+        assert_eq!(interpreter.host().output, "80 2 wide\n");
+
+        // Presets are set again on every run.
+        interpreter.run("PRINT width").unwrap();
+        assert_eq!(interpreter.host().output, "80 2 wide\n80\n");
+    }
+}
+
+#[test]
+fn variables_are_read_after_a_run() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    assert!(interpreter.variables().is_empty());
+
+    interpreter.run("n% = 3\nn& = 4\nn = 1.5\nn$ = \"x\"\nPROCset\nEND\nDEF PROCset\nm# = 2\n").unwrap();
+
+    assert_eq!(interpreter.variable("n%").unwrap(), Value::Integer(3));
+    assert_eq!(interpreter.variable("n#").unwrap(), Value::Float(1.5));
+    assert_eq!(interpreter.variable("m").unwrap(), Value::Float(2.0));
+    assert!(matches!(interpreter.variable("q%"), Err(InterpreterError::UnknownVariable(_))));
+    assert_eq!(interpreter.variables(), [
+        ("n%".to_string(), Value::Integer(3)),
+        ("n&".to_string(), Value::Byte(4)),
+        ("n".to_string(), Value::Float(1.5)),
+        ("n$".to_string(), Value::String("x".to_string())),
+        ("m".to_string(), Value::Float(2.0)),
+    ]);
+}
+
+#[test]
+fn started_programs_share_their_variables() {
+    let mut interpreter = Interpreter::new(MemoryHost::new());
+    interpreter.set_variable("limit%", 3).unwrap();
+    interpreter.start("FOR i% = 1 TO limit%\n    total% = i% * 10\nNEXT").unwrap();
+
+    interpreter.step(3);
+    assert_eq!(interpreter.variable("total%").unwrap(), Value::Integer(10));
+
+    assert!(matches!(interpreter.step(100), StepResult::Finished(Outcome::Finished)));
+    assert_eq!(interpreter.variable("total%").unwrap(), Value::Integer(30));
+
+    // Lines executed afterwards continue with them.
+    interpreter.set_variable("factor%", 2).unwrap();
+    interpreter.execute("PRINT total% * factor%").unwrap();
+    assert_eq!(interpreter.host().output, "60\n");
+
+    interpreter.clear_presets();
+    interpreter.run("PRINT 1").unwrap();
+    assert!(interpreter.variable("limit%").is_err());
+}
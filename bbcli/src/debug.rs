@@ -0,0 +1,181 @@
+use std::io::Write;
+use std::process::ExitCode;
+use bbbasic::{Debugger, Host, Outcome, Stop, Value};
+
+const HELP: &str = "\
+break N, b N      Stop at line N, without N list the breakpoints
+delete N, d N     Remove the breakpoint at line N
+continue, c       Run until the next breakpoint
+step, s           Run the current statement, stepping into procedures
+next, n           Run the current statement, stepping over procedures
+finish, f         Run until the current procedure returns
+print X, p X      Show variable X, like a% or name$
+set X = V         Change variable X, strings are quoted
+vars, v           Show all variables
+backtrace, bt     Show the running procedures
+list, l           Show the lines around the current one
+quit, q           Stop debugging";
+
+/// Lets the user control `debugger` with commands read from stdin. Commands
+/// are read a line at a time, so the program can read stdin in between.
+pub fn debug<H: Host>(mut debugger: Debugger<H>, code: &str) -> std::io::Result<ExitCode> {
+    let lines: Vec<&str> = code.lines().collect();
+    let output = &mut std::io::stdout();
+
+    writeln!(output, "Type \"help\" for a list of commands.")?;
+    if let Some(line) = debugger.line() {
+        show(&lines, line, output)?;
+    }
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut command = String::new();
+        if std::io::stdin().read_line(&mut command)? == 0 {
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        let command = command.trim();
+        let (verb, rest) = match command.split_once(' ') {
+            None => (command, ""),
+            Some((v, r)) => (v, r.trim())
+        };
+
+        let stop = match verb {
+            "" => continue,
+            "q" | "quit" => return Ok(ExitCode::SUCCESS),
+            "h" | "help" => {
+                writeln!(output, "{}", HELP)?;
+                continue;
+            }
+
+            "b" | "break" if rest.is_empty() => {
+                for line in debugger.breakpoints() {
+                    writeln!(output, "Breakpoint at line {}", line)?;
+                }
+                continue;
+            }
+            "b" | "break" => {
+                match rest.parse() {
+                    Ok(line) => debugger.add_breakpoint(line),
+                    Err(_) => writeln!(output, "Not a line: {}", rest)?
+                }
+                continue;
+            }
+            "d" | "delete" => {
+                if !rest.parse().map(|line| debugger.remove_breakpoint(line)).unwrap_or(false) {
+                    writeln!(output, "No breakpoint at line {}", rest)?;
+                }
+                continue;
+            }
+
+            "p" | "print" => {
+                match debugger.variable(rest) {
+                    Ok(v) => writeln!(output, "{} = {}", rest, literal(&v))?,
+                    Err(e) => writeln!(output, "{}", e)?
+                }
+                continue;
+            }
+            "set" => {
+                match rest.split_once('=') {
+                    None => writeln!(output, "Usage: set X = V")?,
+                    Some((name, value)) => match parse_value(value.trim()) {
+                        None => writeln!(output, "Not a value: {}", value.trim())?,
+                        Some(v) => if let Err(e) = debugger.set_variable(name.trim(), v) {
+                            writeln!(output, "{}", e)?;
+                        }
+                    }
+                }
+                continue;
+            }
+            "v" | "vars" => {
+                for (name, value) in debugger.variables() {
+                    writeln!(output, "{} = {}", name, literal(&value))?;
+                }
+                continue;
+            }
+            "bt" | "backtrace" => {
+                for frame in debugger.call_stack() {
+                    match frame.procedure {
+                        None => writeln!(output, "  main program, line {}", frame.line)?,
+                        Some(p) => writeln!(output, "  PROC{}, line {}", p, frame.line)?
+                    }
+                }
+                continue;
+            }
+            "l" | "list" => {
+                if let Some(line) = debugger.line() {
+                    show(&lines, line, output)?;
+                }
+                continue;
+            }
+
+            "c" | "continue" => debugger.resume(),
+            "s" | "step" => debugger.step_into(),
+            "n" | "next" => debugger.step_over(),
+            "f" | "finish" => debugger.step_out(),
+
+            _ => {
+                writeln!(output, "Unknown command: {}", verb)?;
+                continue;
+            }
+        };
+
+        match stop {
+            Stop::Breakpoint(line) => {
+                writeln!(output, "Breakpoint at line {}", line)?;
+                show(&lines, line, output)?;
+            }
+            Stop::Step(line) => show(&lines, line, output)?,
+            Stop::WaitingForInput => writeln!(output, "Waiting for input")?,
+            Stop::Finished(Outcome::Escaped) => {
+                writeln!(output, "Escape")?;
+                return Ok(ExitCode::FAILURE);
+            }
+            Stop::Finished(Outcome::StepLimitExceeded) => {
+                writeln!(output, "Step limit exceeded")?;
+                return Ok(ExitCode::FAILURE);
+            }
+            Stop::Finished(_) => {
+                writeln!(output, "Program finished")?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            Stop::Error(e) => {
+                writeln!(output, "{}", e)?;
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+}
+
+/// Shows `line` with two lines of context, marking it.
+fn show(lines: &[&str], line: usize, output: &mut dyn Write) -> std::io::Result<()> {
+    let first = line.saturating_sub(2).max(1);
+
+    for n in first..=(line + 2).min(lines.len()) {
+        let marker = if n == line { ">" } else { " " };
+        writeln!(output, "{} {:4}  {}", marker, n, lines[n - 1])?;
+    }
+
+    Ok(())
+}
+
+fn literal(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{}\"", s),
+        v => v.to_string()
+    }
+}
+
+/// Quoted text is a string, anything else a number.
+fn parse_value(text: &str) -> Option<Value> {
+    if let Some(s) = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        return Some(Value::String(s.to_string()));
+    }
+
+    match text.parse() {
+        Ok(i) => Some(Value::Integer(i)),
+        Err(_) => text.parse().ok().map(Value::Float)
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::error::Error;
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics};
+use lsp_types::request::{Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionOptions, CompletionResponse, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbol, DocumentSymbolResponse, GotoDefinitionResponse, Hover, HoverContents, HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, Position, PublishDiagnosticsParams, Range, ServerCapabilities, SymbolKind, TextDocumentSyncCapability, TextDocumentSyncKind, Url};
+use bbbasic::BasicError;
+use bbbasic::checker::check_source;
+
+/// Keywords offered for completion.
+const KEYWORDS: [&str; 40] = [
+    "AND", "BGET", "BPUT", "CLOSE", "CLS", "DEF", "DIM", "DRAW", "ELSE", "END", "ENDIF", "ENDPROC", "ENDWHILE", "EOF", "EXIT", "FN", "FOR",
+    "GET", "IF", "INKEY", "INPUT", "MOVE", "NEXT", "OFF", "ON", "OPENIN", "OPENOUT", "OR", "PLOT", "PRINT", "PROC", "RND",
+    "SOUND", "STEP", "THEN", "TIME", "TO", "TRACE", "VDU", "WHILE",
+];
+
+/// Serves the Language Server Protocol on stdin and stdout until the
+/// client shuts the server down: diagnostics from the parser and the
+/// checker, hover, go to definition of procedures, document symbols and
+/// completion.
+pub fn lsp() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    Server { connection: &connection, documents: HashMap::new() }.run()?;
+
+    // The threads end once the connection is gone.
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// Open documents by their URI.
+struct Server<'a> {
+    connection: &'a Connection,
+    documents: HashMap<Url, String>,
+}
+
+impl Server<'_> {
+    fn run(&mut self) -> Result<(), Box<dyn Error + Sync + Send>> {
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn notification(&mut self, notification: Notification) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.insert(params.text_document.uri.clone(), params.text_document.text);
+                params.text_document.uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                // Changes are always the whole document.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(params.text_document.uri.clone(), change.text);
+                }
+                params.text_document.uri
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                return Ok(());
+            }
+            _ => return Ok(())
+        };
+
+        let code = self.documents.get(&uri).map(String::as_str).unwrap_or("");
+        let params = PublishDiagnosticsParams { uri: uri.clone(), diagnostics: diagnostics(code), version: None };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(Message::Notification(notification))?;
+
+        Ok(())
+    }
+
+    fn request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => self.handle::<HoverRequest>(request, |code, params| {
+                let position = params.text_document_position_params.position;
+                hover(code, position)
+            }),
+            GotoDefinition::METHOD => self.handle::<GotoDefinition>(request, |code, params| {
+                let position = params.text_document_position_params;
+                definition(code, position.position).map(|range| {
+                    GotoDefinitionResponse::Scalar(Location { uri: position.text_document.uri, range })
+                })
+            }),
+            DocumentSymbolRequest::METHOD => self.handle::<DocumentSymbolRequest>(request, |code, _| {
+                Some(DocumentSymbolResponse::Nested(symbols(code)))
+            }),
+            Completion::METHOD => self.handle::<Completion>(request, |code, _| {
+                Some(CompletionResponse::Array(completions(code)))
+            }),
+            _ => return Response::new_err(id, lsp_server::ErrorCode::MethodNotFound as i32, format!("Unknown method {}", request.method))
+        };
+
+        match result {
+            Ok(value) => Response { id, result: Some(value), error: None },
+            Err(e) => Response::new_err(id, lsp_server::ErrorCode::InvalidParams as i32, e.to_string())
+        }
+    }
+
+    /// Answers a request on a document with what `answer` returns for its
+    /// text and the parameters.
+    fn handle<R>(&self, request: Request, answer: impl FnOnce(&str, R::Params) -> R::Result) -> Result<serde_json::Value, serde_json::Error>
+        where R: lsp_types::request::Request, R::Params: HasDocument
+    {
+        let (_, params): (RequestId, R::Params) = request.extract(R::METHOD).map_err(|e| match e {
+            lsp_server::ExtractError::JsonError { error, .. } => error,
+            lsp_server::ExtractError::MethodMismatch(_) => unreachable!("Method checked before")
+        })?;
+
+        let code = self.documents.get(params.uri()).map(String::as_str).unwrap_or("");
+        serde_json::to_value(answer(code, params))
+    }
+}
+
+/// Parameters of requests on a document.
+trait HasDocument {
+    fn uri(&self) -> &Url;
+}
+
+impl HasDocument for lsp_types::HoverParams {
+    fn uri(&self) -> &Url {
+        &self.text_document_position_params.text_document.uri
+    }
+}
+
+impl HasDocument for lsp_types::GotoDefinitionParams {
+    fn uri(&self) -> &Url {
+        &self.text_document_position_params.text_document.uri
+    }
+}
+
+impl HasDocument for lsp_types::DocumentSymbolParams {
+    fn uri(&self) -> &Url {
+        &self.text_document.uri
+    }
+}
+
+impl HasDocument for lsp_types::CompletionParams {
+    fn uri(&self) -> &Url {
+        &self.text_document_position.text_document.uri
+    }
+}
+
+/// Syntax errors and the problems the checker finds, from the position
+/// they are reported at to the end of the line.
+fn diagnostics(code: &str) -> Vec<Diagnostic> {
+    let diagnostic = |line: usize, column: usize, severity, message: String| Diagnostic {
+        range: rest_of_line(code, line.saturating_sub(1), column.saturating_sub(1)),
+        severity: Some(severity),
+        source: Some("bbcli".to_string()),
+        message,
+        ..Diagnostic::default()
+    };
+
+    match check_source(code) {
+        Ok(problems) => problems.into_iter()
+            .map(|d| diagnostic(d.position.line, d.position.column, DiagnosticSeverity::WARNING, d.problem.to_string()))
+            .collect(),
+        Err(BasicError::Parse { line, column, message }) => vec![diagnostic(line, column, DiagnosticSeverity::ERROR, message)],
+        Err(e) => vec![diagnostic(1, 1, DiagnosticSeverity::ERROR, e.to_string())]
+    }
+}
+
+fn rest_of_line(code: &str, line: usize, column: usize) -> Range {
+    let length = code.lines().nth(line).map(|l| l.chars().count()).unwrap_or(0);
+
+    Range {
+        start: Position::new(line as u32, column as u32),
+        end: Position::new(line as u32, length.max(column) as u32),
+    }
+}
+
+/// The name at `position`, with its suffix, and where it is.
+fn word_at(code: &str, position: Position) -> Option<(String, Range)> {
+    let line: Vec<char> = code.lines().nth(position.line as usize)?.chars().collect();
+    let column = (position.character as usize).min(line.len());
+
+    let mut start = column;
+    while start > 0 && is_name(line[start - 1]) {
+        start -= 1;
+    }
+    let mut end = column;
+    while end < line.len() && is_name(line[end]) {
+        end += 1;
+    }
+    if end < line.len() && "%&$#".contains(line[end]) && start < end {
+        end += 1;
+    }
+
+    if start == end {
+        return None;
+    }
+
+    let range = Range::new(Position::new(position.line, start as u32), Position::new(position.line, end as u32));
+    Some((line[start..end].iter().collect(), range))
+}
+
+fn is_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '`'
+}
+
+/// Type of the variable at `position` from its suffix, or the definition of
+/// the procedure. `FN` calls functions of the host, which the program
+/// doesn't define.
+fn hover(code: &str, position: Position) -> Option<Hover> {
+    let (word, range) = word_at(code, position)?;
+
+    let text = if let Some(name) = word.strip_prefix("PROC").filter(|n| !n.is_empty()) {
+        let (line, _) = procedures(code).into_iter().find(|(_, n)| n == name)?;
+        format!("```\n{}\n```", code.lines().nth(line)?.trim())
+    } else if let Some(name) = word.strip_prefix("FN").filter(|n| !n.is_empty()) {
+        format!("`FN{}`: function of the host", name)
+    } else if KEYWORDS.contains(&word.as_str()) || word.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    } else {
+        let data_type = match word.chars().last() {
+            Some('%') => "Integer",
+            Some('&') => "Byte",
+            Some('$') => "String",
+            _ => "Float"
+        };
+        format!("`{}`: {} variable", word, data_type)
+    };
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value: text }),
+        range: Some(range),
+    })
+}
+
+/// Where the procedure called at `position` is defined.
+fn definition(code: &str, position: Position) -> Option<Range> {
+    let (word, _) = word_at(code, position)?;
+    let name = word.strip_prefix("PROC")?;
+
+    let (line, _) = procedures(code).into_iter().find(|(_, n)| n == name)?;
+    name_range(code, line, name)
+}
+
+/// Where the name of the procedure defined on `line` is.
+fn name_range(code: &str, line: usize, name: &str) -> Option<Range> {
+    let column = code.lines().nth(line)?.find("PROC")? + 4;
+
+    Some(Range::new(Position::new(line as u32, column as u32), Position::new(line as u32, (column + name.len()) as u32)))
+}
+
+/// Lines with `DEF PROC` and the names of their procedures. Found in the
+/// text, so they are known while the program doesn't parse.
+fn procedures(code: &str) -> Vec<(usize, String)> {
+    code.lines().enumerate().filter_map(|(i, line)| {
+        let rest = line.trim_start().strip_prefix("DEF")?.trim_start().strip_prefix("PROC")?;
+        let name: String = rest.chars().take_while(|c| is_name(*c)).collect();
+
+        if name.is_empty() { None } else { Some((i, name)) }
+    }).collect()
+}
+
+/// Every procedure, up to the next one or the end of the program.
+fn symbols(code: &str) -> Vec<DocumentSymbol> {
+    let procedures = procedures(code);
+    let last_line = code.lines().count().saturating_sub(1);
+
+    procedures.iter().enumerate().map(|(i, (line, name))| {
+        let end = procedures.get(i + 1).map(|(next, _)| next - 1).unwrap_or(last_line);
+        let end_column = code.lines().nth(end).map(|l| l.chars().count()).unwrap_or(0);
+        let selection = name_range(code, *line, name).unwrap_or_default();
+
+        #[allow(deprecated)]
+        DocumentSymbol {
+            name: format!("PROC{}", name),
+            detail: None,
+            kind: SymbolKind::FUNCTION,
+            tags: None,
+            deprecated: None,
+            range: Range::new(Position::new(*line as u32, 0), Position::new(end as u32, end_column as u32)),
+            selection_range: selection,
+            children: None,
+        }
+    }).collect()
+}
+
+/// Keywords, the procedures and the variables of the program.
+fn completions(code: &str) -> Vec<CompletionItem> {
+    let item = |label: String, kind| CompletionItem { label, kind: Some(kind), ..CompletionItem::default() };
+
+    let mut items: Vec<CompletionItem> = KEYWORDS.iter().map(|k| item(k.to_string(), CompletionItemKind::KEYWORD)).collect();
+    items.extend(procedures(code).into_iter().map(|(_, name)| item(format!("PROC{}", name), CompletionItemKind::FUNCTION)));
+
+    let mut variables: Vec<String> = Vec::new();
+    for line in code.lines() {
+        // Leave out strings.
+        for part in line.split('"').step_by(2) {
+            let mut rest = part;
+            while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '`') {
+                let length = rest[start..].find(|c| !is_name(c)).unwrap_or(rest.len() - start);
+                let mut end = start + length;
+                if rest[end..].starts_with(['%', '&', '$', '#']) {
+                    end += 1;
+                }
+
+                let word = &rest[start..end];
+                let before = &rest[..start];
+                let is_variable = !KEYWORDS.contains(&word) && !word.starts_with("PROC") && !word.starts_with("FN")
+                    && !before.ends_with(|c: char| c.is_ascii_digit());
+                if is_variable && !variables.iter().any(|v| v == word) {
+                    variables.push(word.to_string());
+                }
+
+                rest = &rest[end..];
+            }
+        }
+    }
+    items.extend(variables.into_iter().map(|v| item(v, CompletionItemKind::VARIABLE)));
+
+    items
+}
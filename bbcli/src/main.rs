@@ -1,8 +1,19 @@
 use std::fs;
-use std::time::Instant;
-use clap::builder::Str;
-use bbbasic;
-use clap::Parser as Clapper;
+use std::io::Write;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use clap::{Parser as Clapper, Subcommand, ValueEnum};
+use bbbasic::ast::Program;
+use bbbasic::checker::check_source;
+use bbbasic::json;
+use bbbasic::pretty::{format_source, pretty_print};
+use bbbasic::tokens::load_source;
+use bbbasic::{Debugger, Engine, Host, IntegerModel, Interpreter, Listing, Outcome, StdHost, Trace};
+
+mod debug;
+mod lsp;
+mod profile;
+mod repl;
 
 #[derive(Clapper, Debug)]
 #[clap(author = "Stephan Smola", version = "0.1", about = "Basic Interpreter inspired by BBC Basic", long_about = None)]
@@ -16,51 +27,593 @@ struct Args {
     #[clap(short = 'c', long, default_value = "20")]
     count: u32,
 
+    /// Engine used to run the program.
+    #[clap(short = 'e', long, value_enum, default_value = "tree")]
+    engine: EngineArg,
+
+    /// Run on both engines, compare their output and timings.
+    #[clap(long, action)]
+    compare: bool,
+
+    /// Run the program exactly as written, without constant folding.
+    #[clap(long, action)]
+    no_optimize: bool,
+
+    /// Integer model: 32-bit Acorn or 64-bit BBC BASIC for SDL.
+    #[clap(short = 'i', long, value_enum, default_value = "sdl")]
+    integers: IntegersArg,
+
+    /// Stop the program after this many steps.
+    #[clap(long)]
+    max_steps: Option<u64>,
+
+    /// Print the number of every line executed, like TRACE ON. `--trace=stderr` keeps it apart from the output.
+    #[clap(long, value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "stdout")]
+    trace: Option<TraceArg>,
 
+    /// Filename of a .bbb-File, without one a prompt for typing in programs is shown.
+    filename: Option<String>,
 
-    /// Filename of a .bbb-File
-    filename: String
+    #[clap(subcommand)]
+    command: Option<Command>,
 
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a program in the debugger.
+    Debug {
+        /// Filename of a .bbb-File
+        filename: String
+    },
+
+    /// Run a program and show how often every line ran and the time spent on it.
+    Profile {
+        /// Filename of a .bbb-File
+        filename: String,
+
+        /// Number of lines in the hotspots table.
+        #[clap(long, default_value = "10")]
+        top: usize,
+
+        /// Format of the report.
+        #[clap(long, value_enum, default_value = "listing")]
+        format: ProfileFormat,
+
+        /// Write the report to this file instead of stdout.
+        #[clap(short = 'o', long)]
+        output: Option<String>,
+    },
+
+    /// Format programs in place: upper case keywords, spaces around operators, indented blocks.
+    Fmt {
+        /// Filenames of .bbb-Files
+        #[clap(required = true)]
+        filenames: Vec<String>,
+
+        /// Only check the formatting, failing if a file would change.
+        #[clap(long, action)]
+        check: bool,
+    },
+
+    /// Find mistakes like variables read before they are assigned without running the programs.
+    Check {
+        /// Filenames of programs in any format
+        #[clap(required = true)]
+        filenames: Vec<String>,
+    },
+
+    /// Print the program tree of a program.
+    Parse {
+        /// Filename of a program in any format
+        filename: String,
+
+        /// Print the tree as JSON, which bbcli runs like source when saved as .json.
+        #[clap(long, action)]
+        json: bool,
+    },
+
+    /// Serve the Language Server Protocol on stdin and stdout, for editors.
+    Lsp,
+
+    /// Renumber classic listings with line numbers in place, like RENUMBER.
+    Renumber {
+        /// Filename of a listing
+        filename: String,
+
+        /// Number of the first line.
+        #[clap(long, default_value = "10")]
+        start: u32,
+
+        /// Difference between line numbers.
+        #[clap(long, default_value = "10")]
+        step: u32,
+    },
+
+    /// Convert a program between plain text and the tokenised formats.
+    Convert {
+        /// Filename of a program in any format
+        filename: String,
+
+        /// Format to convert to.
+        #[clap(long, value_enum)]
+        to: ConvertFormat,
+
+        /// Keep the line numbers when converting to text.
+        #[clap(long, action)]
+        numbered: bool,
+
+        /// Write the program to this file instead of stdout.
+        #[clap(short = 'o', long)]
+        output: Option<String>,
+    },
+}
+
+/// Format `convert` writes.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ConvertFormat {
+    Text,
+    /// Tokenised, as saved on a BBC Micro or RISC OS.
+    Acorn,
+    /// Tokenised, as saved by BBC BASIC for Windows or BBCSDL.
+    Bbc,
+}
+
+/// Format of the `profile` report.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ProfileFormat {
+    /// The program annotated with counts and times, and the hotspots.
+    Listing,
+    Json,
+    /// Folded stacks for flame graph tools.
+    Folded,
+}
+
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum EngineArg {
+    Tree,
+    Bytecode,
+}
+
+/// Where `--trace` writes to.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TraceArg {
+    Stdout,
+    Stderr,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum IntegersArg {
+    Acorn,
+    Sdl,
+}
+
+impl From<IntegersArg> for IntegerModel {
+    fn from(i: IntegersArg) -> Self {
+        match i {
+            IntegersArg::Acorn => IntegerModel::Acorn,
+            IntegersArg::Sdl => IntegerModel::BbcSdl
+        }
+    }
+}
+
+impl Args {
+    /// Interpreter configured from the command line.
+    fn interpreter<H: Host>(&self, host: H) -> Interpreter<H> {
+        let mut interpreter = Interpreter::new(host)
+            .with_engine(self.engine.into())
+            .with_optimization(!self.no_optimize)
+            .with_integer_model(self.integers.into());
+        interpreter.set_step_limit(self.max_steps);
+
+        if self.trace.is_some() {
+            interpreter.set_trace(Trace::off().lines());
+        }
+
+        interpreter
+    }
+
+    /// Host on stdin and stdout, tracing to stderr if asked to.
+    fn stdio(&self) -> StdHost {
+        match self.trace {
+            Some(TraceArg::Stderr) => StdHost::stdio().with_trace_sink(std::io::stderr()),
+            _ => StdHost::stdio()
+        }
+    }
+}
+
+impl From<EngineArg> for Engine {
+    fn from(e: EngineArg) -> Self {
+        match e {
+            EngineArg::Tree => Engine::TreeWalker,
+            EngineArg::Bytecode => Engine::Bytecode
+        }
+    }
+}
+
+/// Source of a program file, which may also be a classic listing with line
+/// numbers, tokenised or a program tree exported as JSON. Errors are
+/// reported right away.
+fn read_program(filename: &str) -> Option<String> {
+    let bytes = match fs::read(filename) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            eprintln!("File not found: {}", filename);
+            return None;
+        }
+    };
+
+    if filename.ends_with(".json") {
+        return match json::import(&String::from_utf8_lossy(&bytes)) {
+            Ok((program, symbols)) => Some(pretty_print(&program, &symbols)),
+            Err(e) => {
+                eprintln!("{}: {}", filename, e);
+                None
+            }
+        };
+    }
+
+    match load_source(&bytes) {
+        Ok(code) => Some(code),
+        Err(e) => {
+            eprintln!("{}: {}", filename, e);
+            None
+        }
+    }
+}
+
+fn benchmark(args: &Args, filename: &str) -> ExitCode {
+    let count = args.count;
+
+    match read_program(filename) {
+        Some(code) => {
+            let mut interpreter = args.interpreter(args.stdio());
 
-fn benchmark(filename: &String, count: u32) {
-    match fs::read_to_string(filename) {
-        Ok(code) => {
             let t1 = Instant::now();
             for _ in 0..count {
-                bbbasic::execute(code.as_str());
+                if let Err(e) = interpreter.run(code.as_str()) {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
             }
             let elapsed = t1.elapsed();
 
-            println!("Running {} {} times:", filename, count);
+            println!("Running {} {} times on {:?}:", filename, count, interpreter.engine());
             println!("Total time:\t\t{:.2?}", elapsed);
             println!("Avg. single exec:\t{:.2?}", elapsed / count);
 
+            ExitCode::SUCCESS
+        }
+        None => ExitCode::FAILURE
+    }
+}
+
+/// Runs the program `count` times on an engine, capturing its output.
+fn timed_run(args: &Args, code: &str, engine: Engine) -> Result<(String, Duration), bbbasic::BasicError> {
+    let mut output = Vec::new();
+
+    let t1 = Instant::now();
+    for _ in 0..args.count {
+        output.clear();
+        args.interpreter(StdHost::new(std::io::empty(), &mut output)).with_engine(engine).run(code)?;
+    }
+    let elapsed = t1.elapsed();
+
+    Ok((String::from_utf8_lossy(&output).to_string(), elapsed))
+}
+
+fn compare(args: &Args, filename: &str) -> ExitCode {
+    let count = args.count;
+
+    let code = match read_program(filename) {
+        Some(code) => code,
+        None => return ExitCode::FAILURE
+    };
+
+    let mut results = Vec::new();
+
+    for engine in [Engine::TreeWalker, Engine::Bytecode] {
+        match timed_run(args, &code, engine) {
+            Ok(r) => results.push((engine, r)),
+            Err(e) => {
+                eprintln!("{:?}: {}", engine, e);
+                return ExitCode::FAILURE;
+            }
         }
+    }
+
+    println!("Running {} {} times:", filename, count);
+    for (engine, (_, elapsed)) in &results {
+        println!("{:?}:\tTotal {:.2?}\tAvg. {:.2?}", engine, elapsed, *elapsed / count);
+    }
+
+    if results[0].1.0 == results[1].1.0 {
+        println!("Output is identical");
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("Output differs");
+        ExitCode::FAILURE
+    }
+}
+
+fn run(args: &Args, filename: &str) -> ExitCode {
+    match read_program(filename) {
+        Some(code) => match args.interpreter(args.stdio()).run(code.as_str()) {
+            Ok(Outcome::Escaped) => {
+                eprintln!("Escape");
+                ExitCode::FAILURE
+            }
+            Ok(Outcome::StepLimitExceeded) => {
+                eprintln!("Step limit exceeded");
+                ExitCode::FAILURE
+            }
+            Ok(_) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        }
+        None => ExitCode::FAILURE
+    }
+}
+
+fn debug(args: &Args, filename: &str) -> ExitCode {
+    let code = match read_program(filename) {
+        Some(code) => code,
+        None => return ExitCode::FAILURE
+    };
+
+    // The debugger only works on the bytecode engine.
+    let debugger = match Debugger::new(args.interpreter(args.stdio()), &code) {
+        Ok(d) => d,
         Err(e) => {
-            eprintln!("File not found: {}", filename);
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match debug::debug(debugger, &code) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn profile(args: &Args, filename: &str, top: usize, format: ProfileFormat, output: Option<&str>) -> ExitCode {
+    let code = match read_program(filename) {
+        Some(code) => code,
+        None => return ExitCode::FAILURE
+    };
+
+    let mut interpreter = args.interpreter(args.stdio()).with_profiling(true);
+    let result = interpreter.run(&code);
+    if let Err(e) = &result {
+        eprintln!("{}", e);
+    }
+
+    // Failed programs are profiled up to the error.
+    let Some(profile) = interpreter.profile() else {
+        return ExitCode::FAILURE;
+    };
+
+    let mut report = Vec::new();
+    let written = match format {
+        ProfileFormat::Listing => profile::listing(profile, &code, top, &mut report),
+        ProfileFormat::Json => writeln!(report, "{}", profile.to_json()),
+        ProfileFormat::Folded => write!(report, "{}", profile.folded()),
+    };
+
+    let written = written.and_then(|_| match output {
+        Some(file) => fs::write(file, &report),
+        None => std::io::stdout().write_all(&report)
+    });
+
+    match (written, result) {
+        (Err(e), _) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+        (Ok(_), Ok(Outcome::Finished | Outcome::Ended)) => ExitCode::SUCCESS,
+        (Ok(_), _) => ExitCode::FAILURE
+    }
+}
+
+fn fmt(filenames: &[String], check: bool) -> ExitCode {
+    let mut result = ExitCode::SUCCESS;
+
+    for filename in filenames {
+        let code = match fs::read_to_string(filename) {
+            Ok(code) => code,
+            Err(_) => {
+                eprintln!("File not found: {}", filename);
+                result = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let formatted = match format_source(&code) {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("{}: {}", filename, e);
+                result = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        if formatted == code {
+            continue;
+        }
+
+        if check {
+            println!("{} is not formatted", filename);
+            result = ExitCode::FAILURE;
+        } else if let Err(e) = fs::write(filename, formatted) {
+            eprintln!("{}: {}", filename, e);
+            result = ExitCode::FAILURE;
+        }
+    }
+
+    result
+}
+
+fn parse(filename: &str, as_json: bool) -> ExitCode {
+    let Some(code) = read_program(filename) else {
+        return ExitCode::FAILURE;
+    };
+
+    let tree = if as_json {
+        json::export_source(&code)
+    } else {
+        Program::parse_source(&code).map(|(program, _)| format!("{:#?}", program))
+    };
+
+    match tree {
+        Ok(tree) => {
+            println!("{}", tree);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}: {}", filename, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn check(filenames: &[String]) -> ExitCode {
+    let mut result = ExitCode::SUCCESS;
+
+    for filename in filenames {
+        let Some(code) = read_program(filename) else {
+            result = ExitCode::FAILURE;
+            continue;
+        };
+
+        match check_source(&code) {
+            // Unknown procedures and functions may be natives of the host
+            // running the program.
+            Ok(diagnostics) => for d in &diagnostics {
+                if d.problem.is_warning() {
+                    println!("{}:{}:{}: warning: {}", filename, d.position.line, d.position.column, d.problem);
+                } else {
+                    println!("{}:{}", filename, d);
+                    result = ExitCode::FAILURE;
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", filename, e);
+                result = ExitCode::FAILURE;
+            }
         }
     }
+
+    result
 }
 
-fn run(filename: &String) {
-    match fs::read_to_string(filename) {
-        Ok(code) => {
-            bbbasic::execute(code.as_str());
+fn renumber(filename: &str, start: u32, step: u32) -> ExitCode {
+    let code = match fs::read_to_string(filename) {
+        Ok(code) => code,
+        Err(_) => {
+            eprintln!("File not found: {}", filename);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut listing = match Listing::from_numbered(&code) {
+        Some(l) => l,
+        None => {
+            eprintln!("{} has no line numbers", filename);
+            return ExitCode::FAILURE;
         }
+    };
+
+    match listing.renumber(start, step) {
+        Ok(failed) => for f in failed {
+            eprintln!("Failed at {}: no line {}", f.line, f.target);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match fs::write(filename, listing.numbered_source()) {
+        Ok(_) => ExitCode::SUCCESS,
         Err(e) => {
+            eprintln!("{}: {}", filename, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn convert(filename: &str, to: ConvertFormat, numbered: bool, output: Option<&str>) -> ExitCode {
+    let listing = match fs::read(filename) {
+        Ok(bytes) => Listing::load(&bytes),
+        Err(_) => {
             eprintln!("File not found: {}", filename);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let converted = listing.and_then(|listing| match to {
+        ConvertFormat::Text if numbered => Ok(listing.numbered_source().into_bytes()),
+        ConvertFormat::Text => Ok(listing.source().into_bytes()),
+        ConvertFormat::Acorn => listing.to_acorn(),
+        ConvertFormat::Bbc => listing.to_bbc(),
+    });
+
+    let converted = match converted {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}: {}", filename, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let written = match output {
+        Some(file) => fs::write(file, &converted),
+        None => std::io::stdout().write_all(&converted)
+    };
+
+    match written {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
         }
     }
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = Args::parse();
 
-    if args.benchmark {
-        benchmark(&args.filename, args.count)
+    let filename = match (&args.command, &args.filename) {
+        (Some(Command::Debug { filename }), _) => return debug(&args, filename),
+        (Some(Command::Profile { filename, top, format, output }), _) =>
+            return profile(&args, filename, *top, *format, output.as_deref()),
+        (Some(Command::Fmt { filenames, check }), _) => return fmt(filenames, *check),
+        (Some(Command::Check { filenames }), _) => return check(filenames),
+        (Some(Command::Parse { filename, json }), _) => return parse(filename, *json),
+        (Some(Command::Lsp), _) => return match lsp::lsp() {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        },
+        (Some(Command::Renumber { filename, start, step }), _) => return renumber(filename, *start, *step),
+        (Some(Command::Convert { filename, to, numbered, output }), _) => return convert(filename, *to, *numbered, output.as_deref()),
+        (None, Some(filename)) => filename,
+        (None, None) => return repl::repl(args.interpreter(args.stdio())),
+    };
+
+    if args.compare {
+        compare(&args, filename)
+    } else if args.benchmark {
+        benchmark(&args, filename)
     } else {
-        run(&args.filename);
+        run(&args, filename)
     }
 }
\ No newline at end of file
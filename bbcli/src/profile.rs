@@ -0,0 +1,41 @@
+use std::io::Write;
+use std::time::Duration;
+use bbbasic::Profile;
+
+/// Writes `code` annotated with how often every line ran and the time spent
+/// on it, followed by the `top` lines the most time was spent on.
+pub fn listing(profile: &Profile, code: &str, top: usize, output: &mut dyn Write) -> std::io::Result<()> {
+    let total = profile.total_time();
+    let lines = profile.lines();
+    let mut samples = lines.iter().peekable();
+
+    writeln!(output, "{:>6} {:>10} {:>12} {:>6}  Source", "Line", "Count", "Time", "%")?;
+    for (i, source) in code.lines().enumerate() {
+        let n = i + 1;
+
+        match samples.next_if(|l| l.line == n) {
+            Some(l) => writeln!(output, "{:>6} {:>10} {:>12} {:>6}  {}",
+                                n, l.count, format!("{:.2?}", l.time), percent(l.time, total), source)?,
+            None => writeln!(output, "{:>6} {:>10} {:>12} {:>6}  {}", n, "", "", "", source)?
+        }
+    }
+
+    writeln!(output)?;
+    writeln!(output, "Top {} lines, {:.2?} in total:", top, total)?;
+    writeln!(output, "{:>6} {:>10} {:>12} {:>6}  Source", "Line", "Count", "Time", "%")?;
+    for l in profile.hotspots(top) {
+        let source = code.lines().nth(l.line.wrapping_sub(1)).unwrap_or("").trim();
+        writeln!(output, "{:>6} {:>10} {:>12} {:>6}  {}",
+                 l.line, l.count, format!("{:.2?}", l.time), percent(l.time, total), source)?;
+    }
+
+    Ok(())
+}
+
+fn percent(time: Duration, total: Duration) -> String {
+    if total.is_zero() {
+        return "-".to_string();
+    }
+
+    format!("{:.1}", time.as_secs_f64() * 100.0 / total.as_secs_f64())
+}
@@ -0,0 +1,195 @@
+use std::fs;
+use std::process::ExitCode;
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+use bbbasic::{BasicError, Host, Interpreter, InterpreterError, Listing, Outcome};
+use bbbasic::listing::{split_line_number, MAX_LINE_NUMBER};
+
+/// Program being edited at the prompt, with the one `NEW` dropped so `OLD`
+/// can bring it back.
+struct Session<H: Host> {
+    interpreter: Interpreter<H>,
+    listing: Listing,
+    old: Option<Listing>,
+    /// Number and step of the next line while `AUTO` is on.
+    auto: Option<(u32, u32)>,
+}
+
+/// Reads lines at a `>` prompt like a BBC Micro. Lines starting with a
+/// number edit the program, commands like `RUN` and `LIST` work on it, and
+/// anything else is executed right away on the variables the program and
+/// earlier lines left.
+pub fn repl<H: Host>(interpreter: Interpreter<H>) -> ExitCode {
+    let mut editor = match DefaultEditor::new() {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut session = Session { interpreter, listing: Listing::new(), old: None, auto: None };
+
+    loop {
+        let prompt = match session.auto {
+            None => ">".to_string(),
+            Some((number, _)) => format!("{:>5} ", number)
+        };
+
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                session.auto = None;
+                println!("Escape");
+                continue;
+            }
+            Err(ReadlineError::Eof) => return ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        if !session.enter(line.trim_end()) {
+            return ExitCode::SUCCESS;
+        }
+    }
+}
+
+impl<H: Host> Session<H> {
+    /// Handles a line typed at the prompt, `false` once the user quits.
+    fn enter(&mut self, line: &str) -> bool {
+        if let Some((number, step)) = self.auto {
+            // Like on a BBC Micro AUTO also stops with Escape, an empty line
+            // is easier to type. It leaves the line offered alone.
+            if line.trim().is_empty() {
+                self.auto = None;
+                return true;
+            }
+
+            self.listing.set_line(number, line);
+            self.old = None;
+
+            self.auto = match number.checked_add(step) {
+                Some(next) if next <= MAX_LINE_NUMBER => Some((next, step)),
+                _ => None
+            };
+            return true;
+        }
+
+        if let Some((number, text)) = split_line_number(line.trim_start()) {
+            self.listing.set_line(number, text);
+            self.old = None;
+            return true;
+        }
+
+        let (command, argument) = match line.trim().split_once(' ') {
+            None => (line.trim(), ""),
+            Some((c, a)) => (c, a.trim())
+        };
+
+        match command {
+            "" => {}
+            "QUIT" | "BYE" => return false,
+            "RUN" => {
+                let result = self.interpreter.run_listing(&self.listing);
+                self.report(result, true);
+            }
+            "LIST" => match self.listing.pretty() {
+                Ok(listing) => print!("{}", listing),
+                Err(_) => print!("{}", self.listing)
+            }
+            "NEW" => {
+                if !self.listing.is_empty() {
+                    self.old = Some(std::mem::take(&mut self.listing));
+                }
+            }
+            "OLD" => match self.old.take() {
+                Some(old) if self.listing.is_empty() => self.listing = old,
+                Some(old) => {
+                    self.old = Some(old);
+                    println!("Program not empty");
+                }
+                None => println!("No old program"),
+            }
+            "CLEAR" => self.interpreter.clear(),
+            "RENUMBER" => match numbers(argument) {
+                None => println!("{}", InterpreterError::Silly),
+                Some((start, step)) => match self.listing.renumber(start, step) {
+                    Ok(failed) => for f in failed {
+                        println!("Failed at {}", f.line);
+                    }
+                    Err(e) => println!("{}", e)
+                }
+            }
+            "AUTO" => match numbers(argument) {
+                Some((start, step)) if start <= MAX_LINE_NUMBER => self.auto = Some((start, step)),
+                _ => println!("{}", InterpreterError::Silly)
+            }
+            "SAVE" => match filename(argument) {
+                None => println!("Usage: SAVE \"file\""),
+                Some(f) => if let Err(e) = fs::write(f, self.listing.numbered_source()) {
+                    println!("{}", e);
+                }
+            }
+            "LOAD" => match filename(argument) {
+                None => println!("Usage: LOAD \"file\""),
+                Some(f) => match fs::read(f) {
+                    Ok(bytes) => match Listing::load(&bytes) {
+                        Ok(listing) => {
+                            self.listing = listing;
+                            self.old = None;
+                        }
+                        Err(e) => println!("{}", e)
+                    }
+                    Err(_) => println!("File not found: {}", f)
+                }
+            }
+            _ => {
+                let result = self.interpreter.execute(&format!("{}\n", line));
+                self.report(result, false);
+            }
+        }
+
+        true
+    }
+
+    /// Reports how the program or a line ended. Syntax errors in the
+    /// program are reported with the line numbers of the listing.
+    fn report(&self, result: Result<Outcome, BasicError>, in_program: bool) {
+        match result {
+            Ok(Outcome::Escaped) => println!("Escape"),
+            Ok(Outcome::StepLimitExceeded) => println!("Step limit exceeded"),
+            Ok(_) => {}
+            Err(BasicError::Parse { line, message, .. }) => match in_program {
+                true => println!("Syntax error at line {}: {}", line, message),
+                false => println!("Syntax error: {}", message)
+            }
+            Err(e) => println!("{}", e)
+        }
+    }
+}
+
+/// `"file"` or `file`, as given to `SAVE` and `LOAD`.
+fn filename(argument: &str) -> Option<&str> {
+    let name = argument.strip_prefix('"').and_then(|a| a.strip_suffix('"')).unwrap_or(argument);
+
+    if name.is_empty() { None } else { Some(name) }
+}
+
+/// `start,step` as given to `RENUMBER` and `AUTO`, both 10 if left out.
+fn numbers(argument: &str) -> Option<(u32, u32)> {
+    let (start, step) = match argument.split_once(',') {
+        None => (argument, ""),
+        Some((start, step)) => (start, step)
+    };
+
+    let number = |n: &str| if n.trim().is_empty() { Some(10) } else { n.trim().parse().ok() };
+    let (start, step) = (number(start)?, number(step)?);
+
+    if start == 0 || step == 0 { None } else { Some((start, step)) }
+}
@@ -0,0 +1,145 @@
+//! Drives `bbcli lsp` like an editor would, over its stdin and stdout.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use serde_json::{json, Value};
+
+const URI: &str = "file:///tmp/count.bbb";
+
+const PROGRAM: &str = "total% = 0
+FOR i% = 1 TO 3
+    PROCadd(i%)
+NEXT i%
+PRINT totl%
+END
+DEF PROCadd(n%)
+total% = total% + n%
+";
+
+/// A client speaking JSON-RPC with `Content-Length` headers.
+struct Client {
+    server: Child,
+    input: ChildStdin,
+    output: BufReader<ChildStdout>,
+    id: u64,
+}
+
+impl Client {
+    fn start() -> Client {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_bbbcli"))
+            .arg("lsp")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Server didn't start");
+
+        let input = server.stdin.take().unwrap();
+        let output = BufReader::new(server.stdout.take().unwrap());
+
+        Client { server, input, output, id: 0 }
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.input, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.input.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            self.output.read_line(&mut header).unwrap();
+            match header.trim_end() {
+                "" => break,
+                h => if let Some(l) = h.strip_prefix("Content-Length: ") {
+                    length = l.parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; length];
+        self.output.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Sends a request and returns the result of its response, skipping
+    /// notifications.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": self.id, "method": method, "params": params }));
+
+        loop {
+            let message = self.receive();
+            if message["id"] == self.id {
+                return message["result"].clone();
+            }
+        }
+    }
+
+    fn notification(&mut self, method: &str) -> Value {
+        loop {
+            let message = self.receive();
+            if message["method"] == method {
+                return message["params"].clone();
+            }
+        }
+    }
+}
+
+fn position(line: u32, character: u32) -> Value {
+    json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+}
+
+#[test]
+fn editors_are_served() {
+    let mut client = Client::start();
+
+    let result = client.request("initialize", json!({ "capabilities": {} }));
+    assert_eq!(result["capabilities"]["hoverProvider"], true);
+    client.notify("initialized", json!({}));
+
+    client.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": URI, "languageId": "bbcbasic", "version": 1, "text": PROGRAM }
+    }));
+    let diagnostics = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(diagnostics["diagnostics"].as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics["diagnostics"][0]["message"], "totl% is read before it is assigned");
+    assert_eq!(diagnostics["diagnostics"][0]["range"]["start"], json!({ "line": 4, "character": 0 }));
+
+    let hover = client.request("textDocument/hover", position(1, 5));
+    assert_eq!(hover["contents"]["value"], "`i%`: Integer variable");
+
+    let hover = client.request("textDocument/hover", position(2, 8));
+    assert_eq!(hover["contents"]["value"], "```\nDEF PROCadd(n%)\n```");
+
+    let definition = client.request("textDocument/definition", position(2, 6));
+    assert_eq!(definition["uri"], URI);
+    assert_eq!(definition["range"]["start"], json!({ "line": 6, "character": 8 }));
+
+    let symbols = client.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": URI } }));
+    assert_eq!(symbols[0]["name"], "PROCadd");
+    assert_eq!(symbols[0]["range"]["end"]["line"], 7);
+
+    let completions = client.request("textDocument/completion", position(4, 0));
+    let labels: Vec<&str> = completions.as_array().unwrap().iter().map(|c| c["label"].as_str().unwrap()).collect();
+    assert!(labels.contains(&"ENDWHILE"));
+    assert!(labels.contains(&"PROCadd"));
+    assert!(labels.contains(&"total%"));
+    assert!(!labels.contains(&"add"));
+
+    client.notify("textDocument/didChange", json!({
+        "textDocument": { "uri": URI, "version": 2 },
+        "contentChanges": [{ "text": "PRINT (1\n" }]
+    }));
+    let diagnostics = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(diagnostics["diagnostics"][0]["severity"], 1);
+
+    client.request("shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.server.wait().unwrap().success());
+}
@@ -0,0 +1,65 @@
+//! Types lines at the `>` prompt of `bbcli`, through its stdin.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Everything `bbcli` prints for the lines in `input`. Prompts aren't
+/// shown when stdin isn't a terminal.
+fn repl(input: &str) -> String {
+    let mut repl = Command::new(env!("CARGO_BIN_EXE_bbbcli"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("REPL didn't start");
+
+    repl.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = repl.wait_with_output().unwrap();
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn an_empty_line_stops_auto_without_deleting() {
+    let output = repl("10 PRINT 1\n20 PRINT 2\nAUTO 20\n\nLIST\n");
+
+    assert_eq!(output, "   10 PRINT 1\n   20 PRINT 2\n");
+}
+
+#[test]
+fn auto_numbers_the_lines_typed() {
+    let output = repl("AUTO 100,5\nPRINT 1\nPRINT 2\n\nLIST\n");
+
+    assert_eq!(output, "  100 PRINT 1\n  105 PRINT 2\n");
+}
+
+#[test]
+fn run_traces_the_numbers_of_the_listing() {
+    let output = repl("10 TRACE ON\n20 x = 1\n30 PRINT x\nRUN\n");
+
+    assert_eq!(output, "[20] [30] 1\n");
+}
+
+#[test]
+fn syntax_errors_name_the_line_of_the_listing() {
+    let output = repl("10 PRINT 1\n20 PRINT (1 +\nRUN\n");
+
+    assert!(output.starts_with("Syntax error at line 20:"), "{}", output);
+}
+
+#[test]
+fn renumbered_trace_limits_follow_the_listing() {
+    let output = repl("10 TRACE 30\n20 x = 1\n30 PRINT x\nRENUMBER 100,100\nRUN\n");
+
+    assert_eq!(output, "[200] 1\n");
+}
+
+#[test]
+fn save_keeps_the_line_numbers() {
+    let file = std::env::temp_dir().join(format!("bbcli-save-{}.bbb", std::process::id()));
+    let file = file.display();
+
+    let output = repl(&format!("100 PRINT 1\n150 PRINT 2\nSAVE \"{file}\"\nNEW\nLOAD \"{file}\"\nLIST\n"));
+    let _ = std::fs::remove_file(file.to_string());
+
+    assert_eq!(output, "  100 PRINT 1\n  150 PRINT 2\n");
+}